@@ -61,7 +61,9 @@ where
     let mut l1_provider = OracleL1ChainProvider::new(boot.l1_head, oracle.clone());
     let mut l2_provider =
         OracleL2ChainProvider::new(safe_head_hash, rollup_config.clone(), oracle.clone());
-    let beacon = OracleBlobProvider::new(oracle.clone());
+    let beacon = OracleBlobProvider::new(oracle.clone())
+        .with_bulk_blobs(cfg!(feature = "bulk-blobs"))
+        .with_blob_verification(cfg!(feature = "blob-verification"));
 
     // Set the active L2 chain ID for the L2 provider.
     l2_provider.set_chain_id(boot.agreed_pre_state.active_l2_chain_id());
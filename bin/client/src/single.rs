@@ -1,12 +1,17 @@
 //! Single-chain fault proof program entrypoint.
 
 use alloc::sync::Arc;
-use alloy_consensus::Sealed;
+use alloy_consensus::{Header, Sealed};
 use alloy_primitives::B256;
-use core::fmt::Debug;
-use kona_derive::errors::PipelineErrorKind;
-use kona_driver::{Driver, DriverError};
+use core::{fmt::Debug, ops::ControlFlow};
+use kona_derive::{
+    errors::PipelineErrorKind,
+    traits::{Pipeline, SignalReceiver},
+};
+use kona_driver::{Driver, DriverError, DriverPipeline, DriverResult, Executor};
 use kona_executor::{ExecutorError, KonaHandleRegister, TrieDBProvider};
+use kona_genesis::RollupConfig;
+use kona_mpt::TrieNodeCache;
 use kona_preimage::{CommsClient, HintWriterClient, PreimageKey, PreimageOracleClient};
 use kona_proof::{
     BootInfo, CachingOracle, HintType,
@@ -16,6 +21,8 @@ use kona_proof::{
     l2::OracleL2ChainProvider,
     sync::new_pipeline_cursor,
 };
+use kona_protocol::L2BlockInfo;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{error, info};
 
@@ -36,6 +43,48 @@ pub enum FaultProofProgramError {
     Driver(#[from] DriverError<ExecutorError>),
 }
 
+/// A phase of the fault proof program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProgramPhase {
+    /// Loading the boot information and the agreed upon safe head.
+    Prologue,
+    /// Deriving and executing the L2 blocks up to the claimed L2 block.
+    DerivationAndExecution,
+    /// Validating the output root of the claimed L2 block.
+    Epilogue,
+}
+
+/// An observer of the [ProgramPhase]s of the fault proof program, notified as the program enters
+/// each phase.
+pub trait ProgramPhaseObserver {
+    /// Called when the program enters the given [ProgramPhase].
+    fn enter(&self, phase: ProgramPhase);
+}
+
+impl ProgramPhaseObserver for () {
+    fn enter(&self, _: ProgramPhase) {}
+}
+
+/// An observer of the L2 blocks derived and executed by the fault proof program, notified after
+/// each block is executed.
+pub trait BlockObserver {
+    /// Called with the [L2BlockInfo], the sealed [Header] and the output root of each L2 block
+    /// once it is executed. Returning [ControlFlow::Break] halts derivation at the block.
+    fn on_block(
+        &self,
+        block: &L2BlockInfo,
+        header: &Sealed<Header>,
+        output_root: B256,
+    ) -> ControlFlow<()>;
+}
+
+impl BlockObserver for () {
+    fn on_block(&self, _: &L2BlockInfo, _: &Sealed<Header>, _: B256) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
 /// Executes the fault proof program with the given [PreimageOracleClient] and [HintWriterClient].
 #[inline]
 pub async fn run<P, H>(
@@ -51,13 +100,84 @@ pub async fn run<P, H>(
 where
     P: PreimageOracleClient + Send + Sync + Debug + Clone,
     H: HintWriterClient + Send + Sync + Debug + Clone,
+{
+    run_observed(oracle_client, hint_client, handle_register, ()).await
+}
+
+/// Executes the fault proof program with the given [PreimageOracleClient] and [HintWriterClient],
+/// deriving and executing the L2 blocks one at a time, and notifying the given [BlockObserver]
+/// after each of them.
+///
+/// If the [BlockObserver] halts derivation before the claimed L2 block, the output root of the
+/// last executed block is validated against the claim.
+pub async fn run_with_block_observer<P, H, B>(
+    oracle_client: P,
+    hint_client: H,
+    handle_register: Option<
+        KonaHandleRegister<
+            OracleL2ChainProvider<CachingOracle<P, H>>,
+            OracleL2ChainProvider<CachingOracle<P, H>>,
+        >,
+    >,
+    block_observer: B,
+) -> Result<(), FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone,
+    H: HintWriterClient + Send + Sync + Debug + Clone,
+    B: BlockObserver,
+{
+    run_inner(oracle_client, hint_client, handle_register, (), Some(block_observer)).await
+}
+
+/// Executes the fault proof program with the given [PreimageOracleClient] and [HintWriterClient],
+/// notifying the given [ProgramPhaseObserver] as the program enters each [ProgramPhase].
+pub async fn run_observed<P, H, O>(
+    oracle_client: P,
+    hint_client: H,
+    handle_register: Option<
+        KonaHandleRegister<
+            OracleL2ChainProvider<CachingOracle<P, H>>,
+            OracleL2ChainProvider<CachingOracle<P, H>>,
+        >,
+    >,
+    observer: O,
+) -> Result<(), FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone,
+    H: HintWriterClient + Send + Sync + Debug + Clone,
+    O: ProgramPhaseObserver,
+{
+    run_inner::<_, _, _, ()>(oracle_client, hint_client, handle_register, observer, None).await
+}
+
+/// Executes the fault proof program, notifying the [ProgramPhaseObserver] as the program enters
+/// each [ProgramPhase], and the [BlockObserver], if any, after each executed L2 block.
+async fn run_inner<P, H, O, B>(
+    oracle_client: P,
+    hint_client: H,
+    handle_register: Option<
+        KonaHandleRegister<
+            OracleL2ChainProvider<CachingOracle<P, H>>,
+            OracleL2ChainProvider<CachingOracle<P, H>>,
+        >,
+    >,
+    observer: O,
+    block_observer: Option<B>,
+) -> Result<(), FaultProofProgramError>
+where
+    P: PreimageOracleClient + Send + Sync + Debug + Clone,
+    H: HintWriterClient + Send + Sync + Debug + Clone,
+    O: ProgramPhaseObserver,
+    B: BlockObserver,
 {
     const ORACLE_LRU_SIZE: usize = 1024;
+    const TRIE_NODE_CACHE_SIZE: usize = 16 * 1024 * 1024;
 
     ////////////////////////////////////////////////////////////////
     //                          PROLOGUE                          //
     ////////////////////////////////////////////////////////////////
 
+    observer.enter(ProgramPhase::Prologue);
     let oracle = Arc::new(CachingOracle::new(ORACLE_LRU_SIZE, oracle_client, hint_client));
     let boot = BootInfo::load(oracle.as_ref()).await?;
     let rollup_config = Arc::new(boot.rollup_config);
@@ -66,7 +186,10 @@ where
     let mut l1_provider = OracleL1ChainProvider::new(boot.l1_head, oracle.clone());
     let mut l2_provider =
         OracleL2ChainProvider::new(safe_head_hash, rollup_config.clone(), oracle.clone());
-    let beacon = OracleBlobProvider::new(oracle.clone());
+    l2_provider.set_trie_node_cache(TrieNodeCache::new(TRIE_NODE_CACHE_SIZE));
+    let beacon = OracleBlobProvider::new(oracle.clone())
+        .with_bulk_blobs(cfg!(feature = "bulk-blobs"))
+        .with_blob_verification(cfg!(feature = "blob-verification"));
 
     // Fetch the safe head's block header.
     let safe_head = l2_provider
@@ -102,6 +225,8 @@ where
     //                   DERIVATION & EXECUTION                   //
     ////////////////////////////////////////////////////////////////
 
+    observer.enter(ProgramPhase::DerivationAndExecution);
+
     // Create a new derivation driver with the given boot information and oracle.
     let cursor =
         new_pipeline_cursor(rollup_config.as_ref(), safe_head, &mut l1_provider, &mut l2_provider)
@@ -128,14 +253,29 @@ where
 
     // Run the derivation pipeline until we are able to produce the output root of the claimed
     // L2 block.
-    let (safe_head, output_root) = driver
-        .advance_to_target(rollup_config.as_ref(), Some(boot.claimed_l2_block_number))
-        .await?;
+    let (safe_head, output_root) = match block_observer {
+        Some(block_observer) => {
+            advance_observed(
+                &mut driver,
+                rollup_config.as_ref(),
+                boot.claimed_l2_block_number,
+                &block_observer,
+            )
+            .await?
+        }
+        None => {
+            driver
+                .advance_to_target(rollup_config.as_ref(), Some(boot.claimed_l2_block_number))
+                .await?
+        }
+    };
 
     ////////////////////////////////////////////////////////////////
     //                          EPILOGUE                          //
     ////////////////////////////////////////////////////////////////
 
+    observer.enter(ProgramPhase::Epilogue);
+
     if output_root != boot.claimed_l2_output_root {
         error!(
             target: "client",
@@ -156,6 +296,44 @@ where
     Ok(())
 }
 
+/// Advances the [Driver] to the target block number one L2 block at a time, notifying the
+/// [BlockObserver] after each block, until the target is reached, the data source is exhausted or
+/// the [BlockObserver] halts derivation.
+async fn advance_observed<E, DP, P, B>(
+    driver: &mut Driver<E, DP, P>,
+    cfg: &RollupConfig,
+    target: u64,
+    block_observer: &B,
+) -> DriverResult<(L2BlockInfo, B256), E::Error>
+where
+    E: Executor + Send + Sync + Debug,
+    DP: DriverPipeline<P> + Send + Sync + Debug,
+    P: Pipeline + SignalReceiver + Send + Sync + Debug,
+    B: BlockObserver,
+{
+    loop {
+        let (safe_head, output_root) = {
+            let cursor = driver.cursor.read();
+            (*cursor.l2_safe_head(), *cursor.l2_safe_head_output_root())
+        };
+        if safe_head.block_info.number >= target {
+            return Ok((safe_head, output_root));
+        }
+
+        let (next_head, next_output_root) =
+            driver.advance_to_target(cfg, Some(safe_head.block_info.number + 1)).await?;
+        if next_head.block_info.number == safe_head.block_info.number {
+            // The data source is exhausted, and no more blocks can be produced.
+            return Ok((next_head, next_output_root));
+        }
+
+        let header = driver.cursor.read().l2_safe_head_header().clone();
+        if block_observer.on_block(&next_head, &header, next_output_root).is_break() {
+            return Ok((next_head, next_output_root));
+        }
+    }
+}
+
 /// Fetches the safe head hash of the L2 chain based on the agreed upon L2 output root in the
 /// [BootInfo].
 pub async fn fetch_safe_head_hash<O>(
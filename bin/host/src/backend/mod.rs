@@ -4,6 +4,19 @@ mod offline;
 pub use offline::OfflineHostBackend;
 
 mod online;
-pub use online::{HintHandler, OnlineHostBackend, OnlineHostBackendCfg};
+pub use online::{
+    DEFAULT_MAX_CONCURRENT_FETCHES, HintHandler, OnlineHostBackend, OnlineHostBackendCfg,
+};
+
+mod retry;
+pub use retry::{
+    DEFAULT_FETCH_BACKOFF, DEFAULT_FETCH_MAX_BACKOFF, DEFAULT_FETCH_RETRIES, HintRetryPolicy,
+};
+
+mod recorder;
+pub use recorder::{
+    PreimageAccess, PreimageAccessLog, PreimageAccessStats, RecordingHostBackend,
+    SharedPreimageAccessLog,
+};
 
 pub(crate) mod util;
@@ -1,22 +1,65 @@
 //! Contains the [OnlineHostBackend] definition.
 
-use crate::SharedKeyValueStore;
-use anyhow::Result;
+use crate::{HintRetryPolicy, PreimageVerificationError, SharedKeyValueStore};
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use futures::{
+    FutureExt,
+    future::{BoxFuture, Shared, join_all},
+};
 use kona_preimage::{
     HintRouter, PreimageFetcher, PreimageKey,
     errors::{PreimageOracleError, PreimageOracleResult},
 };
 use kona_proof::{Hint, errors::HintParsingError};
-use std::{collections::HashSet, hash::Hash, str::FromStr, sync::Arc};
-use tokio::sync::RwLock;
-use tracing::{debug, error, trace};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    num::NonZeroUsize,
+    str::FromStr,
+    sync::Arc,
+};
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tracing::{debug, error, trace, warn};
+
+/// The default maximum number of hint fetches that the [OnlineHostBackend] runs concurrently.
+pub const DEFAULT_MAX_CONCURRENT_FETCHES: NonZeroUsize = NonZeroUsize::new(16).unwrap();
+
+/// A fetch of a hint running in a background task, which can be awaited by multiple waiters.
+type InFlightFetch = Shared<BoxFuture<'static, Result<(), FetchError>>>;
+
+/// The error of a failed [InFlightFetch], shared between all of its waiters.
+#[derive(Debug, Clone)]
+enum FetchError {
+    /// The hint's providers returned a preimage that does not match its key. Refetching will not
+    /// help, so the host fails fast.
+    InvalidPreimage(String),
+    /// The fetch failed for another reason, e.g. a transient provider error.
+    Other(String),
+}
+
+impl From<FetchError> for PreimageOracleError {
+    fn from(err: FetchError) -> Self {
+        match err {
+            FetchError::InvalidPreimage(e) => Self::InvalidPreimage(e),
+            FetchError::Other(e) => Self::Other(e),
+        }
+    }
+}
 
 /// The [OnlineHostBackendCfg] trait is used to define the type configuration for the
 /// [OnlineHostBackend].
 pub trait OnlineHostBackendCfg {
     /// The hint type describing the range of hints that can be received.
-    type HintType: FromStr<Err = HintParsingError> + Hash + Eq + PartialEq + Clone + Send + Sync;
+    type HintType: FromStr<Err = HintParsingError>
+        + Display
+        + Hash
+        + Eq
+        + PartialEq
+        + Clone
+        + Send
+        + Sync;
 
     /// The providers that are used to fetch data in response to hints.
     type Providers: Send + Sync;
@@ -36,6 +79,14 @@ pub trait HintHandler {
         providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
         kv: SharedKeyValueStore,
     ) -> Result<()>;
+
+    /// Returns the key of the preimage that fetching the hint stores last, if it is known
+    /// without fetching the hint. Hints whose preimage is already stored are not refetched.
+    fn preimage_key(
+        _hint: &Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>,
+    ) -> Option<PreimageKey> {
+        None
+    }
 }
 
 /// The [OnlineHostBackend] is a [HintRouter] and [PreimageFetcher] that is used to fetch data from
@@ -49,15 +100,23 @@ where
     H: HintHandler,
 {
     /// The configuration that is used to route hints.
-    cfg: C,
+    cfg: Arc<C>,
     /// The key-value store that is used to store preimages.
     kv: SharedKeyValueStore,
     /// The providers that are used to fetch data in response to hints.
-    providers: C::Providers,
+    providers: Arc<C::Providers>,
     /// Hints that should be immediately executed by the host.
     proactive_hints: HashSet<C::HintType>,
+    /// Hints that should be fetched in the background as soon as they are received.
+    prefetch_hints: HashSet<C::HintType>,
     /// The last hint that was received.
     last_hint: Arc<RwLock<Option<Hint<C::HintType>>>>,
+    /// The fetches that are currently running, keyed by their hint.
+    in_flight: Arc<Mutex<HashMap<Hint<C::HintType>, InFlightFetch>>>,
+    /// Bounds the number of fetches that run concurrently.
+    fetch_permits: Arc<Semaphore>,
+    /// The policy with which failed fetches are retried.
+    retry_policy: HintRetryPolicy,
     /// Phantom marker for the [HintHandler].
     _hint_handler: std::marker::PhantomData<H>,
 }
@@ -71,11 +130,15 @@ where
     /// external configuration.
     pub fn new(cfg: C, kv: SharedKeyValueStore, providers: C::Providers, _: H) -> Self {
         Self {
-            cfg,
+            cfg: Arc::new(cfg),
             kv,
-            providers,
+            providers: Arc::new(providers),
             proactive_hints: HashSet::default(),
+            prefetch_hints: HashSet::default(),
             last_hint: Arc::new(RwLock::new(None)),
+            in_flight: Default::default(),
+            fetch_permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_FETCHES.get())),
+            retry_policy: HintRetryPolicy::default(),
             _hint_handler: std::marker::PhantomData,
         }
     }
@@ -85,13 +148,113 @@ where
         self.proactive_hints.insert(hint_type);
         self
     }
+
+    /// Adds a new prefetch hint to the [OnlineHostBackend]. Prefetch hints are fetched in the
+    /// background as soon as they are received, without blocking the client.
+    pub fn with_prefetch_hint(mut self, hint_type: C::HintType) -> Self {
+        self.prefetch_hints.insert(hint_type);
+        self
+    }
+
+    /// Sets the maximum number of hint fetches that run concurrently.
+    pub fn with_max_concurrent_fetches(mut self, max_concurrent_fetches: NonZeroUsize) -> Self {
+        self.fetch_permits = Arc::new(Semaphore::new(max_concurrent_fetches.get()));
+        self
+    }
+
+    /// Sets the [HintRetryPolicy] with which failed hint fetches are retried.
+    pub const fn with_retry_policy(mut self, retry_policy: HintRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+}
+
+impl<C, H> OnlineHostBackend<C, H>
+where
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
+{
+    /// Returns `true` if the preimage stored by fetching the given hint is already in the
+    /// key-value store.
+    async fn is_stored(&self, hint: &Hint<C::HintType>) -> bool {
+        match H::preimage_key(hint) {
+            Some(key) => self.kv.read().await.get(key.into()).is_some(),
+            None => false,
+        }
+    }
+
+    /// Starts fetching the given hint in a background task, or joins the in-flight fetch of the
+    /// same hint if there is one. The returned future resolves once the fetch completes.
+    async fn fetch(&self, hint: Hint<C::HintType>) -> InFlightFetch {
+        let mut in_flight = self.in_flight.lock().await;
+        if let Some(fetch) = in_flight.get(&hint) {
+            trace!(target: "host-backend", "Joining in-flight fetch for hint data {}", hint.data);
+            return fetch.clone();
+        }
+
+        let (cfg, providers, kv) = (self.cfg.clone(), self.providers.clone(), self.kv.clone());
+        let (fetch_permits, in_flight_ref) = (self.fetch_permits.clone(), self.in_flight.clone());
+        let retry_policy = self.retry_policy;
+        let key = hint.clone();
+        let fetch = async move {
+            let mut attempt = 0;
+            let result = loop {
+                let permit =
+                    fetch_permits.acquire().await.expect("Semaphore is never closed");
+                let fetch = H::fetch_hint(key.clone(), &cfg, &providers, kv.clone());
+                let result = match retry_policy.timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, fetch)
+                        .await
+                        .unwrap_or_else(|_| Err(anyhow!("Timed out after {timeout:?}"))),
+                    None => fetch.await,
+                };
+                drop(permit);
+
+                match result {
+                    // Invalid preimages are not transient, so they are never retried.
+                    Err(e)
+                        if attempt < retry_policy.max_retries &&
+                            e.downcast_ref::<PreimageVerificationError>().is_none() =>
+                    {
+                        let backoff = retry_policy.backoff(attempt);
+                        warn!(
+                            target: "host-backend",
+                            "Failed to fetch hint `{}` (attempt {}): {e}; Retrying in {backoff:?}",
+                            key.encode(),
+                            attempt + 1
+                        );
+                        tokio::time::sleep(backoff).await;
+                        attempt += 1;
+                    }
+                    result => break result,
+                }
+            };
+
+            let result = result.map_err(|e| {
+                if e.downcast_ref::<PreimageVerificationError>().is_some() {
+                    error!(target: "host-backend", "Hint `{}` returned an invalid preimage: {e}", key.encode());
+                    FetchError::InvalidPreimage(format!("Hint `{}`: {e}", key.encode()))
+                } else {
+                    FetchError::Other(e.to_string())
+                }
+            });
+            in_flight_ref.lock().await.remove(&key);
+            result
+        }
+        .boxed()
+        .shared();
+
+        tokio::spawn(fetch.clone());
+        in_flight.insert(hint, fetch.clone());
+        fetch
+    }
 }
 
 #[async_trait]
 impl<C, H> HintRouter for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Set the last hint to be received.
     async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
@@ -101,9 +264,13 @@ where
             hint.parse::<Hint<C::HintType>>().map_err(|_| PreimageOracleError::KeyNotFound)?;
         if self.proactive_hints.contains(&parsed_hint.ty) {
             debug!(target: "host-backend", "Proactive hint received; Immediately fetching {hint}");
-            H::fetch_hint(parsed_hint, &self.cfg, &self.providers, self.kv.clone())
-                .await
-                .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+            self.fetch(parsed_hint).await.await?;
+        } else if self.prefetch_hints.contains(&parsed_hint.ty) {
+            debug!(target: "host-backend", "Prefetch hint received; Fetching {hint} in the background");
+            drop(self.fetch(parsed_hint.clone()).await);
+
+            let mut hint_lock = self.last_hint.write().await;
+            hint_lock.replace(parsed_hint);
         } else {
             let mut hint_lock = self.last_hint.write().await;
             hint_lock.replace(parsed_hint);
@@ -111,13 +278,50 @@ where
 
         Ok(())
     }
+
+    /// Fetch every hint in the batch whose preimage is not yet stored concurrently, and set the
+    /// last hint in the batch to be the last hint received.
+    ///
+    /// Failed fetches are logged rather than returned, as with prefetched hints, so that a
+    /// preimage request for the last hint refetches it.
+    async fn route_hints(&self, hints: Vec<String>) -> PreimageOracleResult<()> {
+        trace!(target: "host-backend", "Received batch of {} hints", hints.len());
+
+        let parsed_hints = hints
+            .iter()
+            .map(|hint| hint.parse::<Hint<C::HintType>>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| PreimageOracleError::KeyNotFound)?;
+
+        if let Some(last_hint) = parsed_hints.last() {
+            self.last_hint.write().await.replace(last_hint.clone());
+        }
+
+        let mut unfetched = Vec::with_capacity(parsed_hints.len());
+        for hint in parsed_hints {
+            if !self.is_stored(&hint).await {
+                unfetched.push(hint);
+            }
+        }
+
+        debug!(target: "host-backend", "Fetching batch of {} hints", unfetched.len());
+        let encoded = unfetched.iter().map(|hint| hint.encode()).collect::<Vec<_>>();
+        let fetches = join_all(unfetched.into_iter().map(|hint| self.fetch(hint))).await;
+        for (hint, result) in encoded.iter().zip(join_all(fetches).await) {
+            if let Err(e) = result.map_err(PreimageOracleError::from) {
+                warn!(target: "host-backend", "Failed to fetch hint `{hint}` in batch: {e}");
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl<C, H> PreimageFetcher for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + Send + Sync + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Get the preimage for the given key.
     async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
@@ -132,20 +336,204 @@ where
 
         // Use a loop to keep retrying the prefetch as long as the key is not found
         while preimage.is_none() {
-            if let Some(hint) = self.last_hint.read().await.as_ref() {
-                let value =
-                    H::fetch_hint(hint.clone(), &self.cfg, &self.providers, self.kv.clone()).await;
-
-                if let Err(e) = value {
+            // The missing preimage is produced by the most recent hint. Wait for its in-flight
+            // fetch to land in the key-value store, or fetch it on demand.
+            let Some(hint) = self.last_hint.read().await.clone() else {
+                error!(target: "host-backend", "Pre-image {key} not found, and no hint was received");
+                return Err(PreimageOracleError::KeyNotFound);
+            };
+            match self.fetch(hint).await.await {
+                Err(FetchError::InvalidPreimage(e)) => {
+                    return Err(PreimageOracleError::InvalidPreimage(e));
+                }
+                Err(FetchError::Other(e)) => {
                     error!(target: "host-backend", "Failed to prefetch hint: {e}");
                     continue;
                 }
-
-                let kv_lock = self.kv.read().await;
-                preimage = kv_lock.get(key.into());
+                Ok(()) => {}
             }
+
+            let kv_lock = self.kv.read().await;
+            preimage = kv_lock.get(key.into());
         }
 
         preimage.ok_or(PreimageOracleError::KeyNotFound)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MemoryKeyValueStore, VerifyingKeyValueStore};
+    use alloy_primitives::keccak256;
+    use kona_proof::HintType;
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    #[derive(Default)]
+    struct TestCfg {
+        fetches: AtomicUsize,
+        failed: AtomicBool,
+    }
+
+    impl OnlineHostBackendCfg for TestCfg {
+        type HintType = HintType;
+        type Providers = ();
+    }
+
+    struct TestHintHandler;
+
+    #[async_trait]
+    impl HintHandler for TestHintHandler {
+        type Cfg = TestCfg;
+
+        async fn fetch_hint(
+            hint: Hint<HintType>,
+            cfg: &TestCfg,
+            _: &(),
+            kv: SharedKeyValueStore,
+        ) -> Result<()> {
+            cfg.fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            // Hints with the data `0xee` fail on their first fetch.
+            if hint.data.as_ref() == [0xee] && !cfg.failed.swap(true, Ordering::SeqCst) {
+                anyhow::bail!("Transient provider error");
+            }
+
+            // Hints with the data `0xff` are answered with a preimage that does not match its key.
+            let key = PreimageKey::new_keccak256(*keccak256(hint.data.as_ref()));
+            let value = if hint.data.as_ref() == [0xff] { vec![0x00] } else { hint.data.to_vec() };
+            kv.write().await.set(key.into(), value)
+        }
+
+        fn preimage_key(hint: &Hint<HintType>) -> Option<PreimageKey> {
+            Some(PreimageKey::new_keccak256(*keccak256(hint.data.as_ref())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_dedupes_in_flight_fetches() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv, (), TestHintHandler)
+            .with_prefetch_hint(HintType::L1Receipts);
+
+        let hint = HintType::L1Receipts.with_data(&[&[0xaa]]).encode();
+        backend.route_hint(hint.clone()).await.unwrap();
+        backend.route_hint(hint).await.unwrap();
+
+        // The preimage request waits on the in-flight fetch rather than refetching.
+        let key = PreimageKey::new_keccak256(*keccak256([0xaa]));
+        assert_eq!(backend.get_preimage(key).await.unwrap(), vec![0xaa]);
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 1);
+        assert!(backend.in_flight.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_preimage_without_hint() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv, (), TestHintHandler);
+
+        let key = PreimageKey::new_keccak256(*keccak256([0xaa]));
+        assert!(matches!(backend.get_preimage(key).await, Err(PreimageOracleError::KeyNotFound)));
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_batch_fetches_each_hint_once() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv.clone(), (), TestHintHandler)
+            .with_max_concurrent_fetches(NonZeroUsize::new(1).unwrap());
+
+        let hints = [[0x01], [0x02], [0x01]]
+            .map(|data| HintType::L1BlockHeader.with_data(&[&data]).encode())
+            .to_vec();
+        backend.route_hints(hints).await.unwrap();
+
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 2);
+        for data in [0x01, 0x02] {
+            let key = PreimageKey::new_keccak256(*keccak256([data]));
+            assert_eq!(kv.read().await.get(key.into()), Some(vec![data]));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_preimage_fails_fast() {
+        let kv: SharedKeyValueStore =
+            Arc::new(RwLock::new(VerifyingKeyValueStore::new(MemoryKeyValueStore::new())));
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv, (), TestHintHandler);
+
+        backend.route_hint(HintType::L1BlockHeader.with_data(&[&[0xff]]).encode()).await.unwrap();
+
+        let key = PreimageKey::new_keccak256(*keccak256([0xff]));
+        let err = backend.get_preimage(key).await.unwrap_err();
+        assert!(
+            matches!(err, PreimageOracleError::InvalidPreimage(e) if e.contains("l1-block-header"))
+        );
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_failed_fetches() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let hint = HintType::L1BlockHeader.with_data(&[&[0xee]]).encode();
+
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv.clone(), (), TestHintHandler)
+            .with_retry_policy(HintRetryPolicy::none());
+        backend.route_hints(vec![hint.clone()]).await.unwrap();
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            kv.read().await.get(PreimageKey::new_keccak256(*keccak256([0xee])).into()),
+            None
+        );
+
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv.clone(), (), TestHintHandler)
+            .with_retry_policy(HintRetryPolicy {
+                max_retries: 1,
+                backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+                timeout: Some(Duration::from_secs(1)),
+            });
+        backend.route_hints(vec![hint]).await.unwrap();
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 2);
+
+        let key = PreimageKey::new_keccak256(*keccak256([0xee]));
+        assert_eq!(kv.read().await.get(key.into()), Some(vec![0xee]));
+    }
+
+    #[tokio::test]
+    async fn test_batch_skips_stored_preimages() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let key = PreimageKey::new_keccak256(*keccak256([0x01]));
+        kv.write().await.set(key.into(), vec![0x01]).unwrap();
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv.clone(), (), TestHintHandler);
+
+        let hints = [[0x01], [0x02]]
+            .map(|data| HintType::L1BlockHeader.with_data(&[&data]).encode())
+            .to_vec();
+        backend.route_hints(hints).await.unwrap();
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_batch_fetch_is_refetched() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend = OnlineHostBackend::new(TestCfg::default(), kv.clone(), (), TestHintHandler)
+            .with_retry_policy(HintRetryPolicy::none());
+
+        // The last hint of the batch fails to fetch, but the batch is still acknowledged.
+        let hints = [[0x01], [0xee]]
+            .map(|data| HintType::L1BlockHeader.with_data(&[&data]).encode())
+            .to_vec();
+        backend.route_hints(hints).await.unwrap();
+
+        // The server keeps serving, and the failed hint is refetched on demand.
+        let key = PreimageKey::new_keccak256(*keccak256([0x01]));
+        assert_eq!(backend.get_preimage(key).await.unwrap(), vec![0x01]);
+        let key = PreimageKey::new_keccak256(*keccak256([0xee]));
+        assert_eq!(backend.get_preimage(key).await.unwrap(), vec![0xee]);
+        assert_eq!(backend.cfg.fetches.load(Ordering::SeqCst), 3);
+    }
+}
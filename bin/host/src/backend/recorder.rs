@@ -0,0 +1,273 @@
+//! Contains the [RecordingHostBackend], which records the exact set of preimages served to the
+//! client program.
+
+use crate::{KeyValueStore, MemoryKeyValueStore, PreimageServer, PreimageServerError};
+use alloy_primitives::B256;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use kona_preimage::{
+    Channel, HintReader, HintRouter, OracleServer, PreimageFetcher, PreimageKey, PreimageKeyType,
+    PreimageServerBackend, errors::PreimageOracleResult,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+/// A type alias for a shared [PreimageAccessLog].
+pub type SharedPreimageAccessLog = Arc<RwLock<PreimageAccessLog>>;
+
+/// A record of a single preimage served to the client program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreimageAccess {
+    /// The type of the preimage key.
+    pub key_type: PreimageKeyType,
+    /// The size of the preimage, in bytes.
+    pub size: usize,
+    /// The number of times the preimage was requested.
+    pub reads: u64,
+}
+
+/// Aggregate statistics for all accessed preimages of a single [PreimageKeyType].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreimageAccessStats {
+    /// The number of distinct preimages.
+    pub count: usize,
+    /// The total size of the distinct preimages, in bytes.
+    pub size: usize,
+}
+
+/// The read-set of a client program run: every preimage key served, along with its type and size.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreimageAccessLog {
+    /// The accessed preimages, keyed by their preimage key.
+    pub accesses: BTreeMap<B256, PreimageAccess>,
+}
+
+impl PreimageAccessLog {
+    /// Records that the preimage for `key`, of `size` bytes, was served to the client.
+    pub fn record(&mut self, key: PreimageKey, size: usize) {
+        self.accesses
+            .entry(key.into())
+            .and_modify(|access| access.reads += 1)
+            .or_insert(PreimageAccess { key_type: key.key_type(), size, reads: 1 });
+    }
+
+    /// Returns `true` if the preimage for `key` was served to the client.
+    pub fn contains(&self, key: &B256) -> bool {
+        self.accesses.contains_key(key)
+    }
+
+    /// Returns the number of distinct preimages served to the client.
+    pub fn len(&self) -> usize {
+        self.accesses.len()
+    }
+
+    /// Returns `true` if no preimages were served to the client.
+    pub fn is_empty(&self) -> bool {
+        self.accesses.is_empty()
+    }
+
+    /// Returns the total size of the distinct preimages served to the client, in bytes.
+    pub fn total_size(&self) -> usize {
+        self.accesses.values().map(|access| access.size).sum()
+    }
+
+    /// Returns the [PreimageAccessStats] of the read-set, grouped by [PreimageKeyType].
+    pub fn stats(&self) -> BTreeMap<PreimageKeyType, PreimageAccessStats> {
+        let mut stats = BTreeMap::<_, PreimageAccessStats>::new();
+        for access in self.accesses.values() {
+            let entry = stats.entry(access.key_type).or_default();
+            entry.count += 1;
+            entry.size += access.size;
+        }
+        stats
+    }
+
+    /// Copies only the preimages in the read-set out of the given [KeyValueStore].
+    ///
+    /// Errors if any accessed preimage is missing from the store.
+    pub fn compact<KV>(&self, kv_store: &KV) -> Result<MemoryKeyValueStore>
+    where
+        KV: KeyValueStore + ?Sized,
+    {
+        let mut compacted = MemoryKeyValueStore::new();
+        for key in self.accesses.keys() {
+            let value =
+                kv_store.get(*key).ok_or_else(|| anyhow!("Accessed preimage {key} not found"))?;
+            compacted.set(*key, value)?;
+        }
+        Ok(compacted)
+    }
+
+    /// Writes the access log, as JSON, to the file at the given path.
+    pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Reads an access log from the JSON file at the given path.
+    pub fn read_from_file(path: &Path) -> std::io::Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+}
+
+/// A [PreimageFetcher] and [HintRouter] wrapper that records every preimage served by the inner
+/// backend in a [PreimageAccessLog].
+#[derive(Debug)]
+pub struct RecordingHostBackend<B> {
+    /// The inner backend.
+    inner: B,
+    /// The read-set recorded so far.
+    access_log: SharedPreimageAccessLog,
+}
+
+impl<B> RecordingHostBackend<B> {
+    /// Creates a new [RecordingHostBackend] wrapping the given backend, with an empty
+    /// [PreimageAccessLog].
+    pub fn new(inner: B) -> Self {
+        Self { inner, access_log: Default::default() }
+    }
+
+    /// Returns a handle to the [PreimageAccessLog] of the backend.
+    pub fn access_log(&self) -> SharedPreimageAccessLog {
+        self.access_log.clone()
+    }
+}
+
+impl<B> RecordingHostBackend<B>
+where
+    B: PreimageServerBackend + Send + Sync + 'static,
+{
+    /// Serves preimages from the backend over the given channels until the client program hangs
+    /// up, and returns the recorded [PreimageAccessLog]. If an access log path is given, the
+    /// read-set is also written to it as JSON.
+    pub async fn serve<C, E>(
+        self,
+        hint: C,
+        preimage: C,
+        access_log_path: Option<&Path>,
+    ) -> Result<PreimageAccessLog, E>
+    where
+        C: Channel + Send + Sync + 'static,
+        E: From<PreimageServerError> + From<std::io::Error>,
+    {
+        let access_log = self.access_log();
+        PreimageServer::new(OracleServer::new(preimage), HintReader::new(hint), Arc::new(self))
+            .start()
+            .await?;
+
+        let access_log = access_log.read().await.clone();
+        if let Some(access_log_path) = access_log_path {
+            access_log.write_to_file(access_log_path)?;
+            info!(
+                target: "host-backend",
+                "Recorded {} preimages ({} bytes) to {access_log_path:?}",
+                access_log.len(),
+                access_log.total_size()
+            );
+        }
+        Ok(access_log)
+    }
+}
+
+#[async_trait]
+impl<B> PreimageFetcher for RecordingHostBackend<B>
+where
+    B: PreimageFetcher + Send + Sync,
+{
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let preimage = self.inner.get_preimage(key).await?;
+
+        debug!(
+            target: "host-backend",
+            "Served preimage {key} (type: {:?}, size: {})",
+            key.key_type(),
+            preimage.len()
+        );
+        self.access_log.write().await.record(key, preimage.len());
+
+        Ok(preimage)
+    }
+}
+
+#[async_trait]
+impl<B> HintRouter for RecordingHostBackend<B>
+where
+    B: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        self.inner.route_hint(hint).await
+    }
+
+    async fn route_hints(&self, hints: Vec<String>) -> PreimageOracleResult<()> {
+        self.inner.route_hints(hints).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::OfflineHostBackend;
+    use alloy_primitives::keccak256;
+
+    #[tokio::test]
+    async fn test_records_served_preimages() {
+        let preimage = b"hello".to_vec();
+        let key = PreimageKey::new_keccak256(*keccak256(&preimage));
+        let missing = PreimageKey::new_keccak256(*keccak256(b"missing"));
+
+        let mut kv_store = MemoryKeyValueStore::new();
+        kv_store.set(key.into(), preimage.clone()).unwrap();
+        let backend =
+            RecordingHostBackend::new(OfflineHostBackend::new(Arc::new(RwLock::new(kv_store))));
+
+        assert_eq!(backend.get_preimage(key).await.unwrap(), preimage);
+        assert_eq!(backend.get_preimage(key).await.unwrap(), preimage);
+        assert!(backend.get_preimage(missing).await.is_err());
+
+        let access_log = backend.access_log();
+        let access_log = access_log.read().await;
+        assert_eq!(access_log.len(), 1);
+        assert_eq!(
+            access_log.accesses[&B256::from(key)],
+            PreimageAccess { key_type: PreimageKeyType::Keccak256, size: 5, reads: 2 }
+        );
+        assert_eq!(
+            access_log.stats()[&PreimageKeyType::Keccak256],
+            PreimageAccessStats { count: 1, size: 5 }
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let used = PreimageKey::new_keccak256([1; 32]);
+        let unused = PreimageKey::new_keccak256([2; 32]);
+
+        let mut kv_store = MemoryKeyValueStore::new();
+        kv_store.set(used.into(), vec![1]).unwrap();
+        kv_store.set(unused.into(), vec![2]).unwrap();
+
+        let mut access_log = PreimageAccessLog::default();
+        access_log.record(used, 1);
+
+        let compacted = access_log.compact(&kv_store).unwrap();
+        assert_eq!(compacted.get(used.into()), Some(vec![1]));
+        assert_eq!(compacted.get(unused.into()), None);
+
+        access_log.record(PreimageKey::new_keccak256([3; 32]), 1);
+        assert!(access_log.compact(&kv_store).is_err());
+    }
+
+    #[test]
+    fn test_write_and_read_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access-log.json");
+
+        let mut access_log = PreimageAccessLog::default();
+        access_log.record(PreimageKey::new_local(1), 32);
+        access_log.record(PreimageKey::new_keccak256([1; 32]), 128);
+        access_log.write_to_file(&path).unwrap();
+
+        assert_eq!(PreimageAccessLog::read_from_file(&path).unwrap(), access_log);
+    }
+}
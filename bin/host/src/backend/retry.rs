@@ -0,0 +1,78 @@
+//! Contains the [HintRetryPolicy], which controls how the [OnlineHostBackend] retries failed hint
+//! fetches.
+//!
+//! [OnlineHostBackend]: super::OnlineHostBackend
+
+use std::time::Duration;
+
+/// The default number of times a failed hint fetch is retried.
+pub const DEFAULT_FETCH_RETRIES: u32 = 3;
+
+/// The default delay before the first retry of a failed hint fetch.
+pub const DEFAULT_FETCH_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The default upper bound on the delay between retries of a failed hint fetch.
+pub const DEFAULT_FETCH_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The policy with which the [OnlineHostBackend] retries failed hint fetches.
+///
+/// Each attempt may be bounded by a timeout. Failed attempts are retried up to `max_retries`
+/// times, with a delay that starts at `backoff` and doubles after every attempt, up to
+/// `max_backoff`.
+///
+/// [OnlineHostBackend]: super::OnlineHostBackend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HintRetryPolicy {
+    /// The number of times a failed fetch is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub backoff: Duration,
+    /// The upper bound on the delay between retries.
+    pub max_backoff: Duration,
+    /// The time after which a single fetch attempt is abandoned, if any.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for HintRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_FETCH_RETRIES,
+            backoff: DEFAULT_FETCH_BACKOFF,
+            max_backoff: DEFAULT_FETCH_MAX_BACKOFF,
+            timeout: None,
+        }
+    }
+}
+
+impl HintRetryPolicy {
+    /// Returns a [HintRetryPolicy] that never retries, and never times out.
+    pub const fn none() -> Self {
+        Self { max_retries: 0, backoff: Duration::ZERO, max_backoff: Duration::ZERO, timeout: None }
+    }
+
+    /// Returns the delay before the retry following the given (zero-indexed) failed attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(1 << attempt.min(31)).min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_exponential_and_bounded() {
+        let policy = HintRetryPolicy {
+            max_retries: 8,
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            timeout: None,
+        };
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+}
@@ -38,6 +38,20 @@ pub enum HostMode {
     /// Run the host in single-chain mode.
     #[cfg(feature = "single")]
     Single(kona_host::single::SingleChainHost),
+    /// Run the host in single-chain mode, serving preimages from a witness bundle.
+    #[cfg(feature = "single")]
+    Bundle(kona_host::bundle::BundleHost),
+    /// Compact a witness bundle, or a persistent preimage store, down to the preimages recorded in
+    /// an access log.
+    #[cfg(feature = "single")]
+    Compact(kona_host::bundle::CompactBundle),
+    /// Replay a proof trace recorded with `--trace` through the client program, without a host.
+    #[cfg(feature = "single")]
+    Replay(kona_host::trace::ReplayTrace),
+    /// Derive and execute a range of L2 blocks one at a time, and find the first block that
+    /// diverges from the L2 node.
+    #[cfg(feature = "single")]
+    Bisect(kona_host::bisect::BisectOutputRoots),
     /// Run the host in super-chain (interop) mode.
     #[cfg(feature = "interop")]
     Super(kona_host::interop::InteropHost),
@@ -53,6 +67,23 @@ async fn main() -> Result<()> {
         HostMode::Single(cfg) => {
             cfg.start().await?;
         }
+        #[cfg(feature = "single")]
+        HostMode::Bundle(cfg) => {
+            cfg.start().await?;
+        }
+        #[cfg(feature = "single")]
+        HostMode::Compact(cfg) => {
+            cfg.start().await?;
+        }
+        #[cfg(feature = "single")]
+        HostMode::Replay(cfg) => {
+            cfg.start().await?;
+        }
+        #[cfg(feature = "single")]
+        HostMode::Bisect(cfg) => {
+            let report = cfg.start().await?;
+            std::process::exit(report.divergence.is_some() as i32)
+        }
         #[cfg(feature = "interop")]
         HostMode::Super(cfg) => {
             cfg.start().await?;
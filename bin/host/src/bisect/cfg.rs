@@ -0,0 +1,227 @@
+//! Contains the [BisectOutputRoots] command, which derives and executes a range of L2 blocks one
+//! at a time, and finds the first block that diverges from a reference L2 node.
+
+use super::{BisectReport, BlockComparator, BlockRoots, compute_output_root};
+use crate::{
+    DEFAULT_MAX_CONCURRENT_FETCHES,
+    eth::{L2StateFetchMode, failover_http_provider},
+    single::{SingleChainHost, SingleChainHostError},
+};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, B256, address};
+use alloy_provider::{Provider, RootProvider};
+use clap::Parser;
+use futures::{StreamExt, TryStreamExt, stream};
+use kona_cli::cli_styles;
+use kona_preimage::{BidirectionalChannel, HintWriter, OracleReader};
+use op_alloy_network::Optimism;
+use serde::Serialize;
+use std::{collections::BTreeMap, path::PathBuf};
+use tracing::{info, warn};
+
+/// The address of the L2 to L1 message passer predeploy.
+const L2_TO_L1_MESSAGE_PASSER_ADDRESS: Address =
+    address!("4200000000000000000000000000000000000016");
+
+/// Derives and executes the L2 blocks after `--start-block`, up to and including `--end-block`,
+/// one at a time, and compares the output root, state root, receipts root and transactions root
+/// of each block against those of the L2 node. Stops at the first block that differs, and reports
+/// which of its components differed.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct BisectOutputRoots {
+    /// Number of the agreed upon L2 block to start derivation from.
+    #[arg(long, env)]
+    pub start_block: u64,
+    /// Number of the last L2 block to derive and execute.
+    #[arg(long, env)]
+    pub end_block: u64,
+    /// Hash of the L1 head block. Derivation stops after this block is processed. Defaults to the
+    /// latest block of the L1 node.
+    #[arg(long, env)]
+    pub l1_head: Option<B256>,
+    /// Address of the reference L2 JSON-RPC endpoint, which is also used to fetch preimages.
+    /// Several comma-separated addresses of redundant endpoints may be given, in order of
+    /// preference.
+    #[arg(long, visible_alias = "l2", value_delimiter = ',', required = true, env)]
+    pub l2_node_address: Vec<String>,
+    /// Address of L1 JSON-RPC endpoint to use. Several comma-separated addresses of redundant
+    /// endpoints may be given, in order of preference.
+    #[arg(long, visible_alias = "l1", value_delimiter = ',', required = true, env)]
+    pub l1_node_address: Vec<String>,
+    /// Address of the L1 Beacon API endpoint to use. Several comma-separated addresses of
+    /// redundant endpoints may be given, in order of preference.
+    #[arg(long, visible_alias = "beacon", value_delimiter = ',', required = true, env)]
+    pub l1_beacon_address: Vec<String>,
+    /// The strategy with which L2 state trie nodes and contract code are fetched by hash.
+    #[arg(long, value_enum, default_value_t, env)]
+    pub l2_state_fetch_mode: L2StateFetchMode,
+    /// The L2 chain ID of a supported chain.
+    #[arg(
+        long,
+        conflicts_with = "rollup_config_path",
+        required_unless_present = "rollup_config_path",
+        env
+    )]
+    pub l2_chain_id: Option<u64>,
+    /// Path to rollup config, used instead of looking up the config in the superchain registry.
+    #[arg(
+        long,
+        alias = "rollup-cfg",
+        conflicts_with = "l2_chain_id",
+        required_unless_present = "l2_chain_id",
+        env
+    )]
+    pub rollup_config_path: Option<PathBuf>,
+    /// Write the [BisectReport], as JSON, to the given path.
+    #[arg(long, env)]
+    pub report: Option<PathBuf>,
+}
+
+/// An error that can occur when bisecting output roots.
+#[derive(Debug, thiserror::Error)]
+pub enum BisectError {
+    /// An error in the host.
+    #[error(transparent)]
+    Host(#[from] SingleChainHostError),
+    /// An RPC error.
+    #[error("RPC error: {0}")]
+    Rpc(#[from] alloy_transport::TransportError),
+    /// A block is missing from a node.
+    #[error("Block {0} not found")]
+    MissingBlock(BlockNumberOrTag),
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// The range of L2 blocks is empty.
+    #[error("End block {1} is not after start block {0}")]
+    EmptyRange(u64, u64),
+    /// The client program failed before any L2 block diverged.
+    #[error("Client program failed without diverging: {0}")]
+    Client(#[from] kona_client::single::FaultProofProgramError),
+}
+
+impl BisectOutputRoots {
+    /// Runs the [BisectOutputRoots] command, returning the [BisectReport]. If the client program
+    /// fails before any L2 block diverges, its error is returned after the report is written.
+    pub async fn start(self) -> Result<BisectReport, BisectError> {
+        if self.end_block <= self.start_block {
+            return Err(BisectError::EmptyRange(self.start_block, self.end_block));
+        }
+
+        let l1_provider: RootProvider = failover_http_provider(&self.l1_node_address);
+        let l2_provider = failover_http_provider::<Optimism>(&self.l2_node_address);
+
+        let l1_head = match self.l1_head {
+            Some(l1_head) => l1_head,
+            None => {
+                l1_provider
+                    .get_block_by_number(BlockNumberOrTag::Latest)
+                    .await?
+                    .ok_or(BisectError::MissingBlock(BlockNumberOrTag::Latest))?
+                    .header
+                    .hash
+            }
+        };
+
+        info!(
+            target: "bisect",
+            "Fetching the reference roots of L2 blocks #{}-#{}",
+            self.start_block,
+            self.end_block
+        );
+        let expected = stream::iter(self.start_block..=self.end_block)
+            .map(|number| fetch_block_roots(&l2_provider, number))
+            .buffered(DEFAULT_MAX_CONCURRENT_FETCHES.get())
+            .map_ok(|roots| (roots.number, roots))
+            .try_collect::<BTreeMap<_, _>>()
+            .await?;
+        let (agreed, claimed) = (expected[&self.start_block], expected[&self.end_block]);
+
+        let host = SingleChainHost {
+            l1_head,
+            agreed_l2_head_hash: agreed.hash,
+            agreed_l2_output_root: agreed.output_root,
+            claimed_l2_output_root: claimed.output_root,
+            claimed_l2_block_number: self.end_block,
+            l2_node_address: Some(self.l2_node_address.clone()),
+            l1_node_address: Some(self.l1_node_address.clone()),
+            l1_beacon_address: Some(self.l1_beacon_address.clone()),
+            l2_state_fetch_mode: self.l2_state_fetch_mode,
+            l2_chain_id: self.l2_chain_id,
+            rollup_config_path: self.rollup_config_path.clone(),
+            native: true,
+            ..Default::default()
+        };
+
+        let hint = BidirectionalChannel::new()?;
+        let preimage = BidirectionalChannel::new()?;
+        let server_task = host.start_server(hint.host, preimage.host).await?;
+
+        let comparator = BlockComparator::new(self.start_block, self.end_block, expected);
+        let client_result = kona_client::single::run_with_block_observer(
+            OracleReader::new(preimage.client),
+            HintWriter::new(hint.client),
+            None,
+            comparator.clone(),
+        )
+        .await;
+        server_task.abort();
+
+        let report = comparator.report();
+        match &report.divergence {
+            Some(divergence) => warn!(
+                target: "bisect",
+                "First diverging L2 block: #{} ({} components differ)",
+                divergence.expected.number,
+                divergence.mismatches.len()
+            ),
+            None => info!(
+                target: "bisect",
+                "No divergence found in {} L2 blocks",
+                report.blocks_checked
+            ),
+        }
+
+        if let Some(ref report_path) = self.report {
+            std::fs::write(
+                report_path,
+                serde_json::to_vec_pretty(&report).map_err(std::io::Error::from)?,
+            )?;
+        }
+
+        // A divergence explains a failure of the client, and is reported instead.
+        if report.divergence.is_none() {
+            client_result?;
+        }
+        Ok(report)
+    }
+}
+
+/// Fetches the [BlockRoots] of an L2 block from the L2 node.
+async fn fetch_block_roots(
+    l2_provider: &RootProvider<Optimism>,
+    number: u64,
+) -> Result<BlockRoots, BisectError> {
+    let block = l2_provider
+        .get_block_by_number(number.into())
+        .await?
+        .ok_or(BisectError::MissingBlock(number.into()))?;
+    let message_passer = l2_provider
+        .get_proof(L2_TO_L1_MESSAGE_PASSER_ADDRESS, Default::default())
+        .block_id(number.into())
+        .await?;
+
+    Ok(BlockRoots {
+        number,
+        hash: block.header.hash,
+        transactions_root: block.header.transactions_root,
+        receipts_root: block.header.receipts_root,
+        state_root: block.header.state_root,
+        output_root: compute_output_root(
+            block.header.state_root,
+            message_passer.storage_hash,
+            block.header.hash,
+        ),
+    })
+}
@@ -0,0 +1,12 @@
+//! This module contains a tool that derives and executes a range of L2 blocks one at a time, and
+//! finds the first block whose output root, state root, receipts root or transactions root
+//! diverges from a reference L2 node.
+
+mod report;
+pub use report::{
+    BisectReport, BlockComparator, BlockComponent, BlockDivergence, BlockRoots, ComponentMismatch,
+    compute_output_root,
+};
+
+mod cfg;
+pub use cfg::{BisectError, BisectOutputRoots};
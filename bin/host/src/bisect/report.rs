@@ -0,0 +1,250 @@
+//! Contains the [BisectReport] of an output root bisection, and the [BlockComparator] that
+//! compares each L2 block executed by the client program against the reference L2 node.
+
+use alloy_consensus::{Header, Sealed};
+use alloy_primitives::{B256, keccak256};
+use kona_client::single::BlockObserver;
+use kona_protocol::L2BlockInfo;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ops::ControlFlow,
+    sync::{Arc, Mutex},
+};
+use tracing::{error, info};
+
+/// The version of the output root construction.
+const OUTPUT_ROOT_VERSION: u8 = 0;
+
+/// Computes the version 0 output root of an L2 block.
+pub fn compute_output_root(
+    state_root: B256,
+    message_passer_storage_root: B256,
+    block_hash: B256,
+) -> B256 {
+    let mut raw_output = [0u8; 128];
+    raw_output[31] = OUTPUT_ROOT_VERSION;
+    raw_output[32..64].copy_from_slice(state_root.as_ref());
+    raw_output[64..96].copy_from_slice(message_passer_storage_root.as_ref());
+    raw_output[96..128].copy_from_slice(block_hash.as_ref());
+    keccak256(raw_output)
+}
+
+/// A component of an L2 block that is compared against the reference L2 node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockComponent {
+    /// The transactions root. A mismatch points at derivation.
+    TransactionsRoot,
+    /// The receipts root.
+    ReceiptsRoot,
+    /// The state root.
+    StateRoot,
+    /// The block hash.
+    BlockHash,
+    /// The output root.
+    OutputRoot,
+}
+
+/// The roots of an L2 block, as compared by the [BlockComparator].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockRoots {
+    /// The block number.
+    pub number: u64,
+    /// The block hash.
+    pub hash: B256,
+    /// The transactions root.
+    pub transactions_root: B256,
+    /// The receipts root.
+    pub receipts_root: B256,
+    /// The state root.
+    pub state_root: B256,
+    /// The output root.
+    pub output_root: B256,
+}
+
+impl BlockRoots {
+    /// Returns the [BlockRoots] of the given sealed [Header], with the given output root.
+    pub fn from_header(header: &Sealed<Header>, output_root: B256) -> Self {
+        Self {
+            number: header.number,
+            hash: header.hash(),
+            transactions_root: header.transactions_root,
+            receipts_root: header.receipts_root,
+            state_root: header.state_root,
+            output_root,
+        }
+    }
+
+    /// Returns the [ComponentMismatch]es between the expected roots and the actual roots, in the
+    /// order in which the components are produced.
+    pub fn diff(&self, actual: &Self) -> Vec<ComponentMismatch> {
+        [
+            (BlockComponent::TransactionsRoot, self.transactions_root, actual.transactions_root),
+            (BlockComponent::ReceiptsRoot, self.receipts_root, actual.receipts_root),
+            (BlockComponent::StateRoot, self.state_root, actual.state_root),
+            (BlockComponent::BlockHash, self.hash, actual.hash),
+            (BlockComponent::OutputRoot, self.output_root, actual.output_root),
+        ]
+        .into_iter()
+        .filter(|(_, expected, actual)| expected != actual)
+        .map(|(component, expected, actual)| ComponentMismatch { component, expected, actual })
+        .collect()
+    }
+}
+
+/// A [BlockComponent] of an L2 block that differs from the reference L2 node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentMismatch {
+    /// The component that differs.
+    pub component: BlockComponent,
+    /// The value of the component on the reference L2 node.
+    pub expected: B256,
+    /// The value of the component produced by the client program.
+    pub actual: B256,
+}
+
+/// The first L2 block executed by the client program that differs from the reference L2 node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockDivergence {
+    /// The roots of the block on the reference L2 node.
+    pub expected: BlockRoots,
+    /// The roots of the block produced by the client program.
+    pub actual: BlockRoots,
+    /// The components of the block that differ.
+    pub mismatches: Vec<ComponentMismatch>,
+}
+
+/// The report of an output root bisection over a range of L2 blocks.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectReport {
+    /// The agreed upon L2 block that derivation starts from.
+    pub start_block: u64,
+    /// The last L2 block of the range.
+    pub end_block: u64,
+    /// The number of L2 blocks compared against the reference L2 node.
+    pub blocks_checked: u64,
+    /// The last L2 block that matched the reference L2 node, if any.
+    pub last_matching_block: Option<u64>,
+    /// The first L2 block that differs from the reference L2 node, if any.
+    pub divergence: Option<BlockDivergence>,
+}
+
+/// A [BlockObserver] that compares the [BlockRoots] of each L2 block executed by the client
+/// program against those of the reference L2 node, and halts derivation at the first mismatch.
+#[derive(Debug, Clone)]
+pub struct BlockComparator {
+    /// The [BlockRoots] of the reference L2 node, by block number.
+    expected: Arc<BTreeMap<u64, BlockRoots>>,
+    /// The report of the comparisons so far.
+    report: Arc<Mutex<BisectReport>>,
+}
+
+impl BlockComparator {
+    /// Creates a new [BlockComparator] for the range of L2 blocks after `start_block`, up to and
+    /// including `end_block`, with the [BlockRoots] of the reference L2 node.
+    pub fn new(start_block: u64, end_block: u64, expected: BTreeMap<u64, BlockRoots>) -> Self {
+        Self {
+            expected: Arc::new(expected),
+            report: Arc::new(Mutex::new(BisectReport {
+                start_block,
+                end_block,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// Returns the [BisectReport] of the comparisons so far.
+    pub fn report(&self) -> BisectReport {
+        self.report.lock().expect("Lock poisoned").clone()
+    }
+}
+
+impl BlockObserver for BlockComparator {
+    fn on_block(
+        &self,
+        block: &L2BlockInfo,
+        header: &Sealed<Header>,
+        output_root: B256,
+    ) -> ControlFlow<()> {
+        let number = block.block_info.number;
+        let Some(expected) = self.expected.get(&number) else {
+            error!(target: "bisect", "No reference roots for L2 block #{number}");
+            return ControlFlow::Break(());
+        };
+
+        let actual = BlockRoots::from_header(header, output_root);
+        let mismatches = expected.diff(&actual);
+
+        let mut report = self.report.lock().expect("Lock poisoned");
+        report.blocks_checked += 1;
+        if mismatches.is_empty() {
+            info!(target: "bisect", "L2 block #{number} matches the reference L2 node");
+            report.last_matching_block = Some(number);
+            return ControlFlow::Continue(());
+        }
+
+        error!(
+            target: "bisect",
+            "L2 block #{number} diverges from the reference L2 node in {:?}",
+            mismatches.iter().map(|mismatch| mismatch.component).collect::<Vec<_>>()
+        );
+        report.divergence = Some(BlockDivergence { expected: *expected, actual, mismatches });
+        ControlFlow::Break(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_consensus::Sealable;
+
+    fn block(header: &Sealed<Header>) -> L2BlockInfo {
+        let mut block = L2BlockInfo::default();
+        block.block_info.number = header.number;
+        block
+    }
+
+    #[test]
+    fn test_halts_at_first_divergence() {
+        let headers = (1..=3)
+            .map(|number| {
+                Header {
+                    number,
+                    state_root: B256::with_last_byte(number as u8),
+                    ..Default::default()
+                }
+                .seal_slow()
+            })
+            .collect::<Vec<_>>();
+        let expected = headers
+            .iter()
+            .map(|header| {
+                let output_root = compute_output_root(header.state_root, B256::ZERO, header.hash());
+                (header.number, BlockRoots::from_header(header, output_root))
+            })
+            .collect();
+        let comparator = BlockComparator::new(0, 3, expected);
+
+        let output_root = comparator.expected[&1].output_root;
+        assert!(comparator.on_block(&block(&headers[0]), &headers[0], output_root).is_continue());
+
+        // The second block executes to a different state root.
+        let diverged = Header { state_root: B256::repeat_byte(0xff), ..headers[1].inner().clone() }
+            .seal_slow();
+        let output_root = compute_output_root(diverged.state_root, B256::ZERO, diverged.hash());
+        assert!(comparator.on_block(&block(&diverged), &diverged, output_root).is_break());
+
+        let report = comparator.report();
+        assert_eq!(report.blocks_checked, 2);
+        assert_eq!(report.last_matching_block, Some(1));
+        let divergence = report.divergence.unwrap();
+        assert_eq!(divergence.expected.number, 2);
+        assert_eq!(
+            divergence.mismatches.iter().map(|mismatch| mismatch.component).collect::<Vec<_>>(),
+            [BlockComponent::StateRoot, BlockComponent::BlockHash, BlockComponent::OutputRoot]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! This module contains all CLI-specific code for the witness bundle entrypoint.
+
+use super::{WitnessBundle, WitnessBundleError};
+use crate::{
+    MemoryKeyValueStore, OfflineHostBackend, PreimageServer, RecordingHostBackend,
+    server::PreimageServerError, trace::run_native_client,
+};
+use clap::Parser;
+use kona_cli::cli_styles;
+use kona_preimage::{BidirectionalChannel, Channel, HintReader, OracleServer};
+use kona_std_fpvm::{FileChannel, FileDescriptor};
+use serde::Serialize;
+use std::{path::PathBuf, sync::Arc};
+use tokio::{
+    sync::RwLock,
+    task::{self, JoinHandle},
+};
+use tracing::info;
+
+/// The witness bundle host application. Serves the preimages of a witness bundle exported by a
+/// previous single-chain run, without any RPC access.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct BundleHost {
+    /// Path to the witness bundle to serve preimages from.
+    #[arg(long, env)]
+    pub bundle: PathBuf,
+    /// Record every preimage served to the client program, along with its type and size, and
+    /// write the read-set as JSON to the given path once the server exits.
+    #[arg(long, env)]
+    pub access_log: Option<PathBuf>,
+    /// Record every hint sent and preimage requested by the client program, with their sizes
+    /// and timings, and write the trace, along with the preimages served, as JSON to the given
+    /// path once the client program exits. The trace can be replayed without a host with
+    /// `kona-host replay`.
+    #[arg(long, conflicts_with = "server", env)]
+    pub trace: Option<PathBuf>,
+    /// Run the client program natively.
+    #[arg(long, conflicts_with = "server", required_unless_present = "server")]
+    pub native: bool,
+    /// Run in pre-image server mode without executing any client program. If not provided, the
+    /// host will run the client program in the host process.
+    #[arg(long, conflicts_with = "native", required_unless_present = "native")]
+    pub server: bool,
+}
+
+/// An error that can occur when handling witness bundle hosts
+#[derive(Debug, thiserror::Error)]
+pub enum BundleHostError {
+    /// An error when handling preimage requests.
+    #[error("Error handling preimage request: {0}")]
+    PreimageServerError(#[from] PreimageServerError),
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// An error when reading the witness bundle.
+    #[error("Witness bundle error: {0}")]
+    WitnessBundleError(#[from] WitnessBundleError),
+    /// Task failed to execute to completion.
+    #[error("Join error: {0}")]
+    ExecutionError(#[from] tokio::task::JoinError),
+}
+
+impl BundleHost {
+    /// Starts the [BundleHost] application.
+    pub async fn start(self) -> Result<(), BundleHostError> {
+        if self.server {
+            let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
+            let preimage =
+                FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
+
+            self.start_server(hint, preimage).await?.await?
+        } else {
+            self.start_native().await
+        }
+    }
+
+    /// Starts the preimage server, communicating with the client over the provided channels.
+    pub async fn start_server<C>(
+        &self,
+        hint: C,
+        preimage: C,
+    ) -> Result<JoinHandle<Result<(), BundleHostError>>, BundleHostError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
+        let bundle = WitnessBundle::read_from_file(&self.bundle)?;
+        info!(target: "bundle_host", "Loaded witness bundle with {} preimages", bundle.len());
+
+        let kv_store = Arc::new(RwLock::new(MemoryKeyValueStore::from(bundle)));
+        let backend = OfflineHostBackend::new(kv_store);
+        let Some(access_log_path) = self.access_log.clone() else {
+            return Ok(task::spawn(async {
+                PreimageServer::new(
+                    OracleServer::new(preimage),
+                    HintReader::new(hint),
+                    Arc::new(backend),
+                )
+                .start()
+                .await
+                .map_err(BundleHostError::from)
+            }));
+        };
+
+        Ok(task::spawn(async move {
+            RecordingHostBackend::new(backend)
+                .serve(hint, preimage, Some(&access_log_path))
+                .await
+                .map(drop)
+        }))
+    }
+
+    /// Starts the host in native mode, running both the client and preimage server in the same
+    /// process.
+    async fn start_native(&self) -> Result<(), BundleHostError> {
+        let hint = BidirectionalChannel::new()?;
+        let preimage = BidirectionalChannel::new()?;
+
+        let server_task = self.start_server(hint.host, preimage.host).await?;
+        let client_task =
+            task::spawn(run_native_client(preimage.client, hint.client, self.trace.clone()));
+
+        let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+        let client_result = client_result?;
+
+        // Bubble up the exit status of the client program if execution completes.
+        std::process::exit(client_result.is_err() as i32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BundleHost;
+    use clap::Parser;
+
+    #[test]
+    fn test_flags() {
+        let cases = [
+            // valid
+            (["--bundle", "dummy", "--native"].as_slice(), true),
+            (["--bundle", "dummy", "--server"].as_slice(), true),
+            (["--bundle", "dummy", "--native", "--access-log", "dummy"].as_slice(), true),
+            (["--bundle", "dummy", "--native", "--trace", "dummy"].as_slice(), true),
+            // invalid
+            (["--bundle", "dummy"].as_slice(), false),
+            (["--bundle", "dummy", "--native", "--server"].as_slice(), false),
+            (["--native"].as_slice(), false),
+            (["--bundle", "dummy", "--server", "--trace", "dummy"].as_slice(), false),
+        ];
+
+        for (args, valid) in cases.into_iter() {
+            let args = ["bundle"].iter().chain(args.iter()).cloned().collect::<Vec<_>>();
+            assert_eq!(BundleHost::try_parse_from(args).is_ok(), valid);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! This module contains the CLI for compacting a witness bundle, or a persistent preimage store,
+//! down to a recorded read-set.
+
+use super::{WitnessBundle, WitnessBundleError};
+use crate::{
+    DiskKeyValueBackend, DiskKeyValueStoreMetadata, MemoryKeyValueStore, PreimageAccessLog,
+};
+use anyhow::anyhow;
+use clap::Parser;
+use kona_cli::cli_styles;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Compacts a witness bundle, or a persistent preimage store, down to only the preimages that a
+/// client program run read, as recorded with `--access-log`.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct CompactBundle {
+    /// Path to the witness bundle to compact.
+    #[arg(long, conflicts_with = "data_dir", required_unless_present = "data_dir", env)]
+    pub bundle: Option<PathBuf>,
+    /// Path to the data directory of a persistent preimage store, written by a host run with
+    /// `--persist`, to compact instead of a witness bundle.
+    #[arg(long, env)]
+    pub data_dir: Option<PathBuf>,
+    /// The embedded database of the preimage store in `--data-dir`.
+    #[arg(long, value_enum, default_value_t, requires = "data_dir", env)]
+    pub kv_backend: DiskKeyValueBackend,
+    /// Path to the access log recorded by a host run with `--access-log`.
+    #[arg(long, env)]
+    pub access_log: PathBuf,
+    /// Path to write the compacted witness bundle to or, with `--data-dir`, the new data
+    /// directory to write the compacted preimage store to.
+    #[arg(long, env)]
+    pub output: PathBuf,
+}
+
+/// An error that can occur when compacting a witness bundle.
+#[derive(Debug, thiserror::Error)]
+pub enum CompactBundleError {
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// An error when reading or writing a witness bundle.
+    #[error("Witness bundle error: {0}")]
+    WitnessBundleError(#[from] WitnessBundleError),
+    /// A preimage in the access log is missing from the witness bundle.
+    #[error("Compaction error: {0}")]
+    CompactionError(anyhow::Error),
+}
+
+impl CompactBundle {
+    /// Runs the [CompactBundle] command.
+    pub async fn start(self) -> Result<(), CompactBundleError> {
+        let access_log = PreimageAccessLog::read_from_file(&self.access_log)?;
+
+        let original_len = match (&self.bundle, &self.data_dir) {
+            (Some(bundle), _) => self.compact_bundle(bundle, &access_log)?,
+            (None, Some(data_dir)) => self.compact_store(data_dir, &access_log)?,
+            (None, None) => {
+                return Err(CompactBundleError::CompactionError(anyhow!(
+                    "No witness bundle or data directory to compact"
+                )));
+            }
+        };
+
+        for (key_type, stats) in access_log.stats() {
+            info!(
+                target: "bundle_compact",
+                "{key_type:?}: {} preimages ({} bytes)",
+                stats.count,
+                stats.size
+            );
+        }
+        info!(
+            target: "bundle_compact",
+            "Compacted preimages from {original_len} to {} preimages ({} bytes)",
+            access_log.len(),
+            access_log.total_size()
+        );
+
+        Ok(())
+    }
+
+    /// Compacts the witness bundle at the given path into a new witness bundle at `--output`,
+    /// returning the number of preimages in the original bundle.
+    fn compact_bundle(
+        &self,
+        bundle: &Path,
+        access_log: &PreimageAccessLog,
+    ) -> Result<u64, CompactBundleError> {
+        let bundle = WitnessBundle::read_from_file(bundle)?;
+        let original_len = bundle.len() as u64;
+
+        let compacted = WitnessBundle::from(
+            access_log
+                .compact(&MemoryKeyValueStore::from(bundle))
+                .map_err(CompactBundleError::CompactionError)?,
+        );
+        compacted.write_to_file(&self.output)?;
+
+        Ok(original_len)
+    }
+
+    /// Compacts the persistent preimage store in the given data directory into a new persistent
+    /// store, of the same backend and chain ID, in `--output`, returning the number of preimages
+    /// in the original store.
+    fn compact_store(
+        &self,
+        data_dir: &Path,
+        access_log: &PreimageAccessLog,
+    ) -> Result<u64, CompactBundleError> {
+        let metadata = DiskKeyValueStoreMetadata::read(data_dir)
+            .map_err(CompactBundleError::CompactionError)?
+            .unwrap_or_default();
+
+        let compacted = {
+            let store = self
+                .kv_backend
+                .open(data_dir, true, true, None)
+                .map_err(CompactBundleError::CompactionError)?;
+            access_log.compact(&store).map_err(CompactBundleError::CompactionError)?
+        };
+
+        // The compacted store is flushed, along with its metadata, when it is dropped.
+        let mut output = self
+            .kv_backend
+            .open(&self.output, true, false, metadata.chain_id)
+            .map_err(CompactBundleError::CompactionError)?;
+        for (key, value) in compacted.store {
+            output.set(key, value).map_err(CompactBundleError::CompactionError)?;
+        }
+
+        Ok(metadata.total_keys())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::KeyValueStore;
+    use alloy_primitives::B256;
+    use kona_preimage::PreimageKey;
+
+    #[tokio::test]
+    async fn test_compact_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let used = PreimageKey::new_keccak256([1; 32]);
+        let unused = PreimageKey::new_keccak256([2; 32]);
+
+        let mut bundle = WitnessBundle::default();
+        bundle.insert(used.into(), vec![1]);
+        bundle.insert(unused.into(), vec![2]);
+        bundle.write_to_file(&dir.path().join("full.bundle")).unwrap();
+
+        let mut access_log = PreimageAccessLog::default();
+        access_log.record(used, 1);
+        access_log.write_to_file(&dir.path().join("access-log.json")).unwrap();
+
+        CompactBundle {
+            bundle: Some(dir.path().join("full.bundle")),
+            access_log: dir.path().join("access-log.json"),
+            output: dir.path().join("compact.bundle"),
+            ..Default::default()
+        }
+        .start()
+        .await
+        .unwrap();
+
+        let store = MemoryKeyValueStore::from(
+            WitnessBundle::read_from_file(&dir.path().join("compact.bundle")).unwrap(),
+        );
+        assert_eq!(store.get(used.into()), Some(vec![1]));
+        assert_eq!(store.get(B256::from(unused)), None);
+    }
+
+    /// Compacts a persistent store of the given backend, and checks that only the read-set is
+    /// kept in the compacted store, along with the chain ID of the original store.
+    async fn test_compact_store(kv_backend: DiskKeyValueBackend) {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        let output = dir.path().join("compact");
+        let used = PreimageKey::new_keccak256([1; 32]);
+        let unused = PreimageKey::new_keccak256([2; 32]);
+
+        let mut store = kv_backend.open(&data_dir, true, false, Some(10)).unwrap();
+        store.set(used.into(), vec![1]).unwrap();
+        store.set(unused.into(), vec![2]).unwrap();
+        drop(store);
+
+        let mut access_log = PreimageAccessLog::default();
+        access_log.record(used, 1);
+        access_log.write_to_file(&dir.path().join("access-log.json")).unwrap();
+
+        CompactBundle {
+            data_dir: Some(data_dir),
+            kv_backend,
+            access_log: dir.path().join("access-log.json"),
+            output: output.clone(),
+            ..Default::default()
+        }
+        .start()
+        .await
+        .unwrap();
+
+        let metadata = DiskKeyValueStoreMetadata::read(&output).unwrap().unwrap();
+        assert_eq!(metadata.chain_id, Some(10));
+        assert_eq!(metadata.total_keys(), 1);
+
+        let store = kv_backend.open(&output, true, true, Some(10)).unwrap();
+        assert_eq!(store.get(used.into()), Some(vec![1]));
+        assert_eq!(store.get(B256::from(unused)), None);
+    }
+
+    #[tokio::test]
+    async fn test_compact_rocksdb_store() {
+        test_compact_store(DiskKeyValueBackend::RocksDb).await;
+    }
+
+    #[cfg(feature = "redb")]
+    #[tokio::test]
+    async fn test_compact_redb_store() {
+        test_compact_store(DiskKeyValueBackend::Redb).await;
+    }
+}
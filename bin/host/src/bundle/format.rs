@@ -0,0 +1,250 @@
+//! Contains the [WitnessBundle] type, a portable, checksummed container for the preimages of a
+//! single proof run.
+
+use crate::MemoryKeyValueStore;
+use alloy_primitives::{B256, keccak256};
+use std::{collections::BTreeMap, path::Path};
+
+/// The magic bytes at the start of every witness bundle.
+pub const WITNESS_BUNDLE_MAGIC: [u8; 8] = *b"KONAWB\0\0";
+
+/// The current version of the witness bundle format.
+pub const WITNESS_BUNDLE_VERSION: u32 = 1;
+
+/// The length of the bundle header: magic, version and entry count.
+const HEADER_LENGTH: usize = 8 + 4 + 8;
+
+/// The length of the trailing checksum.
+const CHECKSUM_LENGTH: usize = 32;
+
+/// An error that can occur when reading or writing a [WitnessBundle].
+#[derive(Debug, thiserror::Error)]
+pub enum WitnessBundleError {
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// The bundle does not start with [WITNESS_BUNDLE_MAGIC].
+    #[error("Invalid witness bundle magic")]
+    InvalidMagic,
+    /// The bundle was written with an unsupported version of the format.
+    #[error("Unsupported witness bundle version: {0}")]
+    UnsupportedVersion(u32),
+    /// The bundle ended before all of its entries could be read.
+    #[error("Witness bundle is truncated")]
+    Truncated,
+    /// The bundle holds trailing data after its entries.
+    #[error("Witness bundle has {0} trailing bytes")]
+    TrailingBytes(usize),
+    /// The checksum of the bundle does not match its contents.
+    #[error("Witness bundle checksum mismatch. Expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// The checksum stored in the bundle.
+        expected: B256,
+        /// The checksum of the bundle's contents.
+        actual: B256,
+    },
+}
+
+/// A [WitnessBundle] holds every preimage needed to run the client program for a single claim,
+/// including the local boot information, so that a proof can be reproduced fully offline.
+///
+/// **Layout** (all integers are big-endian):
+/// | Field       | Size          | Description                                      |
+/// |-------------|---------------|--------------------------------------------------|
+/// | magic       | 8             | [WITNESS_BUNDLE_MAGIC]                           |
+/// | version     | 4             | [WITNESS_BUNDLE_VERSION]                         |
+/// | count       | 8             | The number of entries                            |
+/// | entries     | variable      | `key (32) ++ len (4) ++ value (len)`, key-sorted |
+/// | checksum    | 32            | `keccak256` of all preceding bytes               |
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WitnessBundle {
+    /// The preimages in the bundle, keyed by their preimage key.
+    pub preimages: BTreeMap<B256, Vec<u8>>,
+}
+
+impl WitnessBundle {
+    /// Inserts a preimage into the bundle.
+    pub fn insert(&mut self, key: B256, value: Vec<u8>) {
+        self.preimages.insert(key, value);
+    }
+
+    /// Returns the number of preimages in the bundle.
+    pub fn len(&self) -> usize {
+        self.preimages.len()
+    }
+
+    /// Returns `true` if the bundle holds no preimages.
+    pub fn is_empty(&self) -> bool {
+        self.preimages.is_empty()
+    }
+
+    /// Encodes the bundle into its binary representation.
+    pub fn encode(&self) -> Vec<u8> {
+        let payload_len = self.preimages.values().map(|v| 32 + 4 + v.len()).sum::<usize>();
+        let mut buf = Vec::with_capacity(HEADER_LENGTH + payload_len + CHECKSUM_LENGTH);
+
+        buf.extend_from_slice(&WITNESS_BUNDLE_MAGIC);
+        buf.extend_from_slice(&WITNESS_BUNDLE_VERSION.to_be_bytes());
+        buf.extend_from_slice(&(self.preimages.len() as u64).to_be_bytes());
+        for (key, value) in self.preimages.iter() {
+            buf.extend_from_slice(key.as_slice());
+            buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            buf.extend_from_slice(value);
+        }
+
+        let checksum = keccak256(&buf);
+        buf.extend_from_slice(checksum.as_slice());
+        buf
+    }
+
+    /// Decodes a bundle from its binary representation, verifying its checksum.
+    pub fn decode(buf: &[u8]) -> Result<Self, WitnessBundleError> {
+        if buf.len() < HEADER_LENGTH + CHECKSUM_LENGTH {
+            return Err(WitnessBundleError::Truncated);
+        }
+        if buf[..8] != WITNESS_BUNDLE_MAGIC {
+            return Err(WitnessBundleError::InvalidMagic);
+        }
+
+        let (data, checksum) = buf.split_at(buf.len() - CHECKSUM_LENGTH);
+        let expected = B256::from_slice(checksum);
+        let actual = keccak256(data);
+        if expected != actual {
+            return Err(WitnessBundleError::ChecksumMismatch { expected, actual });
+        }
+
+        let mut reader = &data[8..];
+        let version = u32::from_be_bytes(take(&mut reader)?);
+        if version != WITNESS_BUNDLE_VERSION {
+            return Err(WitnessBundleError::UnsupportedVersion(version));
+        }
+
+        let count = u64::from_be_bytes(take(&mut reader)?);
+        let mut preimages = BTreeMap::new();
+        for _ in 0..count {
+            let key = B256::from(take::<32>(&mut reader)?);
+            let len = u32::from_be_bytes(take(&mut reader)?) as usize;
+            if reader.len() < len {
+                return Err(WitnessBundleError::Truncated);
+            }
+            let (value, rest) = reader.split_at(len);
+            preimages.insert(key, value.to_vec());
+            reader = rest;
+        }
+
+        if !reader.is_empty() {
+            return Err(WitnessBundleError::TrailingBytes(reader.len()));
+        }
+
+        Ok(Self { preimages })
+    }
+
+    /// Writes the bundle to the file at the given path.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), WitnessBundleError> {
+        std::fs::write(path, self.encode()).map_err(Into::into)
+    }
+
+    /// Reads and verifies a bundle from the file at the given path.
+    pub fn read_from_file(path: &Path) -> Result<Self, WitnessBundleError> {
+        Self::decode(&std::fs::read(path)?)
+    }
+}
+
+impl From<&MemoryKeyValueStore> for WitnessBundle {
+    fn from(store: &MemoryKeyValueStore) -> Self {
+        Self { preimages: store.store.iter().map(|(k, v)| (*k, v.clone())).collect() }
+    }
+}
+
+impl From<MemoryKeyValueStore> for WitnessBundle {
+    fn from(store: MemoryKeyValueStore) -> Self {
+        Self { preimages: store.store.into_iter().collect() }
+    }
+}
+
+impl From<WitnessBundle> for MemoryKeyValueStore {
+    fn from(bundle: WitnessBundle) -> Self {
+        let mut store = Self::new();
+        store.store.extend(bundle.preimages);
+        store
+    }
+}
+
+/// Takes a fixed number of bytes from the front of the reader.
+fn take<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], WitnessBundleError> {
+    if reader.len() < N {
+        return Err(WitnessBundleError::Truncated);
+    }
+    let (head, rest) = reader.split_at(N);
+    *reader = rest;
+    Ok(head.try_into().expect("Length checked above"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::KeyValueStore;
+    use proptest::{
+        arbitrary::any,
+        collection::{btree_map, vec},
+        proptest,
+        test_runner::Config,
+    };
+
+    proptest! {
+        #![proptest_config(Config::with_cases(16))]
+
+        /// Test that encoding and decoding a [WitnessBundle] is lossless.
+        #[test]
+        fn roundtrip_witness_bundle(k_v in btree_map(any::<[u8; 32]>(), vec(any::<u8>(), 0..128), 0..128)) {
+            let bundle = WitnessBundle { preimages: k_v.into_iter().map(|(k, v)| (k.into(), v)).collect() };
+            assert_eq!(WitnessBundle::decode(&bundle.encode()).unwrap(), bundle);
+        }
+    }
+
+    fn test_bundle() -> WitnessBundle {
+        let mut bundle = WitnessBundle::default();
+        bundle.insert(B256::with_last_byte(1), vec![0xde, 0xad]);
+        bundle.insert(B256::with_last_byte(2), vec![0xbe, 0xef]);
+        bundle
+    }
+
+    #[test]
+    fn test_decode_checksum_mismatch() {
+        let mut encoded = test_bundle().encode();
+        encoded[HEADER_LENGTH + 32 + 4] ^= 0xFF;
+        assert!(matches!(
+            WitnessBundle::decode(&encoded),
+            Err(WitnessBundleError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_invalid_magic() {
+        let mut encoded = test_bundle().encode();
+        encoded[0] = 0;
+        assert!(matches!(WitnessBundle::decode(&encoded), Err(WitnessBundleError::InvalidMagic)));
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        let encoded = test_bundle().encode();
+        assert!(matches!(
+            WitnessBundle::decode(&encoded[..HEADER_LENGTH]),
+            Err(WitnessBundleError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn test_write_and_read_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("witness.bundle");
+
+        let bundle = test_bundle();
+        bundle.write_to_file(&path).unwrap();
+
+        let store = MemoryKeyValueStore::from(WitnessBundle::read_from_file(&path).unwrap());
+        assert_eq!(store.get(B256::with_last_byte(1)), Some(vec![0xde, 0xad]));
+        assert_eq!(store.get(B256::with_last_byte(2)), Some(vec![0xbe, 0xef]));
+    }
+}
@@ -0,0 +1,12 @@
+//! This module contains the witness bundle format and the host mode that serves preimages from a
+//! witness bundle, without any RPC access, along with a tool to compact a witness bundle down to a
+//! recorded read-set.
+
+mod format;
+pub use format::{WITNESS_BUNDLE_MAGIC, WITNESS_BUNDLE_VERSION, WitnessBundle, WitnessBundleError};
+
+mod cfg;
+pub use cfg::{BundleHost, BundleHostError};
+
+mod compact;
+pub use compact::{CompactBundle, CompactBundleError};
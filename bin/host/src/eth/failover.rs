@@ -0,0 +1,179 @@
+//! Contains [Failover], which spreads requests over several redundant endpoints, and the
+//! transports and clients built on it.
+
+use alloy_eips::eip4844::IndexedBlobHash;
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_rpc_types_beacon::sidecar::BlobData;
+use alloy_transport::{TransportError, TransportFut};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use kona_providers_alloy::{
+    APIConfigResponse, APIGenesisResponse, BeaconClient, OnlineBeaconClient,
+};
+use reqwest::Client;
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower::Service;
+use tracing::warn;
+
+/// The health of a single endpoint of a [Failover].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// The number of requests that have failed since the last successful request.
+    pub consecutive_failures: u32,
+    /// The total number of successful requests.
+    pub successes: u64,
+    /// The total number of failed requests.
+    pub failures: u64,
+}
+
+/// A single endpoint of a [Failover].
+#[derive(Debug)]
+struct Endpoint<T> {
+    /// The client of the endpoint.
+    client: T,
+    /// The health of the endpoint.
+    health: Mutex<EndpointHealth>,
+}
+
+/// A set of redundant endpoints serving the same data.
+///
+/// Each request is sent to one endpoint at a time, healthiest first, and fails over to the next
+/// endpoint if it fails. Endpoints are ranked by the number of requests that have failed on them
+/// since their last success, and ties keep the order in which the endpoints were configured, so
+/// that the first endpoint is preferred while it is healthy.
+#[derive(Debug)]
+pub struct Failover<T> {
+    endpoints: Arc<Vec<Endpoint<T>>>,
+}
+
+impl<T> Clone for Failover<T> {
+    fn clone(&self) -> Self {
+        Self { endpoints: self.endpoints.clone() }
+    }
+}
+
+impl<T: Clone> Failover<T> {
+    /// Creates a new [Failover] over the given endpoint clients, in order of preference.
+    ///
+    /// # Panics
+    /// Panics if no endpoints are given.
+    pub fn new(clients: impl IntoIterator<Item = T>) -> Self {
+        let endpoints = clients
+            .into_iter()
+            .map(|client| Endpoint { client, health: Default::default() })
+            .collect::<Vec<_>>();
+        assert!(!endpoints.is_empty(), "Failover requires at least one endpoint");
+        Self { endpoints: Arc::new(endpoints) }
+    }
+
+    /// Returns the [EndpointHealth] of each endpoint, in the order they were configured.
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        self.endpoints.iter().map(|e| *e.health.lock().expect("Lock poisoned")).collect()
+    }
+
+    /// Sends a request to the healthiest endpoint, failing over to the other endpoints in order
+    /// of their health until one succeeds. Returns the error of the last endpoint if all fail.
+    pub async fn request<R, E, F, Fut>(&self, mut f: F) -> Result<R, E>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+        E: Display,
+    {
+        let mut ranked = self.endpoints.iter().enumerate().collect::<Vec<_>>();
+        ranked.sort_by_key(|(_, e)| e.health.lock().expect("Lock poisoned").consecutive_failures);
+
+        let mut last_error = None;
+        for (index, endpoint) in ranked {
+            match f(endpoint.client.clone()).await {
+                Ok(response) => {
+                    let mut health = endpoint.health.lock().expect("Lock poisoned");
+                    health.consecutive_failures = 0;
+                    health.successes += 1;
+                    return Ok(response);
+                }
+                Err(e) => {
+                    let mut health = endpoint.health.lock().expect("Lock poisoned");
+                    health.consecutive_failures += 1;
+                    health.failures += 1;
+                    if self.endpoints.len() > 1 {
+                        warn!(target: "failover", "Request to endpoint #{index} failed: {e}");
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.expect("At least one endpoint was tried"))
+    }
+}
+
+impl Service<RequestPacket> for Failover<Http<Client>> {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move { this.request(|mut transport| transport.call(req.clone())).await })
+    }
+}
+
+/// A [BeaconClient] that fails over between several beacon API endpoints.
+pub type FailoverBeaconClient = Failover<OnlineBeaconClient>;
+
+#[async_trait]
+impl BeaconClient for FailoverBeaconClient {
+    type Error = reqwest::Error;
+
+    async fn config_spec(&self) -> Result<APIConfigResponse, Self::Error> {
+        self.request(|client| async move { client.config_spec().await }).await
+    }
+
+    async fn beacon_genesis(&self) -> Result<APIGenesisResponse, Self::Error> {
+        self.request(|client| async move { client.beacon_genesis().await }).await
+    }
+
+    async fn beacon_blob_side_cars(
+        &self,
+        slot: u64,
+        hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BlobData>, Self::Error> {
+        self.request(|client| async move { client.beacon_blob_side_cars(slot, hashes).await }).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fails_over_to_healthy_endpoints() {
+        let failover = Failover::new([0, 1, 2]);
+
+        // Endpoint #0 fails, so the request fails over to endpoint #1.
+        let response = failover.request(|id| async move { if id == 0 { Err(id) } else { Ok(id) } });
+        assert_eq!(response.await, Ok(1));
+
+        // Endpoint #0 is now ranked behind the healthy endpoints.
+        assert_eq!(failover.request(|id| async move { Ok::<_, u8>(id) }).await, Ok(1));
+        assert_eq!(
+            failover.health(),
+            vec![
+                EndpointHealth { consecutive_failures: 1, successes: 0, failures: 1 },
+                EndpointHealth { consecutive_failures: 0, successes: 2, failures: 0 },
+                EndpointHealth::default(),
+            ]
+        );
+
+        // Once every endpoint fails, the last error is returned.
+        assert_eq!(failover.request(|id| async move { Err::<(), _>(id) }).await, Err(0));
+    }
+}
@@ -0,0 +1,344 @@
+//! Contains the [L2StateFetcher], which fetches L2 state trie nodes and contract code with one of
+//! several [L2StateFetchMode]s.
+
+use crate::SharedKeyValueStore;
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, B256, Bytes, keccak256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::Decodable;
+use alloy_rpc_types::debug::ExecutionWitness;
+use anyhow::{Result, anyhow, bail};
+use kona_mpt::TrieNode;
+use kona_preimage::PreimageKey;
+use op_alloy_network::Optimism;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+use tracing::{info, warn};
+
+/// The strategy with which the host fetches L2 state trie nodes and contract code by hash.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+pub enum L2StateFetchMode {
+    /// Probe the L2 endpoint, and use `db-get` if it serves state trie nodes by hash, or
+    /// `witness` otherwise.
+    #[default]
+    Auto,
+    /// Fetch state trie nodes and contract code by hash with `debug_dbGet`.
+    DbGet,
+    /// Fetch contract code with `eth_getCode`, and state trie nodes with
+    /// `debug_executionWitness`. Account and storage proofs are always fetched with
+    /// `eth_getProof`.
+    Witness,
+}
+
+/// The block context that the [L2StateFetchMode::Witness] mode uses to fetch preimages by hash,
+/// gathered from the account and storage proofs fetched so far.
+#[derive(Debug, Default)]
+struct WitnessContext {
+    /// The address of an account holding the code with the given hash, and the number of the
+    /// block at which it held it.
+    code_index: HashMap<B256, (Address, u64)>,
+    /// The number of the block whose state references the trie node with the given hash, taken
+    /// from the hint of the proof or execution witness that the node was referenced by.
+    node_index: HashMap<B256, u64>,
+    /// The blocks whose execution witnesses have been fetched and stored.
+    witnessed_blocks: HashSet<u64>,
+}
+
+impl WitnessContext {
+    /// Indexes the trie nodes referenced by the given encoded trie node under the given block.
+    fn index_children(&mut self, mut node: &[u8], block_number: u64) {
+        let children = match TrieNode::decode(&mut node) {
+            Ok(TrieNode::Branch { stack }) => stack,
+            Ok(TrieNode::Extension { node, .. }) => vec![*node],
+            _ => return,
+        };
+        for child in children {
+            if let TrieNode::Blinded { commitment } = child {
+                self.node_index.insert(commitment, block_number);
+            }
+        }
+    }
+}
+
+/// Fetches L2 account and storage proofs, state trie nodes and contract code into the
+/// key-value store, with the configured [L2StateFetchMode].
+///
+/// In [L2StateFetchMode::Witness] mode, preimages requested by hash are resolved through the
+/// block context of the proofs fetched before them. The client always hints an account's proof
+/// before it reads the account's code or the trie nodes surrounding it, so code is fetched with
+/// `eth_getCode` for an account known to hold it, and trie nodes are fetched from the
+/// execution witness of the block built on top of the state that the hinted proof referencing
+/// them was taken at.
+#[derive(Debug)]
+pub struct L2StateFetcher {
+    /// The L2 provider.
+    provider: RootProvider<Optimism>,
+    /// The resolved fetch mode. Never [L2StateFetchMode::Auto].
+    mode: L2StateFetchMode,
+    /// The block context for the [L2StateFetchMode::Witness] mode.
+    context: Mutex<WitnessContext>,
+}
+
+impl L2StateFetcher {
+    /// Creates a new [L2StateFetcher] with the given mode. If the mode is
+    /// [L2StateFetchMode::Auto], the provider is probed for `debug_dbGet` support.
+    pub async fn new(provider: RootProvider<Optimism>, mode: L2StateFetchMode) -> Result<Self> {
+        let mode = match mode {
+            L2StateFetchMode::Auto => Self::probe(&provider).await?,
+            mode => mode,
+        };
+        Ok(Self { provider, mode, context: Default::default() })
+    }
+
+    /// Returns the resolved [L2StateFetchMode] of the fetcher.
+    pub const fn mode(&self) -> L2StateFetchMode {
+        self.mode
+    }
+
+    /// Probes whether the provider serves state trie nodes by hash with `debug_dbGet`, by
+    /// requesting the state root node of the latest block.
+    async fn probe(provider: &RootProvider<Optimism>) -> Result<L2StateFetchMode> {
+        let latest = provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+            .ok_or(anyhow!("Latest L2 block not found"))?;
+        let state_root = latest.header.state_root;
+
+        let node = provider.client().request::<_, Bytes>("debug_dbGet", &[state_root]).await;
+        let mode = match node {
+            Ok(node) if keccak256(&node) == state_root => L2StateFetchMode::DbGet,
+            _ => L2StateFetchMode::Witness,
+        };
+        info!(target: "l2_state_fetcher", "Probed L2 endpoint; Using {mode:?} mode");
+        Ok(mode)
+    }
+
+    /// Fetches the account proof, and the storage proofs of the given slots, of an account at the
+    /// given block, and stores their nodes.
+    pub async fn fetch_proof(
+        &self,
+        address: Address,
+        slots: Vec<B256>,
+        block_number: u64,
+        kv: &SharedKeyValueStore,
+    ) -> Result<()> {
+        let proof_response =
+            self.provider.get_proof(address, slots).block_id(block_number.into()).await?;
+
+        let nodes = proof_response
+            .account_proof
+            .into_iter()
+            .chain(proof_response.storage_proof.into_iter().flat_map(|proof| proof.proof))
+            .collect::<Vec<_>>();
+
+        if self.mode == L2StateFetchMode::Witness {
+            let mut context = self.context.lock().expect("Lock poisoned");
+            if ![KECCAK_EMPTY, B256::ZERO].contains(&proof_response.code_hash) {
+                context.code_index.insert(proof_response.code_hash, (address, block_number));
+            }
+            context.node_index.insert(proof_response.storage_hash, block_number);
+            for node in &nodes {
+                context.index_children(node, block_number);
+            }
+        }
+
+        let mut kv_lock = kv.write().await;
+        for node in nodes {
+            let key = PreimageKey::new_keccak256(*keccak256(node.as_ref()));
+            kv_lock.set(key.into(), node.into())?;
+        }
+        Ok(())
+    }
+
+    /// Fetches the contract code with the given hash, and stores it.
+    pub async fn fetch_code(&self, hash: B256, kv: &SharedKeyValueStore) -> Result<()> {
+        let code = match self.mode {
+            L2StateFetchMode::Witness => {
+                let account =
+                    self.context.lock().expect("Lock poisoned").code_index.get(&hash).copied();
+                let Some((address, block_number)) = account else {
+                    warn!(
+                        target: "l2_state_fetcher",
+                        "No account known to hold code {hash}; Fetching execution witness"
+                    );
+                    return self.fetch_witness(hash, kv).await;
+                };
+
+                self.provider.get_code_at(address).block_id(block_number.into()).await?
+            }
+            _ => self.db_get_code(hash).await?,
+        };
+
+        let mut kv_lock = kv.write().await;
+        kv_lock.set(PreimageKey::new_keccak256(*hash).into(), code.into())
+    }
+
+    /// Fetches the state trie node with the given hash, and stores it.
+    pub async fn fetch_state_node(&self, hash: B256, kv: &SharedKeyValueStore) -> Result<()> {
+        if self.mode == L2StateFetchMode::Witness {
+            return self.fetch_witness(hash, kv).await;
+        }
+
+        let preimage: Bytes = self.provider.client().request("debug_dbGet", &[hash]).await?;
+
+        let mut kv_lock = kv.write().await;
+        kv_lock.set(PreimageKey::new_keccak256(*hash).into(), preimage.into())
+    }
+
+    /// Fetches contract code by hash with `debug_dbGet`.
+    async fn db_get_code(&self, hash: B256) -> Result<Bytes> {
+        // geth hashdb scheme code hash key prefix
+        const CODE_PREFIX: u8 = b'c';
+
+        // Attempt to fetch the code from the L2 chain provider.
+        let code_key = [&[CODE_PREFIX], hash.as_slice()].concat();
+        let code = self
+            .provider
+            .client()
+            .request::<&[Bytes; 1], Bytes>("debug_dbGet", &[code_key.into()])
+            .await;
+
+        // Check if the first attempt to fetch the code failed. If it did, try fetching the
+        // code hash preimage without the geth hashdb scheme prefix.
+        match code {
+            Ok(code) => Ok(code),
+            Err(_) => self
+                .provider
+                .client()
+                .request::<&[B256; 1], Bytes>("debug_dbGet", &[hash])
+                .await
+                .map_err(|e| anyhow!("Error fetching code hash preimage: {e}")),
+        }
+    }
+
+    /// Fetches the execution witness of the block built on top of the state that references
+    /// `hash`, and stores all of its preimages. Errors if the witness does not hold the preimage
+    /// of `hash`.
+    async fn fetch_witness(&self, hash: B256, kv: &SharedKeyValueStore) -> Result<()> {
+        let key = PreimageKey::new_keccak256(*hash).into();
+        let (parent, witnessed) = {
+            let context = self.context.lock().expect("Lock poisoned");
+            let parent = *context
+                .node_index
+                .get(&hash)
+                .ok_or(anyhow!("No proven L2 state references preimage {hash}"))?;
+            (parent, context.witnessed_blocks.contains(&(parent + 1)))
+        };
+        let block_number = parent + 1;
+
+        // The preimage may have been stored by a concurrent fetch of the same witness.
+        if kv.read().await.get(key).is_some() {
+            return Ok(());
+        }
+        if witnessed {
+            bail!("Execution witness of L2 block {block_number} does not hold preimage {hash}");
+        }
+
+        let witness: ExecutionWitness = self
+            .provider
+            .client()
+            .request("debug_executionWitness", [BlockNumberOrTag::Number(block_number)])
+            .await?;
+
+        {
+            let mut context = self.context.lock().expect("Lock poisoned");
+            for node in &witness.state {
+                context.index_children(node, parent);
+            }
+        }
+
+        let preimages = witness.state.into_iter().chain(witness.codes).chain(witness.keys);
+
+        let mut kv_lock = kv.write().await;
+        for preimage in preimages {
+            let key = PreimageKey::new_keccak256(*keccak256(preimage.as_ref()));
+            kv_lock.set(key.into(), preimage.into())?;
+        }
+        self.context.lock().expect("Lock poisoned").witnessed_blocks.insert(block_number);
+
+        if kv_lock.get(key).is_none() {
+            bail!("Execution witness of L2 block {block_number} does not hold preimage {hash}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types::EIP1186AccountProofResponse;
+    use alloy_transport::mock::Asserter;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    async fn witness_fetcher(asserter: &Asserter) -> L2StateFetcher {
+        let provider = RootProvider::new(RpcClient::mocked(asserter.clone()));
+        L2StateFetcher::new(provider, L2StateFetchMode::Witness).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_witness_mode_fetches_code_of_proven_accounts() {
+        let asserter = Asserter::new();
+        let fetcher = witness_fetcher(&asserter).await;
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let address = Address::with_last_byte(1);
+        let node = Bytes::from_static(&[0xc0]);
+        asserter.push_success(&EIP1186AccountProofResponse {
+            address,
+            code_hash: keccak256(&code),
+            account_proof: vec![node.clone()],
+            ..Default::default()
+        });
+        fetcher.fetch_proof(address, vec![], 10, &kv).await.unwrap();
+
+        asserter.push_success(&code);
+        fetcher.fetch_code(keccak256(&code), &kv).await.unwrap();
+
+        let kv = kv.read().await;
+        assert_eq!(kv.get(PreimageKey::new_keccak256(*keccak256(&node)).into()), Some(node.into()));
+        assert_eq!(kv.get(PreimageKey::new_keccak256(*keccak256(&code)).into()), Some(code.into()));
+    }
+
+    #[tokio::test]
+    async fn test_witness_mode_fetches_state_nodes_from_execution_witness() {
+        let asserter = Asserter::new();
+        let fetcher = witness_fetcher(&asserter).await;
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+
+        // Without a proven state referencing the node, there is no block to fetch the witness of.
+        let node = Bytes::from_static(&[0xc1, 0x80]);
+        assert!(fetcher.fetch_state_node(keccak256(&node), &kv).await.is_err());
+
+        let mut stack = vec![TrieNode::Empty; 17];
+        stack[0] = TrieNode::new_blinded(keccak256(&node));
+        let branch = Bytes::from(alloy_rlp::encode(TrieNode::Branch { stack }));
+        asserter.push_success(&EIP1186AccountProofResponse {
+            account_proof: vec![branch],
+            ..Default::default()
+        });
+        fetcher.fetch_proof(Address::ZERO, vec![], 10, &kv).await.unwrap();
+
+        asserter
+            .push_success(&ExecutionWitness { state: vec![node.clone()], ..Default::default() });
+        fetcher.fetch_state_node(keccak256(&node), &kv).await.unwrap();
+        assert_eq!(
+            kv.read().await.get(PreimageKey::new_keccak256(*keccak256(&node)).into()),
+            Some(node.clone().into())
+        );
+
+        // Nodes that are already stored are not fetched again.
+        fetcher.fetch_state_node(keccak256(&node), &kv).await.unwrap();
+
+        // The storage root of the proven account is referenced by the state of block 10, but the
+        // witness of block 11 has been fetched, and does not hold it.
+        assert!(fetcher.fetch_state_node(B256::ZERO, &kv).await.is_err());
+    }
+}
@@ -3,14 +3,36 @@
 use alloy_provider::{Network, RootProvider};
 use alloy_rpc_client::RpcClient;
 use alloy_transport_http::Http;
+use kona_providers_alloy::OnlineBeaconClient;
 use reqwest::Client;
 
 mod precompiles;
 pub(crate) use precompiles::execute;
 
+mod failover;
+pub use failover::{EndpointHealth, Failover, FailoverBeaconClient};
+
+mod l2_state;
+pub use l2_state::{L2StateFetchMode, L2StateFetcher};
+
 /// Returns an HTTP provider for the given URL.
 pub fn http_provider<N: Network>(url: &str) -> RootProvider<N> {
     let url = url.parse().unwrap();
     let http = Http::<Client>::new(url);
     RootProvider::new(RpcClient::new(http, true))
 }
+
+/// Returns an HTTP provider that fails over between the given redundant URLs.
+pub fn failover_http_provider<N: Network>(urls: &[String]) -> RootProvider<N> {
+    if let [url] = urls {
+        return http_provider(url);
+    }
+
+    let transports = urls.iter().map(|url| Http::<Client>::new(url.parse().unwrap()));
+    RootProvider::new(RpcClient::new(Failover::new(transports), true))
+}
+
+/// Returns a beacon client that fails over between the given redundant URLs.
+pub fn failover_beacon_client(urls: &[String]) -> FailoverBeaconClient {
+    Failover::new(urls.iter().cloned().map(OnlineBeaconClient::new_http))
+}
@@ -2,9 +2,15 @@
 
 use super::{InteropHintHandler, InteropLocalInputs};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    BoxedKeyValueStore, DEFAULT_FETCH_BACKOFF, DEFAULT_FETCH_MAX_BACKOFF, DEFAULT_FETCH_RETRIES,
+    DEFAULT_MAX_CONCURRENT_FETCHES, DiskKeyValueBackend, HintRetryPolicy, MemoryKeyValueStore,
+    OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg, PreimageServer,
+    SharedCacheKeyValueStore, SharedKeyValueStore, SplitKeyValueStore, VerifyingKeyValueStore,
+    eth::{
+        FailoverBeaconClient, L2StateFetchMode, L2StateFetcher, failover_beacon_client,
+        failover_http_provider, http_provider,
+    },
+    server::PreimageServerError,
 };
 use alloy_primitives::{B256, Bytes};
 use alloy_provider::{Provider, RootProvider};
@@ -15,11 +21,13 @@ use kona_preimage::{
     BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
 };
 use kona_proof_interop::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use kona_providers_alloy::OnlineBlobProvider;
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
-use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap, num::NonZeroUsize, path::PathBuf, str::FromStr, sync::Arc, time::Duration,
+};
 use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
@@ -44,7 +52,9 @@ pub struct InteropHost {
     /// Claimed L2 timestamp, corresponding to the L2 post-state.
     #[arg(long, visible_alias = "l2-timestamp", env)]
     pub claimed_l2_timestamp: u64,
-    /// Addresses of L2 JSON-RPC endpoints to use (eth and debug namespace required).
+    /// Addresses of L2 JSON-RPC endpoints to use (eth and debug namespace required). Endpoints
+    /// are grouped by the chain they serve; requests fail over between the redundant endpoints of
+    /// a chain, in the order they were given.
     #[arg(
         long,
         visible_alias = "l2s",
@@ -54,24 +64,29 @@ pub struct InteropHost {
         env
     )]
     pub l2_node_addresses: Option<Vec<String>>,
-    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required)
+    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required). Several
+    /// comma-separated addresses of redundant endpoints may be given, in order of preference;
+    /// requests fail over between them.
     #[arg(
         long,
         visible_alias = "l1",
         requires = "l2_node_addresses",
         requires = "l1_beacon_address",
+        value_delimiter = ',',
         env
     )]
-    pub l1_node_address: Option<String>,
-    /// Address of the L1 Beacon API endpoint to use.
+    pub l1_node_address: Option<Vec<String>>,
+    /// Address of the L1 Beacon API endpoint to use. Several comma-separated addresses of
+    /// redundant endpoints may be given, in order of preference; requests fail over between them.
     #[arg(
         long,
         visible_alias = "beacon",
         requires = "l1_node_address",
         requires = "l2_node_addresses",
+        value_delimiter = ',',
         env
     )]
-    pub l1_beacon_address: Option<String>,
+    pub l1_beacon_address: Option<Vec<String>>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
@@ -81,6 +96,39 @@ pub struct InteropHost {
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// Keep the preimage database in `--data-dir` after the host exits, so that later runs can
+    /// reuse it. Offline runs open a persistent database read-only.
+    #[arg(long, requires = "data_dir", env)]
+    pub persist: bool,
+    /// The embedded database to store preimages in `--data-dir` with.
+    #[arg(long, value_enum, default_value_t, requires = "data_dir", env)]
+    pub kv_backend: DiskKeyValueBackend,
+    /// A shared, content-addressed preimage cache directory. Preimages are read from the cache
+    /// before being fetched from the providers, and fetched preimages are written back to it, so
+    /// that several hosts on one machine can share them.
+    #[arg(long, env)]
+    pub cache_dir: Option<PathBuf>,
+    /// The strategy with which L2 state trie nodes and contract code are fetched by hash. `auto`
+    /// probes the L2 endpoint, and falls back to `witness` if it does not serve `debug_dbGet`.
+    #[arg(long, value_enum, default_value_t, env)]
+    pub l2_state_fetch_mode: L2StateFetchMode,
+    /// The maximum number of hint fetches to run concurrently against the RPC endpoints. Lower
+    /// values reduce the load on rate-limited endpoints. Defaults to
+    /// [DEFAULT_MAX_CONCURRENT_FETCHES].
+    #[arg(long, env)]
+    pub max_concurrent_fetches: Option<NonZeroUsize>,
+    /// The number of times a failed hint fetch is retried, with exponential backoff. Defaults to
+    /// [DEFAULT_FETCH_RETRIES].
+    #[arg(long, env)]
+    pub fetch_retries: Option<u32>,
+    /// The delay before the first retry of a failed hint fetch, in milliseconds. The delay
+    /// doubles with every retry. Defaults to [DEFAULT_FETCH_BACKOFF].
+    #[arg(long, env)]
+    pub fetch_backoff_ms: Option<u64>,
+    /// The time after which a single attempt to fetch a hint is abandoned, in seconds. By
+    /// default, fetches never time out.
+    #[arg(long, env)]
+    pub fetch_timeout_secs: Option<u64>,
     /// Run the client program natively.
     #[arg(long, conflicts_with = "server", required_unless_present = "server")]
     pub native: bool,
@@ -115,6 +163,12 @@ pub enum InteropHostError {
     /// An error when no provider found for chain ID.
     #[error("No provider found for chain ID: {0}")]
     RootProviderError(u64),
+    /// An error when opening the key-value store.
+    #[error("Key-value store error: {0}")]
+    KeyValueStoreError(anyhow::Error),
+    /// A provider error.
+    #[error("Provider error: {0}")]
+    ProviderError(anyhow::Error),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -164,7 +218,12 @@ impl InteropHost {
                 providers,
                 InteropHintHandler,
             )
-            .with_proactive_hint(HintType::L2BlockData);
+            .with_proactive_hint(HintType::L2BlockData)
+            .with_prefetch_hint(HintType::L1Receipts)
+            .with_max_concurrent_fetches(
+                self.max_concurrent_fetches.unwrap_or(DEFAULT_MAX_CONCURRENT_FETCHES),
+            )
+            .with_retry_policy(self.retry_policy());
 
             task::spawn(async {
                 PreimageServer::new(
@@ -200,6 +259,16 @@ impl InteropHost {
         std::process::exit(client_result.is_err() as i32)
     }
 
+    /// Returns the [HintRetryPolicy] configured by the `--fetch-*` flags.
+    pub fn retry_policy(&self) -> HintRetryPolicy {
+        HintRetryPolicy {
+            max_retries: self.fetch_retries.unwrap_or(DEFAULT_FETCH_RETRIES),
+            backoff: self.fetch_backoff_ms.map_or(DEFAULT_FETCH_BACKOFF, Duration::from_millis),
+            max_backoff: DEFAULT_FETCH_MAX_BACKOFF,
+            timeout: self.fetch_timeout_secs.map(Duration::from_secs),
+        }
+    }
+
     /// Returns `true` if the host is running in offline mode.
     pub const fn is_offline(&self) -> bool {
         self.l1_node_address.is_none() &&
@@ -233,45 +302,65 @@ impl InteropHost {
     fn create_key_value_store(&self) -> Result<SharedKeyValueStore, InteropHostError> {
         let local_kv_store = InteropLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
-            let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
+        let remote_kv_store: BoxedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
+            self.kv_backend
+                .open(data_dir, self.persist, self.is_offline(), None)
+                .map_err(InteropHostError::KeyValueStoreError)?
+        } else {
+            Box::new(MemoryKeyValueStore::new())
+        };
+        let remote_kv_store: BoxedKeyValueStore = if let Some(ref cache_dir) = self.cache_dir {
+            Box::new(
+                SharedCacheKeyValueStore::new(cache_dir.clone(), remote_kv_store)
+                    .map_err(InteropHostError::KeyValueStoreError)?,
+            )
         } else {
-            let mem_kv_store = MemoryKeyValueStore::new();
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, mem_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
+            remote_kv_store
         };
 
-        Ok(kv_store)
+        // Verify fetched preimages against their keys before they reach the store or the cache.
+        let remote_kv_store = VerifyingKeyValueStore::new(remote_kv_store);
+
+        let split_kv_store = SplitKeyValueStore::new(local_kv_store, remote_kv_store);
+        Ok(Arc::new(RwLock::new(split_kv_store)))
     }
 
     /// Creates the providers required for the preimage server backend.
     async fn create_providers(&self) -> Result<InteropProviders, InteropHostError> {
-        let l1_provider = http_provider(
+        let l1_provider = failover_http_provider(
             self.l1_node_address.as_ref().ok_or(InteropHostError::Other("Provider must be set"))?,
         );
 
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
+        let blob_provider = OnlineBlobProvider::init(failover_beacon_client(
             self.l1_beacon_address
-                .clone()
+                .as_ref()
                 .ok_or(InteropHostError::Other("Beacon API URL must be set"))?,
         ))
         .await;
 
-        // Resolve all chain IDs to their corresponding providers.
+        // Resolve all chain IDs to their corresponding endpoints. Several endpoints serving the
+        // same chain are redundant, and requests fail over between them.
         let l2_node_addresses = self
             .l2_node_addresses
             .as_ref()
             .ok_or(InteropHostError::Other("L2 node addresses must be set"))?;
-        let mut l2_providers = HashMap::default();
+        let mut l2_endpoints = HashMap::<u64, Vec<String>>::default();
         for l2_node_address in l2_node_addresses {
-            let l2_provider = http_provider::<Optimism>(l2_node_address);
-            let chain_id = l2_provider.get_chain_id().await?;
+            let chain_id = http_provider::<Optimism>(l2_node_address).get_chain_id().await?;
+            l2_endpoints.entry(chain_id).or_default().push(l2_node_address.clone());
+        }
+        let mut l2_providers = HashMap::default();
+        let mut l2_states = HashMap::default();
+        for (chain_id, urls) in l2_endpoints {
+            let l2_provider = failover_http_provider::<Optimism>(&urls);
+            let l2_state = L2StateFetcher::new(l2_provider.clone(), self.l2_state_fetch_mode)
+                .await
+                .map_err(InteropHostError::ProviderError)?;
             l2_providers.insert(chain_id, l2_provider);
+            l2_states.insert(chain_id, Arc::new(l2_state));
         }
 
-        Ok(InteropProviders { l1: l1_provider, blobs: blob_provider, l2s: l2_providers })
+        Ok(InteropProviders { l1: l1_provider, blobs: blob_provider, l2s: l2_providers, l2_states })
     }
 }
 
@@ -286,9 +375,11 @@ pub struct InteropProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: OnlineBlobProvider<FailoverBeaconClient>,
     /// The L2 EL providers, keyed by chain ID.
     pub l2s: HashMap<u64, RootProvider<Optimism>>,
+    /// The fetchers of L2 state trie nodes and contract code, keyed by chain ID.
+    pub l2_states: HashMap<u64, Arc<L2StateFetcher>>,
 }
 
 impl InteropProviders {
@@ -296,6 +387,14 @@ impl InteropProviders {
     pub fn l2(&self, chain_id: &u64) -> Result<&RootProvider<Optimism>, InteropHostError> {
         self.l2s.get(chain_id).ok_or_else(|| InteropHostError::RootProviderError(*chain_id))
     }
+
+    /// Returns the [L2StateFetcher] for the given chain ID.
+    pub fn l2_state(&self, chain_id: &u64) -> Result<&L2StateFetcher, InteropHostError> {
+        self.l2_states
+            .get(chain_id)
+            .map(Arc::as_ref)
+            .ok_or_else(|| InteropHostError::RootProviderError(*chain_id))
+    }
 }
 
 #[cfg(test)]
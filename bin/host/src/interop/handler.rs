@@ -25,7 +25,7 @@ use kona_preimage::{
 use kona_proof::{
     CachingOracle, Hint,
     executor::KonaExecutor,
-    l1::{OracleBlobProvider, OracleL1ChainProvider, OraclePipeline},
+    l1::{OracleBlobProvider, OracleL1ChainProvider, OraclePipeline, blob_preimage_key},
     l2::OracleL2ChainProvider,
     sync::new_pipeline_cursor,
 };
@@ -147,6 +147,10 @@ impl HintHandler for InteropHintHandler {
                     PreimageKey::new(*blob_key_hash, PreimageKeyType::Blob).into(),
                     sidecar.kzg_proof.to_vec(),
                 )?;
+
+                // Write the whole blob, for clients that retrieve blobs in bulk. It is verified
+                // against the commitment and proof written above.
+                kv_lock.set(blob_preimage_key(hash).into(), sidecar.blob.to_vec())?;
             }
             HintType::L1Precompile => {
                 ensure!(hint.data.len() >= 20, "Invalid hint data length");
@@ -295,48 +299,20 @@ impl HintHandler for InteropHintHandler {
                 store_ordered_trie(kv.as_ref(), raw_receipts.as_slice()).await?;
             }
             HintType::L2Code => {
-                // geth hashdb scheme code hash key prefix
-                const CODE_PREFIX: u8 = b'c';
-
                 ensure!(hint.data.len() == 40, "Invalid hint data length");
 
                 let hash: B256 = B256::from_slice(&hint.data[0..32]);
                 let chain_id = u64::from_be_bytes(hint.data[32..40].try_into()?);
-                let l2_provider = providers.l2(&chain_id)?;
-
-                // Attempt to fetch the code from the L2 chain provider.
-                let code_key = [&[CODE_PREFIX], hash.as_slice()].concat();
-                let code = l2_provider
-                    .client()
-                    .request::<&[Bytes; 1], Bytes>("debug_dbGet", &[code_key.into()])
-                    .await;
-
-                // Check if the first attempt to fetch the code failed. If it did, try fetching the
-                // code hash preimage without the geth hashdb scheme prefix.
-                let code = match code {
-                    Ok(code) => code,
-                    Err(_) => l2_provider
-                        .client()
-                        .request::<&[B256; 1], Bytes>("debug_dbGet", &[hash])
-                        .await
-                        .map_err(|e| anyhow!("Error fetching code hash preimage: {e}"))?,
-                };
 
-                let mut kv_lock = kv.write().await;
-                kv_lock.set(PreimageKey::new_keccak256(*hash).into(), code.into())?;
+                providers.l2_state(&chain_id)?.fetch_code(hash, &kv).await?;
             }
             HintType::L2StateNode => {
                 ensure!(hint.data.len() == 40, "Invalid hint data length");
 
-                let hash: B256 = hint.data.as_ref().try_into()?;
+                let hash: B256 = B256::from_slice(&hint.data[0..32]);
                 let chain_id = u64::from_be_bytes(hint.data[32..40].try_into()?);
 
-                // Fetch the preimage from the L2 chain provider.
-                let preimage: Bytes =
-                    providers.l2(&chain_id)?.client().request("debug_dbGet", &[hash]).await?;
-
-                let mut kv_write_lock = kv.write().await;
-                kv_write_lock.set(PreimageKey::new_keccak256(*hash).into(), preimage.into())?;
+                providers.l2_state(&chain_id)?.fetch_state_node(hash, &kv).await?;
             }
             HintType::L2AccountProof => {
                 ensure!(hint.data.len() == 8 + 20 + 8, "Invalid hint data length");
@@ -345,20 +321,10 @@ impl HintHandler for InteropHintHandler {
                 let address = Address::from_slice(&hint.data.as_ref()[8..28]);
                 let chain_id = u64::from_be_bytes(hint.data[28..].try_into()?);
 
-                let proof_response = providers
-                    .l2(&chain_id)?
-                    .get_proof(address, Default::default())
-                    .block_id(block_number.into())
+                providers
+                    .l2_state(&chain_id)?
+                    .fetch_proof(address, vec![], block_number, &kv)
                     .await?;
-
-                // Write the account proof nodes to the key-value store.
-                let mut kv_lock = kv.write().await;
-                proof_response.account_proof.into_iter().try_for_each(|node| {
-                    let node_hash = keccak256(node.as_ref());
-                    let key = PreimageKey::new_keccak256(*node_hash);
-                    kv_lock.set(key.into(), node.into())?;
-                    Ok::<(), anyhow::Error>(())
-                })?;
             }
             HintType::L2AccountStorageProof => {
                 ensure!(hint.data.len() == 8 + 20 + 32 + 8, "Invalid hint data length");
@@ -368,30 +334,10 @@ impl HintHandler for InteropHintHandler {
                 let slot = B256::from_slice(&hint.data.as_ref()[28..60]);
                 let chain_id = u64::from_be_bytes(hint.data[60..].try_into()?);
 
-                let mut proof_response = providers
-                    .l2(&chain_id)?
-                    .get_proof(address, vec![slot])
-                    .block_id(block_number.into())
+                providers
+                    .l2_state(&chain_id)?
+                    .fetch_proof(address, vec![slot], block_number, &kv)
                     .await?;
-
-                let mut kv_lock = kv.write().await;
-
-                // Write the account proof nodes to the key-value store.
-                proof_response.account_proof.into_iter().try_for_each(|node| {
-                    let node_hash = keccak256(node.as_ref());
-                    let key = PreimageKey::new_keccak256(*node_hash);
-                    kv_lock.set(key.into(), node.into())?;
-                    Ok::<(), anyhow::Error>(())
-                })?;
-
-                // Write the storage proof nodes to the key-value store.
-                let storage_proof = proof_response.storage_proof.remove(0);
-                storage_proof.proof.into_iter().try_for_each(|node| {
-                    let node_hash = keccak256(node.as_ref());
-                    let key = PreimageKey::new_keccak256(*node_hash);
-                    kv_lock.set(key.into(), node.into())?;
-                    Ok::<(), anyhow::Error>(())
-                })?;
             }
             HintType::L2BlockData => {
                 ensure!(hint.data.len() == 72, "Invalid hint data length");
@@ -542,4 +488,24 @@ impl HintHandler for InteropHintHandler {
 
         Ok(())
     }
+
+    fn preimage_key(hint: &Hint<HintType>) -> Option<PreimageKey> {
+        match hint.ty {
+            HintType::L1BlockHeader if hint.data.len() == 32 => {
+                Some(PreimageKey::new_keccak256(hint.data.as_ref().try_into().ok()?))
+            }
+            HintType::L2BlockHeader | HintType::L2Code | HintType::L2StateNode
+                if hint.data.len() == 40 =>
+            {
+                Some(PreimageKey::new_keccak256(hint.data[..32].try_into().ok()?))
+            }
+            HintType::L1Blob if hint.data.len() == 48 => {
+                Some(blob_preimage_key(B256::from_slice(&hint.data[..32])))
+            }
+            HintType::L1Precompile => {
+                Some(PreimageKey::new(*keccak256(hint.data.as_ref()), PreimageKeyType::Precompile))
+            }
+            _ => None,
+        }
+    }
 }
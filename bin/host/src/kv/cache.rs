@@ -0,0 +1,325 @@
+//! Contains a [KeyValueStore] layer that shares preimages between hosts through a
+//! content-addressed cache directory.
+
+use super::{
+    KeyValueStore,
+    verify::{BLOB_KEY_PREIMAGE_LENGTH, blob_element_key, preimage_digest, verify_blob},
+};
+use alloy_eips::eip4844::{Blob, Bytes48, FIELD_ELEMENTS_PER_BLOB};
+use alloy_primitives::{B256, hex};
+use anyhow::Result;
+use kona_preimage::PreimageKeyType;
+use std::{path::PathBuf, sync::Mutex};
+use tracing::{debug, warn};
+
+/// A read-through, write-through [KeyValueStore] layer over a shared cache directory.
+///
+/// Reads that miss the inner store are served from the cache directory, and every preimage
+/// written to the inner store that can be verified when read is also written to the cache
+/// directory. Preimages served
+/// from the cache are written through to the inner store, so that a persistent inner store holds
+/// every preimage the host has read, as if it had been fetched. Several hosts on
+/// one machine, e.g. proving adjacent blocks, can point at the same directory to share preimages
+/// instead of refetching them from their providers.
+///
+/// Preimages are stored one per file, named by their key. Files are written atomically, and
+/// verified when read: [PreimageKeyType::Keccak256] and [PreimageKeyType::Sha256] preimages
+/// against the digest in their key, and [PreimageKeyType::Blob] preimages by verifying their
+/// whole blob against its KZG commitment. Preimages of other types cannot be verified against
+/// their key, and never enter the cache.
+#[derive(Debug)]
+pub struct SharedCacheKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    cache_directory: PathBuf,
+    /// The inner store, locked so that cache hits can be written through to it on reads.
+    inner: Mutex<KV>,
+}
+
+impl<KV> SharedCacheKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    /// Create a new [SharedCacheKeyValueStore] over the given cache directory, creating it if it
+    /// does not exist.
+    pub fn new(cache_directory: PathBuf, inner: KV) -> Result<Self> {
+        std::fs::create_dir_all(&cache_directory)?;
+        Ok(Self { cache_directory, inner: Mutex::new(inner) })
+    }
+
+    /// Returns the path of the cache file for the given key. Files are sharded into
+    /// subdirectories by the first byte of the key's digest.
+    fn cache_path(&self, key: B256) -> PathBuf {
+        self.cache_directory.join(hex::encode([key[1]])).join(hex::encode(key))
+    }
+
+    /// Reads a preimage from the cache directory, discarding it if it does not match the digest
+    /// in its key.
+    fn read_cache(&self, key: B256) -> Option<Vec<u8>> {
+        let value = std::fs::read(self.cache_path(key)).ok()?;
+
+        let key_type = PreimageKeyType::try_from(key[0]).ok()?;
+        let digest = preimage_digest(key_type, &value)?;
+        if digest[1..] != key[1..] {
+            warn!(target: "shared_cache_kv", "Discarding corrupt cached preimage for key {key}");
+            return None;
+        }
+
+        Some(value)
+    }
+
+    /// Reads a blob field element or proof from the cache directory. The whole blob is read and
+    /// verified against its KZG commitment, and all of its preimages are written through to the
+    /// inner store.
+    fn read_cached_blob(&self, key: B256, inner: &mut KV) -> Option<Vec<u8>> {
+        let mut key_preimage_key = key;
+        key_preimage_key[0] = PreimageKeyType::Keccak256 as u8;
+        let key_preimage = inner
+            .get(key_preimage_key)
+            .or_else(|| self.read_cache(key_preimage_key))
+            .filter(|preimage| preimage.len() == BLOB_KEY_PREIMAGE_LENGTH)?;
+        let commitment = Bytes48::from_slice(&key_preimage[..48]);
+        let index = u64::from_be_bytes(key_preimage[72..].try_into().ok()?);
+
+        let mut blob = Box::new(Blob::ZERO);
+        for i in 0..FIELD_ELEMENTS_PER_BLOB {
+            let element = std::fs::read(self.cache_path(blob_element_key(&commitment, i)))
+                .ok()
+                .filter(|element| element.len() == 32)?;
+            let offset = (i as usize) << 5;
+            blob[offset..offset + 32].copy_from_slice(&element);
+        }
+        let proof_key = blob_element_key(&commitment, FIELD_ELEMENTS_PER_BLOB);
+        let proof = std::fs::read(self.cache_path(proof_key)).ok()?;
+        let proof = Bytes48::try_from(proof.as_slice()).ok()?;
+
+        let sidecar = verify_blob(blob, commitment, proof)
+            .inspect_err(|e| {
+                warn!(target: "shared_cache_kv", "Discarding corrupt cached blob: {e}");
+            })
+            .ok()?;
+
+        for i in 0..FIELD_ELEMENTS_PER_BLOB {
+            let offset = (i as usize) << 5;
+            let element = sidecar.blob[offset..offset + 32].to_vec();
+            write_through(inner, blob_element_key(&commitment, i), element);
+        }
+        write_through(inner, proof_key, proof.to_vec());
+
+        if index == FIELD_ELEMENTS_PER_BLOB {
+            Some(proof.to_vec())
+        } else {
+            let offset = (index as usize) << 5;
+            sidecar.blob.get(offset..offset + 32).map(<[u8]>::to_vec)
+        }
+    }
+
+    /// Writes a preimage to the cache directory, if it is not already cached.
+    fn write_cache(&self, key: B256, value: &[u8]) -> std::io::Result<()> {
+        let path = self.cache_path(key);
+        if path.exists() {
+            return Ok(());
+        }
+
+        // Write to a temporary file first, so that other hosts never observe a partial file.
+        let dir = path.parent().expect("Cache paths always have a parent");
+        std::fs::create_dir_all(dir)?;
+        let tmp_path = dir.join(format!(".{}.{}.tmp", hex::encode(key), std::process::id()));
+        std::fs::write(&tmp_path, value)?;
+        std::fs::rename(&tmp_path, &path)
+    }
+}
+
+impl<KV> KeyValueStore for SharedCacheKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(value) = inner.get(key) {
+            return Some(value);
+        }
+
+        if PreimageKeyType::try_from(key[0]).ok()? == PreimageKeyType::Blob {
+            return self.read_cached_blob(key, &mut inner);
+        }
+
+        let value = self.read_cache(key)?;
+        write_through(&mut *inner, key, value.clone());
+        Some(value)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        let cacheable = PreimageKeyType::try_from(key[0]).is_ok_and(|t| {
+            matches!(
+                t,
+                PreimageKeyType::Keccak256 | PreimageKeyType::Sha256 | PreimageKeyType::Blob
+            )
+        });
+        if cacheable {
+            // A failure to populate the cache only costs other hosts a refetch.
+            if let Err(e) = self.write_cache(key, &value) {
+                warn!(target: "shared_cache_kv", "Failed to cache preimage for key {key}: {e}");
+            }
+        }
+
+        self.inner.get_mut().unwrap_or_else(|e| e.into_inner()).set(key, value)
+    }
+}
+
+/// Writes a preimage served from the cache through to the inner store.
+fn write_through<KV: KeyValueStore + ?Sized>(inner: &mut KV, key: B256, value: Vec<u8>) {
+    // Read-only inner stores reject the write, and only serve the preimage from the cache.
+    if let Err(e) = inner.set(key, value) {
+        debug!(target: "shared_cache_kv", "Failed to write cached preimage for key {key}: {e}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use alloy_primitives::keccak256;
+    use kona_preimage::PreimageKey;
+    use sha2::{Digest, Sha256};
+
+    /// The KZG commitment and proof of the zero blob: the compressed point at infinity.
+    const POINT_AT_INFINITY: Bytes48 = {
+        let mut point = [0u8; 48];
+        point[0] = 0xc0;
+        Bytes48::new(point)
+    };
+
+    /// Writes the zero blob to the store the way the host's hint handlers do.
+    fn set_zero_blob<KV: KeyValueStore>(kv: &mut KV) {
+        let mut blob_key = [0u8; BLOB_KEY_PREIMAGE_LENGTH];
+        blob_key[..48].copy_from_slice(POINT_AT_INFINITY.as_ref());
+        for i in 0..=FIELD_ELEMENTS_PER_BLOB {
+            blob_key[72..].copy_from_slice(i.to_be_bytes().as_ref());
+            let value = if i == FIELD_ELEMENTS_PER_BLOB {
+                POINT_AT_INFINITY.to_vec()
+            } else {
+                vec![0u8; 32]
+            };
+            kv.set(PreimageKey::new_keccak256(*keccak256(blob_key)).into(), blob_key.into())
+                .unwrap();
+            kv.set(blob_element_key(&POINT_AT_INFINITY, i), value).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_shares_preimages_between_stores() {
+        let dir = tempfile::tempdir().unwrap();
+        let preimage = b"hello".to_vec();
+        let key = PreimageKey::new_keccak256(*keccak256(&preimage)).into();
+
+        let mut writer =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        writer.set(key, preimage.clone()).unwrap();
+
+        let reader =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        assert_eq!(reader.get(key), Some(preimage));
+        assert_eq!(reader.get(B256::with_last_byte(1)), None);
+    }
+
+    #[test]
+    fn test_cache_hits_are_written_through() {
+        let dir = tempfile::tempdir().unwrap();
+        let preimage = b"hello".to_vec();
+        let key = PreimageKey::new_keccak256(*keccak256(&preimage)).into();
+
+        let mut writer =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        writer.set(key, preimage.clone()).unwrap();
+
+        let reader =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        assert_eq!(reader.get(key), Some(preimage.clone()));
+        assert_eq!(reader.inner.into_inner().unwrap().get(key), Some(preimage));
+    }
+
+    #[test]
+    fn test_local_preimages_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = PreimageKey::new_local(1).into();
+
+        let mut writer =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        writer.set(key, vec![1]).unwrap();
+        assert_eq!(writer.get(key), Some(vec![1]));
+
+        let reader =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        assert_eq!(reader.get(key), None);
+    }
+
+    #[test]
+    fn test_discards_corrupt_preimages() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = PreimageKey::new_keccak256(*keccak256(b"hello")).into();
+
+        let store =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        let path = store.cache_path(key);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"goodbye").unwrap();
+
+        assert_eq!(store.get(key), None);
+
+        let key = PreimageKey::new(Sha256::digest(b"hello").into(), PreimageKeyType::Sha256).into();
+        let path = store.cache_path(key);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"goodbye").unwrap();
+
+        assert_eq!(store.get(key), None);
+    }
+
+    #[test]
+    fn test_unverifiable_preimages_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = PreimageKey::new([1; 32], PreimageKeyType::Precompile).into();
+
+        let mut writer =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        writer.set(key, vec![1]).unwrap();
+        assert!(!writer.cache_path(key).exists());
+    }
+
+    #[test]
+    fn test_verifies_cached_blobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        set_zero_blob(&mut writer);
+
+        let element_key = blob_element_key(&POINT_AT_INFINITY, 1);
+        let reader =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        assert_eq!(reader.get(element_key), Some(vec![0u8; 32]));
+
+        // The whole blob is written through to the inner store once it has been verified.
+        let proof_key = blob_element_key(&POINT_AT_INFINITY, FIELD_ELEMENTS_PER_BLOB);
+        let inner = reader.inner.into_inner().unwrap();
+        assert_eq!(inner.get(proof_key), Some(POINT_AT_INFINITY.to_vec()));
+
+        // A blob with a corrupt field element is not served.
+        std::fs::write(writer.cache_path(element_key), [1u8; 32]).unwrap();
+        let reader =
+            SharedCacheKeyValueStore::new(dir.path().to_path_buf(), MemoryKeyValueStore::new())
+                .unwrap();
+        assert_eq!(reader.get(element_key), None);
+        assert_eq!(reader.get(proof_key), None);
+    }
+}
@@ -3,24 +3,213 @@
 
 use super::{KeyValueStore, MemoryKeyValueStore};
 use alloy_primitives::B256;
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail, ensure};
+use kona_preimage::PreimageKeyType;
 use rocksdb::{DB, Options};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use tracing::warn;
+
+/// The current version of the on-disk layout of a persistent [DiskKeyValueStore].
+pub const DISK_KV_SCHEMA_VERSION: u32 = 1;
+
+/// The name of the metadata file that is written next to a persistent [DiskKeyValueStore]'s data.
+pub const DISK_KV_METADATA_FILE: &str = "kona-kv-metadata.json";
+
+/// The lifecycle of the data directory backing an on-disk [KeyValueStore].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DiskStoreMode {
+    /// The database is destroyed when the store is dropped.
+    Ephemeral,
+    /// The database survives the store, and its metadata is kept up to date.
+    Persistent,
+    /// The database was opened read-only, and may not be written to.
+    ReadOnly,
+}
+
+/// The metadata header of a persistent [DiskKeyValueStore].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskKeyValueStoreMetadata {
+    /// The schema version that the database was written with.
+    pub schema_version: u32,
+    /// The L2 chain ID that the preimages were fetched for, if the store is bound to one.
+    pub chain_id: Option<u64>,
+    /// The number of preimages in the store, keyed by their [PreimageKeyType]. The counts are
+    /// written when the store is flushed, and recounted from the database when it is opened, in
+    /// case the host that last wrote to it did not shut down cleanly.
+    pub key_counts: BTreeMap<PreimageKeyType, u64>,
+}
+
+impl DiskKeyValueStoreMetadata {
+    /// Create a new, empty [DiskKeyValueStoreMetadata] for the given chain ID.
+    pub const fn new(chain_id: Option<u64>) -> Self {
+        Self { schema_version: DISK_KV_SCHEMA_VERSION, chain_id, key_counts: BTreeMap::new() }
+    }
+
+    /// Returns the total number of preimages in the store.
+    pub fn total_keys(&self) -> u64 {
+        self.key_counts.values().sum()
+    }
+
+    /// Recounts the preimages in the store from the first bytes of their keys, which hold their
+    /// [PreimageKeyType].
+    pub(super) fn recount_keys(&mut self, key_types: impl IntoIterator<Item = u8>) {
+        self.key_counts.clear();
+        for key_type in key_types.into_iter().filter_map(|t| PreimageKeyType::try_from(t).ok()) {
+            *self.key_counts.entry(key_type).or_default() += 1;
+        }
+    }
+
+    /// Reads the metadata from the given data directory, if it exists.
+    pub fn read(data_directory: &Path) -> Result<Option<Self>> {
+        let path = data_directory.join(DISK_KV_METADATA_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let raw = std::fs::read(&path)?;
+        serde_json::from_slice(&raw)
+            .map(Some)
+            .map_err(|e| anyhow!("Failed to decode metadata at {path:?}: {e}"))
+    }
+
+    /// Writes the metadata to the given data directory.
+    pub fn write(&self, data_directory: &Path) -> Result<()> {
+        let path = data_directory.join(DISK_KV_METADATA_FILE);
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)
+            .map_err(|e| anyhow!("Failed to write metadata to {path:?}: {e}"))
+    }
+
+    /// Reads and validates the metadata of the persistent store in the given data directory, or
+    /// creates it if the directory holds no store yet.
+    pub(super) fn open_persistent(data_directory: &Path, chain_id: Option<u64>) -> Result<Self> {
+        match Self::read(data_directory)? {
+            Some(mut metadata) => {
+                metadata.validate(chain_id)?;
+                metadata.chain_id = metadata.chain_id.or(chain_id);
+                Ok(metadata)
+            }
+            None => {
+                if data_directory.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+                    bail!(
+                        "Data directory {data_directory:?} is not empty and holds no preimage \
+                         store metadata"
+                    );
+                }
+                Ok(Self::new(chain_id))
+            }
+        }
+    }
+
+    /// Reads and validates the metadata of an existing persistent store in the given data
+    /// directory.
+    pub(super) fn open_read_only(data_directory: &Path, chain_id: Option<u64>) -> Result<Self> {
+        let metadata = Self::read(data_directory)?.ok_or_else(|| {
+            anyhow!("No preimage store metadata found in data directory {data_directory:?}")
+        })?;
+        metadata.validate(chain_id)?;
+        Ok(metadata)
+    }
+
+    /// Checks that a store with this metadata may be used by a host for the given chain ID.
+    fn validate(&self, chain_id: Option<u64>) -> Result<()> {
+        ensure!(
+            self.schema_version == DISK_KV_SCHEMA_VERSION,
+            "Unsupported preimage store schema version {}, expected {}",
+            self.schema_version,
+            DISK_KV_SCHEMA_VERSION
+        );
+        if let (Some(expected), Some(actual)) = (chain_id, self.chain_id) {
+            ensure!(
+                expected == actual,
+                "Preimage store was created for chain ID {actual}, but the host is running for \
+                 chain ID {expected}"
+            );
+        }
+        Ok(())
+    }
+}
 
 /// A simple, synchronous key-value store that stores data on disk.
+///
+/// By default, the store is ephemeral and the database is destroyed when the store is dropped. A
+/// store opened with [DiskKeyValueStore::open_persistent] survives the host, and may be reopened
+/// by later runs (or with [DiskKeyValueStore::open_read_only]) to avoid refetching preimages.
 #[derive(Debug)]
 pub struct DiskKeyValueStore {
     data_directory: PathBuf,
     db: DB,
+    mode: DiskStoreMode,
+    metadata: Option<DiskKeyValueStoreMetadata>,
 }
 
 impl DiskKeyValueStore {
-    /// Create a new [DiskKeyValueStore] with the given data directory.
+    /// Create a new [DiskKeyValueStore] with the given data directory. The database is destroyed
+    /// when the store is dropped.
     pub fn new(data_directory: PathBuf) -> Self {
         let db = DB::open(&Self::get_db_options(), data_directory.as_path())
             .unwrap_or_else(|e| panic!("Failed to open database at {data_directory:?}: {e}"));
 
-        Self { data_directory, db }
+        Self { data_directory, db, mode: DiskStoreMode::Ephemeral, metadata: None }
+    }
+
+    /// Opens or creates a persistent [DiskKeyValueStore] in the given data directory.
+    ///
+    /// If the directory already holds a persistent store, its metadata must match the current
+    /// schema version and, if both are known, the given chain ID.
+    pub fn open_persistent(data_directory: PathBuf, chain_id: Option<u64>) -> Result<Self> {
+        let mut metadata = DiskKeyValueStoreMetadata::open_persistent(&data_directory, chain_id)?;
+
+        std::fs::create_dir_all(&data_directory)?;
+        let db = DB::open(&Self::get_db_options(), data_directory.as_path())
+            .map_err(|e| anyhow!("Failed to open database at {data_directory:?}: {e}"))?;
+        metadata.recount_keys(Self::key_types(&db)?);
+        metadata.write(&data_directory)?;
+
+        Ok(Self { data_directory, db, mode: DiskStoreMode::Persistent, metadata: Some(metadata) })
+    }
+
+    /// Opens an existing persistent [DiskKeyValueStore] in read-only mode. Read-only stores may
+    /// be opened by several hosts at once.
+    pub fn open_read_only(data_directory: PathBuf, chain_id: Option<u64>) -> Result<Self> {
+        let mut metadata = DiskKeyValueStoreMetadata::open_read_only(&data_directory, chain_id)?;
+
+        let db = DB::open_for_read_only(&Self::get_db_options(), data_directory.as_path(), false)
+            .map_err(|e| anyhow!("Failed to open database at {data_directory:?}: {e}"))?;
+        metadata.recount_keys(Self::key_types(&db)?);
+
+        Ok(Self { data_directory, db, mode: DiskStoreMode::ReadOnly, metadata: Some(metadata) })
+    }
+
+    /// Returns the metadata of the store, if it is persistent.
+    pub const fn metadata(&self) -> Option<&DiskKeyValueStoreMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Flushes the database and, if the store is persistent, its metadata to disk.
+    pub fn flush(&self) -> Result<()> {
+        if self.mode != DiskStoreMode::Persistent {
+            return Ok(());
+        }
+
+        self.db.flush().map_err(|e| anyhow!("Failed to flush database: {e}"))?;
+        if let Some(ref metadata) = self.metadata {
+            metadata.write(&self.data_directory)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first byte of every key in the database, which holds its [PreimageKeyType].
+    fn key_types(db: &DB) -> Result<Vec<u8>> {
+        let mut key_types = Vec::new();
+        for entry in db.full_iterator(rocksdb::IteratorMode::Start) {
+            let (key, _) = entry.map_err(|e| anyhow!("Failed to read key: {e}"))?;
+            key_types.extend(key.first());
+        }
+        Ok(key_types)
     }
 
     /// Gets the [Options] for the underlying RocksDB instance.
@@ -38,13 +227,42 @@ impl KeyValueStore for DiskKeyValueStore {
     }
 
     fn set(&mut self, key: alloy_primitives::B256, value: Vec<u8>) -> Result<()> {
+        match self.mode {
+            DiskStoreMode::ReadOnly => bail!("Cannot write to a read-only preimage store"),
+            DiskStoreMode::Persistent => {
+                let is_new = self
+                    .db
+                    .get_pinned(*key)
+                    .map_err(|e| anyhow!("Failed to read key: {}", e))?
+                    .is_none();
+                if is_new {
+                    if let (Some(metadata), Ok(key_type)) =
+                        (self.metadata.as_mut(), PreimageKeyType::try_from(key[0]))
+                    {
+                        *metadata.key_counts.entry(key_type).or_default() += 1;
+                    }
+                }
+            }
+            DiskStoreMode::Ephemeral => {}
+        }
+
         self.db.put(*key, value).map_err(|e| anyhow!("Failed to set key-value pair: {}", e))
     }
 }
 
 impl Drop for DiskKeyValueStore {
     fn drop(&mut self) {
-        let _ = DB::destroy(&Self::get_db_options(), self.data_directory.as_path());
+        match self.mode {
+            DiskStoreMode::Ephemeral => {
+                let _ = DB::destroy(&Self::get_db_options(), self.data_directory.as_path());
+            }
+            DiskStoreMode::Persistent => {
+                if let Err(e) = self.flush() {
+                    warn!(target: "disk_kv", "Failed to flush persistent preimage store: {e}");
+                }
+            }
+            DiskStoreMode::ReadOnly => {}
+        }
     }
 }
 
@@ -69,8 +287,10 @@ impl TryFrom<DiskKeyValueStore> for MemoryKeyValueStore {
 
 #[cfg(test)]
 mod test {
-    use super::DiskKeyValueStore;
+    use super::{DiskKeyValueStore, DiskKeyValueStoreMetadata};
     use crate::kv::{KeyValueStore, MemoryKeyValueStore};
+    use alloy_primitives::keccak256;
+    use kona_preimage::{PreimageKey, PreimageKeyType};
     use proptest::{
         arbitrary::any,
         collection::{hash_map, vec},
@@ -97,4 +317,81 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_persistent_store_survives_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+        let preimage = b"persistent preimage".to_vec();
+        let key = PreimageKey::new_keccak256(*keccak256(&preimage));
+
+        let mut store = DiskKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap();
+        store.set(key.into(), preimage.clone()).unwrap();
+        store.set(key.into(), preimage.clone()).unwrap();
+        drop(store);
+
+        let metadata = DiskKeyValueStoreMetadata::read(&data_dir).unwrap().unwrap();
+        assert_eq!(metadata.chain_id, Some(10));
+        assert_eq!(metadata.key_counts.get(&PreimageKeyType::Keccak256), Some(&1));
+        assert_eq!(metadata.total_keys(), 1);
+
+        let store = DiskKeyValueStore::open_persistent(data_dir, Some(10)).unwrap();
+        assert_eq!(store.get(key.into()), Some(preimage));
+    }
+
+    #[test]
+    fn test_persistent_store_recounts_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+
+        let mut store = DiskKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap();
+        store.set(PreimageKey::new_keccak256([1; 32]).into(), vec![1]).unwrap();
+        store.set(PreimageKey::new_keccak256([2; 32]).into(), vec![2]).unwrap();
+        drop(store);
+
+        // Leave stale counts behind, as a host that did not shut down cleanly would.
+        DiskKeyValueStoreMetadata::new(Some(10)).write(&data_dir).unwrap();
+
+        let store = DiskKeyValueStore::open_read_only(data_dir.clone(), Some(10)).unwrap();
+        assert_eq!(store.metadata().unwrap().total_keys(), 2);
+        drop(store);
+
+        drop(DiskKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap());
+        let metadata = DiskKeyValueStoreMetadata::read(&data_dir).unwrap().unwrap();
+        assert_eq!(metadata.key_counts.get(&PreimageKeyType::Keccak256), Some(&2));
+    }
+
+    #[test]
+    fn test_read_only_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+        let key = PreimageKey::new_keccak256([0xFF; 32]);
+
+        let mut store = DiskKeyValueStore::open_persistent(data_dir.clone(), None).unwrap();
+        store.set(key.into(), vec![0x01]).unwrap();
+        drop(store);
+
+        let mut store = DiskKeyValueStore::open_read_only(data_dir, Some(10)).unwrap();
+        assert_eq!(store.get(key.into()), Some(vec![0x01]));
+        assert!(store.set(key.into(), vec![0x02]).is_err());
+    }
+
+    #[test]
+    fn test_persistent_store_chain_id_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+
+        drop(DiskKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap());
+        assert!(DiskKeyValueStore::open_persistent(data_dir.clone(), Some(8453)).is_err());
+        assert!(DiskKeyValueStore::open_read_only(data_dir, Some(8453)).is_err());
+    }
+
+    #[test]
+    fn test_persistent_store_rejects_foreign_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("foreign"), b"data").unwrap();
+
+        assert!(DiskKeyValueStore::open_persistent(dir.path().to_path_buf(), None).is_err());
+        assert!(DiskKeyValueStore::open_read_only(dir.path().to_path_buf(), None).is_err());
+    }
 }
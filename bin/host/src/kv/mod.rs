@@ -2,21 +2,86 @@
 
 use alloy_primitives::B256;
 use anyhow::Result;
-use std::sync::Arc;
+use serde::Serialize;
+use std::{path::Path, sync::Arc};
 use tokio::sync::RwLock;
 
 mod mem;
 pub use mem::MemoryKeyValueStore;
 
 mod disk;
-pub use disk::DiskKeyValueStore;
+pub use disk::{
+    DISK_KV_METADATA_FILE, DISK_KV_SCHEMA_VERSION, DiskKeyValueStore, DiskKeyValueStoreMetadata,
+};
+
+#[cfg(feature = "redb")]
+mod redb;
+#[cfg(feature = "redb")]
+pub use self::redb::{REDB_KV_FILE, RedbKeyValueStore};
 
 mod split;
 pub use split::SplitKeyValueStore;
 
+mod cache;
+pub use cache::SharedCacheKeyValueStore;
+
+mod verify;
+pub use verify::{PreimageVerificationError, VerifyingKeyValueStore};
+
 /// A type alias for a shared key-value store.
 pub type SharedKeyValueStore = Arc<RwLock<dyn KeyValueStore + Send + Sync>>;
 
+/// A type alias for a boxed key-value store.
+pub type BoxedKeyValueStore = Box<dyn KeyValueStore + Send + Sync>;
+
+/// The embedded database backing the host's on-disk [KeyValueStore].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+pub enum DiskKeyValueBackend {
+    /// The [DiskKeyValueStore], backed by RocksDB.
+    #[default]
+    #[value(name = "rocksdb")]
+    RocksDb,
+    /// The [RedbKeyValueStore], backed by redb.
+    #[cfg(feature = "redb")]
+    #[value(name = "redb")]
+    Redb,
+}
+
+impl DiskKeyValueBackend {
+    /// Opens the on-disk [KeyValueStore] in the given data directory.
+    ///
+    /// Unless `persist` is set, the store is destroyed when it is dropped. Persistent stores are
+    /// opened read-only if `read_only` is set.
+    pub fn open(
+        self,
+        data_directory: &Path,
+        persist: bool,
+        read_only: bool,
+        chain_id: Option<u64>,
+    ) -> Result<BoxedKeyValueStore> {
+        let data_directory = data_directory.to_path_buf();
+        Ok(match self {
+            Self::RocksDb if !persist => Box::new(DiskKeyValueStore::new(data_directory)),
+            Self::RocksDb if read_only => {
+                Box::new(DiskKeyValueStore::open_read_only(data_directory, chain_id)?)
+            }
+            Self::RocksDb => {
+                Box::new(DiskKeyValueStore::open_persistent(data_directory, chain_id)?)
+            }
+            #[cfg(feature = "redb")]
+            Self::Redb if !persist => {
+                Box::new(RedbKeyValueStore::new(data_directory.join(REDB_KV_FILE))?)
+            }
+            #[cfg(feature = "redb")]
+            Self::Redb if read_only => {
+                Box::new(RedbKeyValueStore::open_read_only(data_directory, chain_id)?)
+            }
+            #[cfg(feature = "redb")]
+            Self::Redb => Box::new(RedbKeyValueStore::open_persistent(data_directory, chain_id)?),
+        })
+    }
+}
+
 /// Describes the interface of a simple, synchronous key-value store.
 pub trait KeyValueStore {
     /// Get the value associated with the given key.
@@ -25,3 +90,16 @@ pub trait KeyValueStore {
     /// Set the value associated with the given key.
     fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()>;
 }
+
+impl<KV> KeyValueStore for Box<KV>
+where
+    KV: KeyValueStore + ?Sized,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.as_ref().get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.as_mut().set(key, value)
+    }
+}
@@ -0,0 +1,291 @@
+//! Contains a concrete implementation of the [KeyValueStore] trait that stores data on disk
+//! using [redb].
+
+use super::{DiskKeyValueStoreMetadata, KeyValueStore, MemoryKeyValueStore, disk::DiskStoreMode};
+use alloy_primitives::B256;
+use anyhow::{Result, anyhow, bail};
+use kona_preimage::PreimageKeyType;
+use redb::{Database, Durability, ReadableTable, TableDefinition};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// The name of the database file that a [RedbKeyValueStore] keeps in the host's data directory.
+pub const REDB_KV_FILE: &str = "preimages.redb";
+
+/// The table holding the preimages.
+const PREIMAGES: TableDefinition<'_, &[u8; 32], &[u8]> = TableDefinition::new("preimages");
+
+/// A simple, synchronous key-value store that stores data on disk in a single [redb] file.
+///
+/// Unlike the [DiskKeyValueStore], the [RedbKeyValueStore] is written in pure Rust, and does not
+/// require a C++ toolchain to build. Persistent stores keep the same [DiskKeyValueStoreMetadata]
+/// next to their database file. redb locks its database file, so unlike the [DiskKeyValueStore], a
+/// read-only [RedbKeyValueStore] may only be opened by one host at a time.
+///
+/// [DiskKeyValueStore]: super::DiskKeyValueStore
+#[derive(Debug)]
+pub struct RedbKeyValueStore {
+    path: PathBuf,
+    db: Database,
+    mode: DiskStoreMode,
+    metadata: Option<DiskKeyValueStoreMetadata>,
+}
+
+impl RedbKeyValueStore {
+    /// Create a new [RedbKeyValueStore] at the given path. The database file is removed when the
+    /// store is dropped.
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let db = Self::create_db(&path)?;
+        Ok(Self { path, db, mode: DiskStoreMode::Ephemeral, metadata: None })
+    }
+
+    /// Opens or creates a persistent [RedbKeyValueStore] in the given data directory.
+    ///
+    /// If the directory already holds a persistent store, its metadata must match the current
+    /// schema version and, if both are known, the given chain ID.
+    pub fn open_persistent(data_directory: PathBuf, chain_id: Option<u64>) -> Result<Self> {
+        let mut metadata = DiskKeyValueStoreMetadata::open_persistent(&data_directory, chain_id)?;
+
+        let path = data_directory.join(REDB_KV_FILE);
+        let db = Self::create_db(&path)?;
+        metadata.recount_keys(Self::key_types(&db)?);
+        metadata.write(&data_directory)?;
+
+        Ok(Self { path, db, mode: DiskStoreMode::Persistent, metadata: Some(metadata) })
+    }
+
+    /// Opens an existing persistent [RedbKeyValueStore] in the given data directory in read-only
+    /// mode.
+    pub fn open_read_only(data_directory: PathBuf, chain_id: Option<u64>) -> Result<Self> {
+        let mut metadata = DiskKeyValueStoreMetadata::open_read_only(&data_directory, chain_id)?;
+
+        let path = data_directory.join(REDB_KV_FILE);
+        let db = Database::open(&path)
+            .map_err(|e| anyhow!("Failed to open database at {path:?}: {e}"))?;
+        metadata.recount_keys(Self::key_types(&db)?);
+
+        Ok(Self { path, db, mode: DiskStoreMode::ReadOnly, metadata: Some(metadata) })
+    }
+
+    /// Returns the metadata of the store, if it is persistent.
+    pub const fn metadata(&self) -> Option<&DiskKeyValueStoreMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Durably commits all preimages written to the store so far and, if the store is
+    /// persistent, writes its metadata.
+    pub fn flush(&self) -> Result<()> {
+        if self.mode != DiskStoreMode::Persistent {
+            return Ok(());
+        }
+
+        let mut tx = self.db.begin_write()?;
+        tx.set_durability(Durability::Immediate);
+        tx.commit().map_err(|e| anyhow!("Failed to flush database: {e}"))?;
+        if let (Some(metadata), Some(data_directory)) = (&self.metadata, self.path.parent()) {
+            metadata.write(data_directory)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first byte of every key in the database, which holds its [PreimageKeyType].
+    fn key_types(db: &Database) -> Result<Vec<u8>> {
+        let tx = db.begin_read()?;
+        let mut key_types = Vec::new();
+        for entry in tx.open_table(PREIMAGES)?.iter()? {
+            let (key, _) = entry?;
+            key_types.push(key.value()[0]);
+        }
+        Ok(key_types)
+    }
+
+    /// Creates or opens the database file at the given path.
+    fn create_db(path: &Path) -> Result<Database> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let db = Database::create(path)
+            .map_err(|e| anyhow!("Failed to open database at {path:?}: {e}"))?;
+
+        // Create the preimage table up front, so that reads never observe a missing table.
+        let tx = db.begin_write()?;
+        tx.open_table(PREIMAGES)?;
+        tx.commit()?;
+
+        Ok(db)
+    }
+}
+
+impl KeyValueStore for RedbKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let tx = self.db.begin_read().ok()?;
+        let table = tx.open_table(PREIMAGES).ok()?;
+        let value = table.get(&key.0).ok()??;
+        Some(value.value().to_vec())
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        if self.mode == DiskStoreMode::ReadOnly {
+            bail!("Cannot write to a read-only preimage store");
+        }
+
+        // Individual writes are not synced to disk; they become durable with the next
+        // [RedbKeyValueStore::flush], which runs when the store is dropped.
+        let mut tx = self.db.begin_write()?;
+        tx.set_durability(Durability::None);
+        let is_new = tx.open_table(PREIMAGES)?.insert(&key.0, value.as_slice())?.is_none();
+        tx.commit().map_err(|e| anyhow!("Failed to set key-value pair: {e}"))?;
+
+        if is_new {
+            if let (Some(metadata), Ok(key_type)) =
+                (self.metadata.as_mut(), PreimageKeyType::try_from(key[0]))
+            {
+                *metadata.key_counts.entry(key_type).or_default() += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for RedbKeyValueStore {
+    fn drop(&mut self) {
+        match self.mode {
+            DiskStoreMode::Ephemeral => {
+                let _ = std::fs::remove_file(&self.path);
+            }
+            DiskStoreMode::Persistent => {
+                if let Err(e) = self.flush() {
+                    warn!(target: "redb_kv", "Failed to flush persistent preimage store: {e}");
+                }
+            }
+            DiskStoreMode::ReadOnly => {}
+        }
+    }
+}
+
+impl TryFrom<RedbKeyValueStore> for MemoryKeyValueStore {
+    type Error = anyhow::Error;
+
+    fn try_from(redb_store: RedbKeyValueStore) -> Result<Self> {
+        let mut memory_store = Self::new();
+
+        let tx = redb_store.db.begin_read()?;
+        for entry in tx.open_table(PREIMAGES)?.iter()? {
+            let (key, value) = entry?;
+            memory_store.set(B256::from(*key.value()), value.value().to_vec())?;
+        }
+
+        Ok(memory_store)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kona_preimage::PreimageKey;
+    use proptest::{
+        arbitrary::any,
+        collection::{hash_map, vec},
+        proptest,
+        test_runner::Config,
+    };
+
+    proptest! {
+        #![proptest_config(Config::with_cases(16))]
+
+        /// Test that converting from a [RedbKeyValueStore] to a [MemoryKeyValueStore] is lossless.
+        #[test]
+        fn convert_redb_kv_to_mem_kv(k_v in hash_map(any::<[u8; 32]>(), vec(any::<u8>(), 0..128), 1..128)) {
+            let dir = tempfile::tempdir().unwrap();
+            let mut redb_kv = RedbKeyValueStore::new(dir.path().join(REDB_KV_FILE)).unwrap();
+            for (k, v) in k_v.iter() {
+                redb_kv.set(k.into(), v.to_vec()).unwrap();
+            }
+
+            let mem_kv = MemoryKeyValueStore::try_from(redb_kv).unwrap();
+            for (k, v) in k_v {
+                assert_eq!(mem_kv.get(k.into()).unwrap(), v.to_vec());
+            }
+        }
+    }
+
+    #[test]
+    fn test_persistent_store_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+        let key = PreimageKey::new_keccak256([0xFF; 32]).into();
+
+        let mut store = RedbKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap();
+        store.set(key, vec![0xde, 0xad]).unwrap();
+        store.set(key, vec![0xde, 0xad]).unwrap();
+        drop(store);
+
+        let metadata = DiskKeyValueStoreMetadata::read(&data_dir).unwrap().unwrap();
+        assert_eq!(metadata.chain_id, Some(10));
+        assert_eq!(metadata.key_counts.get(&PreimageKeyType::Keccak256), Some(&1));
+
+        let store = RedbKeyValueStore::open_persistent(data_dir, Some(10)).unwrap();
+        assert_eq!(store.get(key), Some(vec![0xde, 0xad]));
+        assert_eq!(store.get(B256::with_last_byte(2)), None);
+    }
+
+    #[test]
+    fn test_persistent_store_recounts_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+
+        let mut store = RedbKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap();
+        store.set(PreimageKey::new_keccak256([1; 32]).into(), vec![1]).unwrap();
+        store.set(PreimageKey::new_keccak256([2; 32]).into(), vec![2]).unwrap();
+        drop(store);
+
+        // Leave stale counts behind, as a host that did not shut down cleanly would.
+        DiskKeyValueStoreMetadata::new(Some(10)).write(&data_dir).unwrap();
+
+        let store = RedbKeyValueStore::open_read_only(data_dir.clone(), Some(10)).unwrap();
+        assert_eq!(store.metadata().unwrap().total_keys(), 2);
+        drop(store);
+
+        drop(RedbKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap());
+        let metadata = DiskKeyValueStoreMetadata::read(&data_dir).unwrap().unwrap();
+        assert_eq!(metadata.key_counts.get(&PreimageKeyType::Keccak256), Some(&2));
+    }
+
+    #[test]
+    fn test_read_only_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+        let key = PreimageKey::new_keccak256([0xFF; 32]).into();
+
+        let mut store = RedbKeyValueStore::open_persistent(data_dir.clone(), None).unwrap();
+        store.set(key, vec![0x01]).unwrap();
+        drop(store);
+
+        let mut store = RedbKeyValueStore::open_read_only(data_dir, Some(10)).unwrap();
+        assert_eq!(store.get(key), Some(vec![0x01]));
+        assert!(store.set(key, vec![0x02]).is_err());
+    }
+
+    #[test]
+    fn test_persistent_store_chain_id_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+
+        drop(RedbKeyValueStore::open_persistent(data_dir.clone(), Some(10)).unwrap());
+        assert!(RedbKeyValueStore::open_persistent(data_dir.clone(), Some(8453)).is_err());
+        assert!(RedbKeyValueStore::open_read_only(data_dir, Some(8453)).is_err());
+    }
+
+    #[test]
+    fn test_ephemeral_store_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(REDB_KV_FILE);
+
+        let mut store = RedbKeyValueStore::new(path.clone()).unwrap();
+        store.set(B256::with_last_byte(1), vec![0xbe, 0xef]).unwrap();
+        assert!(path.exists());
+
+        drop(store);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,396 @@
+//! Contains a [KeyValueStore] layer that verifies preimages against their keys before storing
+//! them.
+
+use super::KeyValueStore;
+use alloy_eips::eip4844::{Blob, BlobTransactionSidecarItem, Bytes48, FIELD_ELEMENTS_PER_BLOB};
+use alloy_primitives::{B256, keccak256};
+use anyhow::Result;
+use kona_preimage::{PreimageKey, PreimageKeyType};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// The length of the keccak256 preimage of a blob key: `commitment (48) ++ uint256(index) (32)`.
+pub(super) const BLOB_KEY_PREIMAGE_LENGTH: usize = 80;
+
+/// Returns the digest that a preimage of the given type is keyed by, or `None` if keys of the
+/// type do not carry a digest of their preimage.
+pub(super) fn preimage_digest(key_type: PreimageKeyType, value: &[u8]) -> Option<B256> {
+    match key_type {
+        PreimageKeyType::Keccak256 => Some(keccak256(value)),
+        PreimageKeyType::Sha256 => Some(B256::from_slice(&Sha256::digest(value))),
+        _ => None,
+    }
+}
+
+/// Returns the [PreimageKeyType::Blob] key of the field element, or the KZG proof if `index` is
+/// [FIELD_ELEMENTS_PER_BLOB], of the blob with the given commitment.
+pub(super) fn blob_element_key(commitment: &Bytes48, index: u64) -> B256 {
+    let mut blob_key = [0u8; BLOB_KEY_PREIMAGE_LENGTH];
+    blob_key[..48].copy_from_slice(commitment.as_ref());
+    blob_key[72..].copy_from_slice(index.to_be_bytes().as_ref());
+    PreimageKey::new(*keccak256(blob_key), PreimageKeyType::Blob).into()
+}
+
+/// Verifies a blob against its KZG commitment and proof.
+pub(super) fn verify_blob(
+    blob: Box<Blob>,
+    commitment: Bytes48,
+    proof: Bytes48,
+) -> Result<BlobTransactionSidecarItem, PreimageVerificationError> {
+    let sidecar =
+        BlobTransactionSidecarItem { index: 0, blob, kzg_commitment: commitment, kzg_proof: proof };
+    sidecar.verify_blob_kzg_proof().map_err(|e| PreimageVerificationError::InvalidBlob {
+        commitment,
+        reason: e.to_string(),
+    })?;
+    Ok(sidecar)
+}
+
+/// An error returned by the [VerifyingKeyValueStore] when a preimage does not match its key.
+#[derive(Debug, thiserror::Error)]
+pub enum PreimageVerificationError {
+    /// The digest of the preimage does not match its key.
+    #[error("{key_type:?} digest of the preimage does not match its key {key}")]
+    DigestMismatch {
+        /// The key that the preimage was stored under.
+        key: B256,
+        /// The type of the key.
+        key_type: PreimageKeyType,
+    },
+    /// A blob preimage was stored before the keccak256 preimage of its key, so its commitment
+    /// and index are unknown.
+    #[error("Blob key {0} has no known commitment and index")]
+    UnknownBlobKey(B256),
+    /// A blob field element, proof or whole blob has an invalid length.
+    #[error("Blob preimage for key {key} has invalid length {length}")]
+    InvalidBlobElement {
+        /// The key that the preimage was stored under.
+        key: B256,
+        /// The length of the preimage.
+        length: usize,
+    },
+    /// A whole blob was stored before the commitment and KZG proof of its blob, so it cannot be
+    /// verified.
+    #[error("Whole blob {0} has no known commitment and proof")]
+    UnknownWholeBlob(B256),
+    /// A blob does not match its KZG commitment.
+    #[error("Blob with commitment {commitment} failed KZG verification: {reason}")]
+    InvalidBlob {
+        /// The KZG commitment of the blob.
+        commitment: Bytes48,
+        /// The reason the verification failed.
+        reason: String,
+    },
+}
+
+/// The field elements and proof of a blob that has not yet been fully written.
+#[derive(Debug)]
+struct PendingBlob {
+    /// The blob, assembled from its field elements.
+    blob: Box<Blob>,
+    /// The keys of the field elements written so far, by index.
+    element_keys: BTreeMap<u64, B256>,
+    /// The key and value of the KZG proof, if it has been written.
+    proof: Option<(B256, Bytes48)>,
+}
+
+impl Default for PendingBlob {
+    fn default() -> Self {
+        Self { blob: Box::new(Blob::ZERO), element_keys: BTreeMap::new(), proof: None }
+    }
+}
+
+/// A [KeyValueStore] layer that verifies preimages against their keys before writing them to the
+/// inner store, so that a buggy or malicious provider is caught when a hint is fetched rather
+/// than when the client fails to decode the preimage.
+///
+/// - [PreimageKeyType::Keccak256] and [PreimageKeyType::Sha256] preimages are checked against the
+///   digest in their key.
+/// - [PreimageKeyType::Blob] preimages are held back until every field element and the KZG proof of
+///   their blob has been written, and the blob has been verified against its KZG commitment. The
+///   keccak256 preimage of each blob key, `commitment ++ uint256(index)`, must be written before
+///   the blob preimage itself.
+/// - [PreimageKeyType::GlobalGeneric] preimages are whole blobs, keyed by their versioned hash, and
+///   are verified against the commitment and KZG proof of their blob, which must be written before
+///   them.
+///
+/// Other preimages are written through unverified.
+#[derive(Debug)]
+pub struct VerifyingKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    inner: KV,
+    pending_blobs: HashMap<Bytes48, PendingBlob>,
+}
+
+impl<KV> VerifyingKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    /// Create a new [VerifyingKeyValueStore] over the given [KeyValueStore].
+    pub fn new(inner: KV) -> Self {
+        Self { inner, pending_blobs: HashMap::new() }
+    }
+
+    /// Buffers a blob field element or proof, and writes the blob through once it is complete
+    /// and verified.
+    fn set_blob(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        let mut key_preimage_key = key;
+        key_preimage_key[0] = PreimageKeyType::Keccak256 as u8;
+        let key_preimage = self
+            .inner
+            .get(key_preimage_key)
+            .filter(|preimage| preimage.len() == BLOB_KEY_PREIMAGE_LENGTH)
+            .ok_or(PreimageVerificationError::UnknownBlobKey(key))?;
+
+        let commitment = Bytes48::from_slice(&key_preimage[..48]);
+        let index = u64::from_be_bytes(key_preimage[72..].try_into()?);
+
+        // Drop the partially written blob on failure, so that a refetch starts over.
+        self.buffer_blob_element(commitment, index, key, value)
+            .inspect_err(|_| drop(self.pending_blobs.remove(&commitment)))
+    }
+
+    /// Buffers a field element or proof of the blob with the given commitment, and writes the
+    /// blob through once it is complete and verified.
+    fn buffer_blob_element(
+        &mut self,
+        commitment: Bytes48,
+        index: u64,
+        key: B256,
+        value: Vec<u8>,
+    ) -> Result<()> {
+        let invalid_length =
+            || PreimageVerificationError::InvalidBlobElement { key, length: value.len() };
+
+        let pending = self.pending_blobs.entry(commitment).or_default();
+        if index == FIELD_ELEMENTS_PER_BLOB {
+            let proof = Bytes48::try_from(value.as_slice()).map_err(|_| invalid_length())?;
+            pending.proof = Some((key, proof));
+        } else if index < FIELD_ELEMENTS_PER_BLOB && value.len() == 32 {
+            let offset = (index as usize) << 5;
+            pending.blob[offset..offset + 32].copy_from_slice(&value);
+            pending.element_keys.insert(index, key);
+        } else {
+            return Err(invalid_length().into());
+        }
+
+        if pending.element_keys.len() as u64 != FIELD_ELEMENTS_PER_BLOB || pending.proof.is_none() {
+            return Ok(());
+        }
+
+        let PendingBlob { blob, element_keys, proof } =
+            self.pending_blobs.remove(&commitment).expect("Pending blob exists");
+        let (proof_key, proof) = proof.expect("Proof checked above");
+
+        let sidecar = verify_blob(blob, commitment, proof)?;
+
+        for (index, key) in element_keys {
+            let offset = (index as usize) << 5;
+            self.inner.set(key, sidecar.blob[offset..offset + 32].to_vec())?;
+        }
+        self.inner.set(proof_key, proof.to_vec())
+    }
+
+    /// Verifies a whole blob, keyed by its versioned hash, against the commitment and KZG proof
+    /// of its blob, and writes it through.
+    fn set_whole_blob(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        let mut commitment_key = key;
+        commitment_key[0] = PreimageKeyType::Sha256 as u8;
+        let commitment = self
+            .inner
+            .get(commitment_key)
+            .and_then(|commitment| Bytes48::try_from(commitment.as_slice()).ok())
+            .ok_or(PreimageVerificationError::UnknownWholeBlob(key))?;
+        let proof = self
+            .inner
+            .get(blob_element_key(&commitment, FIELD_ELEMENTS_PER_BLOB))
+            .and_then(|proof| Bytes48::try_from(proof.as_slice()).ok())
+            .ok_or(PreimageVerificationError::UnknownWholeBlob(key))?;
+
+        let blob = Blob::try_from(value.as_slice()).map_err(|_| {
+            PreimageVerificationError::InvalidBlobElement { key, length: value.len() }
+        })?;
+        verify_blob(Box::new(blob), commitment, proof)?;
+        self.inner.set(key, value)
+    }
+}
+
+impl<KV> KeyValueStore for VerifyingKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.inner.get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        let key_type = PreimageKeyType::try_from(key[0])?;
+        match key_type {
+            PreimageKeyType::Blob => return self.set_blob(key, value),
+            PreimageKeyType::GlobalGeneric => return self.set_whole_blob(key, value),
+            _ => {}
+        }
+        let Some(digest) = preimage_digest(key_type, &value) else {
+            return self.inner.set(key, value);
+        };
+
+        // The high-order byte of the key holds its type, and the rest holds the digest.
+        if digest[1..] != key[1..] {
+            return Err(PreimageVerificationError::DigestMismatch { key, key_type }.into());
+        }
+        self.inner.set(key, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use alloy_eips::eip4844::kzg_to_versioned_hash;
+
+    /// The KZG commitment and proof of the zero blob: the compressed point at infinity.
+    const POINT_AT_INFINITY: [u8; 48] = {
+        let mut point = [0u8; 48];
+        point[0] = 0xc0;
+        point
+    };
+
+    /// Writes a blob to the store the way the host's hint handlers do, returning the first error.
+    fn set_blob<KV: KeyValueStore>(
+        kv: &mut KV,
+        blob: &Blob,
+        commitment: [u8; 48],
+        proof: [u8; 48],
+    ) -> Result<()> {
+        let mut blob_key = [0u8; 80];
+        blob_key[..48].copy_from_slice(&commitment);
+        for i in 0..=FIELD_ELEMENTS_PER_BLOB {
+            blob_key[72..].copy_from_slice(i.to_be_bytes().as_ref());
+            let blob_key_hash = keccak256(blob_key.as_ref());
+
+            let value = if i == FIELD_ELEMENTS_PER_BLOB {
+                proof.to_vec()
+            } else {
+                blob[(i as usize) << 5..(i as usize + 1) << 5].to_vec()
+            };
+            kv.set(PreimageKey::new_keccak256(*blob_key_hash).into(), blob_key.into())?;
+            kv.set(PreimageKey::new(*blob_key_hash, PreimageKeyType::Blob).into(), value)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifies_digests() {
+        let mut kv = VerifyingKeyValueStore::new(MemoryKeyValueStore::new());
+
+        let keccak_key = PreimageKey::new_keccak256(*keccak256(b"hello")).into();
+        kv.set(keccak_key, b"hello".to_vec()).unwrap();
+        assert_eq!(kv.get(keccak_key), Some(b"hello".to_vec()));
+
+        let sha256_key =
+            PreimageKey::new(Sha256::digest(b"hello").into(), PreimageKeyType::Sha256).into();
+        kv.set(sha256_key, b"hello".to_vec()).unwrap();
+        assert_eq!(kv.get(sha256_key), Some(b"hello".to_vec()));
+
+        for key in [keccak_key, sha256_key] {
+            let err = kv.set(key, b"goodbye".to_vec()).unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<PreimageVerificationError>(),
+                Some(PreimageVerificationError::DigestMismatch { .. })
+            ));
+        }
+
+        // Local keys have no digest, and are written through.
+        kv.set(PreimageKey::new_local(1).into(), b"local".to_vec()).unwrap();
+    }
+
+    #[test]
+    fn test_verifies_blobs() {
+        let mut kv = VerifyingKeyValueStore::new(MemoryKeyValueStore::new());
+        let blob = Blob::ZERO;
+        set_blob(&mut kv, &blob, POINT_AT_INFINITY, POINT_AT_INFINITY).unwrap();
+        assert!(kv.pending_blobs.is_empty());
+
+        let mut blob_key = [0u8; 80];
+        blob_key[..48].copy_from_slice(&POINT_AT_INFINITY);
+        let element_key = PreimageKey::new(*keccak256(blob_key), PreimageKeyType::Blob);
+        assert_eq!(kv.get(element_key.into()), Some(vec![0u8; 32]));
+    }
+
+    #[test]
+    fn test_rejects_invalid_blobs() {
+        let mut kv = VerifyingKeyValueStore::new(MemoryKeyValueStore::new());
+        let mut blob = Blob::ZERO;
+        blob[31] = 1;
+
+        let err = set_blob(&mut kv, &blob, POINT_AT_INFINITY, POINT_AT_INFINITY).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PreimageVerificationError>(),
+            Some(PreimageVerificationError::InvalidBlob { .. })
+        ));
+
+        // None of the blob's field elements may reach the inner store.
+        let mut blob_key = [0u8; 80];
+        blob_key[..48].copy_from_slice(&POINT_AT_INFINITY);
+        let element_key = PreimageKey::new(*keccak256(blob_key), PreimageKeyType::Blob);
+        assert_eq!(kv.get(element_key.into()), None);
+    }
+
+    #[test]
+    fn test_drops_failed_blobs() {
+        let mut kv = VerifyingKeyValueStore::new(MemoryKeyValueStore::new());
+        let mut blob_key = [0u8; 80];
+        blob_key[..48].copy_from_slice(&POINT_AT_INFINITY);
+        let blob_key_hash = keccak256(blob_key.as_ref());
+        kv.set(PreimageKey::new_keccak256(*blob_key_hash).into(), blob_key.into()).unwrap();
+
+        let element_key = PreimageKey::new(*blob_key_hash, PreimageKeyType::Blob).into();
+        kv.set(element_key, vec![0; 32]).unwrap();
+        assert_eq!(kv.pending_blobs.len(), 1);
+
+        assert!(kv.set(element_key, vec![0; 31]).is_err());
+        assert!(kv.pending_blobs.is_empty());
+    }
+
+    #[test]
+    fn test_verifies_whole_blobs() {
+        let mut kv = VerifyingKeyValueStore::new(MemoryKeyValueStore::new());
+        let versioned_hash = kzg_to_versioned_hash(&POINT_AT_INFINITY);
+        let whole_blob_key = PreimageKey::new(*versioned_hash, PreimageKeyType::GlobalGeneric);
+
+        // The commitment and proof of the blob must be written first.
+        let err = kv.set(whole_blob_key.into(), Blob::ZERO.to_vec()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PreimageVerificationError>(),
+            Some(PreimageVerificationError::UnknownWholeBlob(_))
+        ));
+
+        kv.set(
+            PreimageKey::new(*versioned_hash, PreimageKeyType::Sha256).into(),
+            POINT_AT_INFINITY.to_vec(),
+        )
+        .unwrap();
+        set_blob(&mut kv, &Blob::ZERO, POINT_AT_INFINITY, POINT_AT_INFINITY).unwrap();
+        kv.set(whole_blob_key.into(), Blob::ZERO.to_vec()).unwrap();
+
+        let mut blob = Blob::ZERO;
+        blob[31] = 1;
+        let err = kv.set(whole_blob_key.into(), blob.to_vec()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PreimageVerificationError>(),
+            Some(PreimageVerificationError::InvalidBlob { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unknown_blob_keys() {
+        let mut kv = VerifyingKeyValueStore::new(MemoryKeyValueStore::new());
+        let err = kv.set(PreimageKey::new([1; 32], PreimageKeyType::Blob).into(), vec![0; 32]);
+        assert!(matches!(
+            err.unwrap_err().downcast_ref::<PreimageVerificationError>(),
+            Some(PreimageVerificationError::UnknownBlobKey(_))
+        ));
+    }
+}
@@ -6,16 +6,35 @@ pub use server::{PreimageServer, PreimageServerError};
 
 mod kv;
 pub use kv::{
-    DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    BoxedKeyValueStore, DISK_KV_METADATA_FILE, DISK_KV_SCHEMA_VERSION, DiskKeyValueBackend,
+    DiskKeyValueStore, DiskKeyValueStoreMetadata, KeyValueStore, MemoryKeyValueStore,
+    PreimageVerificationError, SharedCacheKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    VerifyingKeyValueStore,
 };
+#[cfg(feature = "redb")]
+pub use kv::{REDB_KV_FILE, RedbKeyValueStore};
 
 mod backend;
-pub use backend::{HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg};
+pub use backend::{
+    DEFAULT_FETCH_BACKOFF, DEFAULT_FETCH_MAX_BACKOFF, DEFAULT_FETCH_RETRIES,
+    DEFAULT_MAX_CONCURRENT_FETCHES, HintHandler, HintRetryPolicy, OfflineHostBackend,
+    OnlineHostBackend, OnlineHostBackendCfg, PreimageAccess, PreimageAccessLog,
+    PreimageAccessStats, RecordingHostBackend, SharedPreimageAccessLog,
+};
 
 pub mod eth;
 
 #[cfg(feature = "single")]
 pub mod single;
 
+#[cfg(feature = "single")]
+pub mod bundle;
+
+#[cfg(feature = "single")]
+pub mod trace;
+
+#[cfg(feature = "single")]
+pub mod bisect;
+
 #[cfg(feature = "interop")]
 pub mod interop;
@@ -28,6 +28,9 @@ pub enum PreimageServerError {
     /// An error when failed to serve route hint.
     #[error("Failed to route hint: {0}")]
     RouteHintFailed(PreimageOracleError),
+    /// A hint was answered with a preimage that does not match its key.
+    #[error("Received an invalid preimage: {0}")]
+    InvalidPreimage(String),
     /// Task failed to execute to completion.
     #[error("Join error: {0}")]
     ExecutionError(#[from] tokio::task::JoinError),
@@ -71,6 +74,10 @@ where
             match oracle_server.next_preimage_request(backend.as_ref()).await {
                 Ok(_) => continue,
                 Err(PreimageOracleError::IOError(_)) => return Ok(()),
+                Err(PreimageOracleError::InvalidPreimage(e)) => {
+                    error!("Failed to serve preimage request: {e}");
+                    return Err(PreimageServerError::InvalidPreimage(e));
+                }
                 Err(e) => {
                     error!("Failed to serve preimage request: {e}");
                     return Err(PreimageServerError::PreimageRequestFailed(e));
@@ -89,6 +96,10 @@ where
             match hint_reader.next_hint(backend.as_ref()).await {
                 Ok(_) => continue,
                 Err(PreimageOracleError::IOError(_)) => return Ok(()),
+                Err(PreimageOracleError::InvalidPreimage(e)) => {
+                    error!("Failed to serve route hint: {e}");
+                    return Err(PreimageServerError::InvalidPreimage(e));
+                }
                 Err(e) => {
                     error!("Failed to serve route hint: {e}");
                     return Err(PreimageServerError::RouteHintFailed(e));
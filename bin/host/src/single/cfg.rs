@@ -2,9 +2,18 @@
 
 use super::{SingleChainHintHandler, SingleChainLocalInputs};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    BoxedKeyValueStore, DEFAULT_FETCH_BACKOFF, DEFAULT_FETCH_MAX_BACKOFF, DEFAULT_FETCH_RETRIES,
+    DEFAULT_MAX_CONCURRENT_FETCHES, DiskKeyValueBackend, HintRetryPolicy, MemoryKeyValueStore,
+    OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg, PreimageAccessLog, PreimageServer,
+    RecordingHostBackend, SharedCacheKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    VerifyingKeyValueStore,
+    bundle::{WitnessBundle, WitnessBundleError},
+    eth::{
+        FailoverBeaconClient, L2StateFetchMode, L2StateFetcher, failover_beacon_client,
+        failover_http_provider,
+    },
+    server::PreimageServerError,
+    trace::run_native_client,
 };
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
@@ -12,18 +21,25 @@ use clap::Parser;
 use kona_cli::cli_styles;
 use kona_genesis::RollupConfig;
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, HintReader, OracleServer, PreimageKey, PreimageServerBackend,
 };
-use kona_proof::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use kona_proof::{
+    HintType,
+    boot::{
+        L1_HEAD_KEY, L2_CHAIN_ID_KEY, L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY, L2_OUTPUT_ROOT_KEY,
+        L2_ROLLUP_CONFIG_KEY,
+    },
+};
+use kona_providers_alloy::OnlineBlobProvider;
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
-use std::{path::PathBuf, sync::Arc};
+use std::{num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::info;
 
 /// The host binary CLI application arguments.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -44,33 +60,41 @@ pub struct SingleChainHost {
     /// Number of the L2 block that the claimed output root commits to.
     #[arg(long, visible_alias = "l2-block-number", env)]
     pub claimed_l2_block_number: u64,
-    /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required).
+    /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required). Several
+    /// comma-separated addresses of redundant endpoints may be given, in order of preference;
+    /// requests fail over between them.
     #[arg(
         long,
         visible_alias = "l2",
         requires = "l1_node_address",
         requires = "l1_beacon_address",
+        value_delimiter = ',',
         env
     )]
-    pub l2_node_address: Option<String>,
-    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required)
+    pub l2_node_address: Option<Vec<String>>,
+    /// Address of L1 JSON-RPC endpoint to use (eth and debug namespace required). Several
+    /// comma-separated addresses of redundant endpoints may be given, in order of preference;
+    /// requests fail over between them.
     #[arg(
         long,
         visible_alias = "l1",
         requires = "l2_node_address",
         requires = "l1_beacon_address",
+        value_delimiter = ',',
         env
     )]
-    pub l1_node_address: Option<String>,
-    /// Address of the L1 Beacon API endpoint to use.
+    pub l1_node_address: Option<Vec<String>>,
+    /// Address of the L1 Beacon API endpoint to use. Several comma-separated addresses of
+    /// redundant endpoints may be given, in order of preference; requests fail over between them.
     #[arg(
         long,
         visible_alias = "beacon",
         requires = "l1_node_address",
         requires = "l2_node_address",
+        value_delimiter = ',',
         env
     )]
-    pub l1_beacon_address: Option<String>,
+    pub l1_beacon_address: Option<Vec<String>>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
@@ -80,6 +104,54 @@ pub struct SingleChainHost {
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// Keep the preimage database in `--data-dir` after the host exits, so that later runs can
+    /// reuse it. Offline runs open a persistent database read-only.
+    #[arg(long, requires = "data_dir", env)]
+    pub persist: bool,
+    /// The embedded database to store preimages in `--data-dir` with.
+    #[arg(long, value_enum, default_value_t, requires = "data_dir", env)]
+    pub kv_backend: DiskKeyValueBackend,
+    /// A shared, content-addressed preimage cache directory. Preimages are read from the cache
+    /// before being fetched from the providers, and fetched preimages are written back to it, so
+    /// that several hosts on one machine can share them.
+    #[arg(long, env)]
+    pub cache_dir: Option<PathBuf>,
+    /// After the client program completes, write every preimage served to it from the persistent
+    /// `--data-dir`, along with the boot information, to a witness bundle at the given path. The
+    /// bundle can be served offline with `kona-host bundle`.
+    #[arg(long, requires = "persist", env)]
+    pub export_bundle: Option<PathBuf>,
+    /// Record every preimage served to the client program, along with its type and size, and
+    /// write the read-set as JSON to the given path once the server exits.
+    #[arg(long, env)]
+    pub access_log: Option<PathBuf>,
+    /// Record every hint sent and preimage requested by the client program, with their sizes
+    /// and timings, and write the trace, along with the preimages served, as JSON to the given
+    /// path once the client program exits. The trace can be replayed without a host with
+    /// `kona-host replay`.
+    #[arg(long, conflicts_with = "server", env)]
+    pub trace: Option<PathBuf>,
+    /// The strategy with which L2 state trie nodes and contract code are fetched by hash. `auto`
+    /// probes the L2 endpoint, and falls back to `witness` if it does not serve `debug_dbGet`.
+    #[arg(long, value_enum, default_value_t, env)]
+    pub l2_state_fetch_mode: L2StateFetchMode,
+    /// The maximum number of hint fetches to run concurrently against the RPC endpoints. Lower
+    /// values reduce the load on rate-limited endpoints. Defaults to
+    /// [DEFAULT_MAX_CONCURRENT_FETCHES].
+    #[arg(long, env)]
+    pub max_concurrent_fetches: Option<NonZeroUsize>,
+    /// The number of times a failed hint fetch is retried, with exponential backoff. Defaults to
+    /// [DEFAULT_FETCH_RETRIES].
+    #[arg(long, env)]
+    pub fetch_retries: Option<u32>,
+    /// The delay before the first retry of a failed hint fetch, in milliseconds. The delay
+    /// doubles with every retry. Defaults to [DEFAULT_FETCH_BACKOFF].
+    #[arg(long, env)]
+    pub fetch_backoff_ms: Option<u64>,
+    /// The time after which a single attempt to fetch a hint is abandoned, in seconds. By
+    /// default, fetches never time out.
+    #[arg(long, env)]
+    pub fetch_timeout_secs: Option<u64>,
     /// Run the client program natively.
     #[arg(long, conflicts_with = "server", required_unless_present = "server")]
     pub native: bool,
@@ -123,6 +195,15 @@ pub enum SingleChainHostError {
     /// Task failed to execute to completion.
     #[error("Join error: {0}")]
     ExecutionError(#[from] tokio::task::JoinError),
+    /// An error when opening the key-value store.
+    #[error("Key-value store error: {0}")]
+    KeyValueStoreError(anyhow::Error),
+    /// A provider error.
+    #[error("Provider error: {0}")]
+    ProviderError(anyhow::Error),
+    /// An error when writing the witness bundle.
+    #[error("Witness bundle error: {0}")]
+    WitnessBundleError(#[from] WitnessBundleError),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -136,34 +217,48 @@ impl SingleChainHost {
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
 
-            self.start_server(hint, preimage).await?.await?
+            let kv_store = self.create_key_value_store()?;
+            let access_log = self.serve(hint, preimage, kv_store.clone()).await?.await??;
+            self.export_witness_bundle(&kv_store, access_log.as_ref()).await
         } else {
             self.start_native().await
         }
     }
 
     /// Starts the preimage server, communicating with the client over the provided channels.
+    ///
+    /// The server task resolves to the [PreimageAccessLog] of the run, if `--access-log` or
+    /// `--export-bundle` is set.
     pub async fn start_server<C>(
         &self,
         hint: C,
         preimage: C,
-    ) -> Result<JoinHandle<Result<(), SingleChainHostError>>, SingleChainHostError>
+    ) -> Result<
+        JoinHandle<Result<Option<PreimageAccessLog>, SingleChainHostError>>,
+        SingleChainHostError,
+    >
     where
         C: Channel + Send + Sync + 'static,
     {
-        let kv_store = self.create_key_value_store()?;
+        self.serve(hint, preimage, self.create_key_value_store()?).await
+    }
 
+    /// Starts the preimage server on top of the given [SharedKeyValueStore], communicating with
+    /// the client over the provided channels.
+    async fn serve<C>(
+        &self,
+        hint: C,
+        preimage: C,
+        kv_store: SharedKeyValueStore,
+    ) -> Result<
+        JoinHandle<Result<Option<PreimageAccessLog>, SingleChainHostError>>,
+        SingleChainHostError,
+    >
+    where
+        C: Channel + Send + Sync + 'static,
+    {
         let task_handle = if self.is_offline() {
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(OfflineHostBackend::new(kv_store)),
-                )
-                .start()
-                .await
-                .map_err(SingleChainHostError::from)
-            })
+            self.spawn_server(hint, preimage, OfflineHostBackend::new(kv_store))
         } else {
             let providers = self.create_providers().await?;
             let backend = OnlineHostBackend::new(
@@ -172,21 +267,52 @@ impl SingleChainHost {
                 providers,
                 SingleChainHintHandler,
             )
-            .with_proactive_hint(HintType::L2PayloadWitness);
+            .with_prefetch_hint(HintType::L2PayloadWitness)
+            .with_prefetch_hint(HintType::L1Receipts)
+            .with_max_concurrent_fetches(
+                self.max_concurrent_fetches.unwrap_or(DEFAULT_MAX_CONCURRENT_FETCHES),
+            )
+            .with_retry_policy(self.retry_policy());
+
+            self.spawn_server(hint, preimage, backend)
+        };
 
-            task::spawn(async {
+        Ok(task_handle)
+    }
+
+    /// Spawns the preimage server with the given backend. If `--access-log` or `--export-bundle`
+    /// is set, the preimages served are recorded, and the read-set is written to the access log
+    /// once the server exits.
+    fn spawn_server<C, B>(
+        &self,
+        hint: C,
+        preimage: C,
+        backend: B,
+    ) -> JoinHandle<Result<Option<PreimageAccessLog>, SingleChainHostError>>
+    where
+        C: Channel + Send + Sync + 'static,
+        B: PreimageServerBackend + Send + Sync + 'static,
+    {
+        if self.access_log.is_none() && self.export_bundle.is_none() {
+            return task::spawn(async {
                 PreimageServer::new(
                     OracleServer::new(preimage),
                     HintReader::new(hint),
                     Arc::new(backend),
                 )
                 .start()
-                .await
-                .map_err(SingleChainHostError::from)
-            })
-        };
+                .await?;
+                Ok(None)
+            });
+        }
 
-        Ok(task_handle)
+        let access_log_path = self.access_log.clone();
+        task::spawn(async move {
+            RecordingHostBackend::new(backend)
+                .serve(hint, preimage, access_log_path.as_deref())
+                .await
+                .map(Some)
+        })
     }
 
     /// Starts the host in native mode, running both the client and preimage server in the same
@@ -195,19 +321,31 @@ impl SingleChainHost {
         let hint = BidirectionalChannel::new()?;
         let preimage = BidirectionalChannel::new()?;
 
-        let server_task = self.start_server(hint.host, preimage.host).await?;
-        let client_task = task::spawn(kona_client::single::run(
-            OracleReader::new(preimage.client),
-            HintWriter::new(hint.client),
-            None,
-        ));
+        let kv_store = self.create_key_value_store()?;
+        let server_task = self.serve(hint.host, preimage.host, kv_store.clone()).await?;
+        let client_task =
+            task::spawn(run_native_client(preimage.client, hint.client, self.trace.clone()));
 
-        let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+        let (access_log, client_result) = tokio::try_join!(server_task, client_task)?;
+        let client_result = client_result?;
+        if client_result.is_ok() {
+            self.export_witness_bundle(&kv_store, access_log?.as_ref()).await?;
+        }
 
         // Bubble up the exit status of the client program if execution completes.
         std::process::exit(client_result.is_err() as i32)
     }
 
+    /// Returns the [HintRetryPolicy] configured by the `--fetch-*` flags.
+    pub fn retry_policy(&self) -> HintRetryPolicy {
+        HintRetryPolicy {
+            max_retries: self.fetch_retries.unwrap_or(DEFAULT_FETCH_RETRIES),
+            backoff: self.fetch_backoff_ms.map_or(DEFAULT_FETCH_BACKOFF, Duration::from_millis),
+            max_backoff: DEFAULT_FETCH_MAX_BACKOFF,
+            timeout: self.fetch_timeout_secs.map(Duration::from_secs),
+        }
+    }
+
     /// Returns `true` if the host is running in offline mode.
     pub const fn is_offline(&self) -> bool {
         self.l1_node_address.is_none() &&
@@ -231,43 +369,112 @@ impl SingleChainHost {
         serde_json::from_str(&ser_config).map_err(SingleChainHostError::ParseError)
     }
 
+    /// Returns the L2 chain ID of the host, from either `--l2-chain-id` or the rollup config.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.l2_chain_id.or_else(|| self.read_rollup_config().ok().map(|cfg| cfg.l2_chain_id))
+    }
+
     /// Creates the key-value store for the host backend.
     pub fn create_key_value_store(&self) -> Result<SharedKeyValueStore, SingleChainHostError> {
         let local_kv_store = SingleChainLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
-            let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
+        let remote_kv_store: BoxedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
+            self.kv_backend
+                .open(data_dir, self.persist, self.is_offline(), self.chain_id())
+                .map_err(SingleChainHostError::KeyValueStoreError)?
+        } else {
+            Box::new(MemoryKeyValueStore::new())
+        };
+        let remote_kv_store: BoxedKeyValueStore = if let Some(ref cache_dir) = self.cache_dir {
+            Box::new(
+                SharedCacheKeyValueStore::new(cache_dir.clone(), remote_kv_store)
+                    .map_err(SingleChainHostError::KeyValueStoreError)?,
+            )
         } else {
-            let mem_kv_store = MemoryKeyValueStore::new();
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, mem_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
+            remote_kv_store
+        };
+
+        // Verify fetched preimages against their keys before they reach the store or the cache.
+        let remote_kv_store = VerifyingKeyValueStore::new(remote_kv_store);
+
+        let split_kv_store = SplitKeyValueStore::new(local_kv_store, remote_kv_store);
+        Ok(Arc::new(RwLock::new(split_kv_store)))
+    }
+
+    /// Writes the preimages in the recorded read-set, along with the boot information, from the
+    /// given [SharedKeyValueStore] to the witness bundle at `--export-bundle`. Does nothing if no
+    /// bundle path or access log was provided.
+    ///
+    /// The bundle is read from the store the server ran on, rather than from the data directory,
+    /// which the store may hold an exclusive lock on.
+    pub async fn export_witness_bundle(
+        &self,
+        kv_store: &SharedKeyValueStore,
+        access_log: Option<&PreimageAccessLog>,
+    ) -> Result<(), SingleChainHostError> {
+        let (Some(bundle_path), Some(access_log)) = (&self.export_bundle, access_log) else {
+            return Ok(());
         };
 
-        Ok(kv_store)
+        let kv_store = kv_store.read().await;
+        let mut bundle = WitnessBundle::from(
+            access_log.compact(&*kv_store).map_err(SingleChainHostError::KeyValueStoreError)?,
+        );
+
+        // Include the local boot information, so that the bundle is self-contained.
+        for local_key in [
+            L1_HEAD_KEY,
+            L2_OUTPUT_ROOT_KEY,
+            L2_CLAIM_KEY,
+            L2_CLAIM_BLOCK_NUMBER_KEY,
+            L2_CHAIN_ID_KEY,
+            L2_ROLLUP_CONFIG_KEY,
+        ] {
+            let key = PreimageKey::new_local(local_key.to()).into();
+            if let Some(value) = kv_store.get(key) {
+                bundle.insert(key, value);
+            }
+        }
+
+        bundle.write_to_file(bundle_path)?;
+        info!(
+            target: "single_host",
+            "Exported witness bundle with {} preimages to {bundle_path:?}",
+            bundle.len()
+        );
+
+        Ok(())
     }
 
     /// Creates the providers required for the host backend.
     pub async fn create_providers(&self) -> Result<SingleChainProviders, SingleChainHostError> {
-        let l1_provider = http_provider(
+        let l1_provider = failover_http_provider(
             self.l1_node_address
                 .as_ref()
                 .ok_or(SingleChainHostError::Other("Provider must be set"))?,
         );
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
+        let blob_provider = OnlineBlobProvider::init(failover_beacon_client(
             self.l1_beacon_address
-                .clone()
+                .as_ref()
                 .ok_or(SingleChainHostError::Other("Beacon API URL must be set"))?,
         ))
         .await;
-        let l2_provider = http_provider::<Optimism>(
+        let l2_provider = failover_http_provider::<Optimism>(
             self.l2_node_address
                 .as_ref()
                 .ok_or(SingleChainHostError::Other("L2 node address must be set"))?,
         );
 
-        Ok(SingleChainProviders { l1: l1_provider, blobs: blob_provider, l2: l2_provider })
+        let l2_state = L2StateFetcher::new(l2_provider.clone(), self.l2_state_fetch_mode)
+            .await
+            .map_err(SingleChainHostError::ProviderError)?;
+
+        Ok(SingleChainProviders {
+            l1: l1_provider,
+            blobs: blob_provider,
+            l2: l2_provider,
+            l2_state: Arc::new(l2_state),
+        })
     }
 }
 
@@ -282,16 +489,19 @@ pub struct SingleChainProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: OnlineBlobProvider<FailoverBeaconClient>,
     /// The L2 EL provider.
     pub l2: RootProvider<Optimism>,
+    /// The fetcher of L2 state trie nodes and contract code.
+    pub l2_state: Arc<L2StateFetcher>,
 }
 
 #[cfg(test)]
 mod test {
-    use crate::single::SingleChainHost;
+    use crate::{HintRetryPolicy, single::SingleChainHost};
     use alloy_primitives::B256;
     use clap::Parser;
+    use std::time::Duration;
 
     #[test]
     fn test_flags() {
@@ -316,6 +526,73 @@ mod test {
             (["--server", "--rollup-config-path", "dummy", "--data-dir", "dummy"].as_slice(), true),
             (["--native", "--l2-chain-id", "0", "--data-dir", "dummy"].as_slice(), true),
             (["--native", "--rollup-config-path", "dummy", "--data-dir", "dummy"].as_slice(), true),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--persist"].as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                ["--native", "--l2-chain-id", "0", "--data-dir", "dummy", "--access-log", "dummy"]
+                    .as_slice(),
+                true,
+            ),
+            (
+                ["--native", "--l2-chain-id", "0", "--data-dir", "dummy", "--trace", "dummy"]
+                    .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--kv-backend",
+                    "rocksdb",
+                    "--cache-dir",
+                    "dummy",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy1,dummy2",
+                    "--l2-node-address",
+                    "dummy1",
+                    "--l2-node-address",
+                    "dummy2",
+                    "--l1-beacon-address",
+                    "dummy1,dummy2",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--fetch-retries",
+                    "5",
+                    "--fetch-backoff-ms",
+                    "100",
+                    "--fetch-timeout-secs",
+                    "30",
+                ]
+                .as_slice(),
+                true,
+            ),
             (
                 [
                     "--l1-node-address",
@@ -327,6 +604,8 @@ mod test {
                     "--server",
                     "--l2-chain-id",
                     "0",
+                    "--l2-state-fetch-mode",
+                    "witness",
                 ]
                 .as_slice(),
                 true,
@@ -341,6 +620,80 @@ mod test {
             (["--l1-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l2-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l1-beacon-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--fetch-retries", "-1"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--trace", "dummy"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--persist",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--export-bundle",
+                    "dummy",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--kv-backend",
+                    "rocksdb",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                ["--native", "--l2-chain-id", "0", "--data-dir", "dummy", "--kv-backend", "lmdb"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--l2-state-fetch-mode",
+                    "debug",
+                ]
+                .as_slice(),
+                false,
+            ),
             ([].as_slice(), false),
         ];
 
@@ -351,4 +704,87 @@ mod test {
             assert_eq!(parsed.is_ok(), valid);
         }
     }
+
+    #[test]
+    fn test_failover_and_retry_flags() {
+        let zero_hash_str = &B256::ZERO.to_string();
+        let host = SingleChainHost::parse_from([
+            "single",
+            "--l1-head",
+            zero_hash_str,
+            "--l2-head",
+            zero_hash_str,
+            "--l2-output-root",
+            zero_hash_str,
+            "--l2-claim",
+            zero_hash_str,
+            "--l2-block-number",
+            "0",
+            "--l1-node-address",
+            "http://l1-a,http://l1-b",
+            "--l2-node-address",
+            "http://l2",
+            "--l1-beacon-address",
+            "http://beacon",
+            "--server",
+            "--l2-chain-id",
+            "0",
+            "--fetch-timeout-secs",
+            "30",
+        ]);
+
+        assert_eq!(
+            host.l1_node_address,
+            Some(vec!["http://l1-a".to_string(), "http://l1-b".to_string()])
+        );
+        assert_eq!(
+            host.retry_policy(),
+            HintRetryPolicy { timeout: Some(Duration::from_secs(30)), ..Default::default() }
+        );
+    }
+
+    #[cfg(feature = "redb")]
+    #[tokio::test]
+    async fn test_exports_bundle_from_open_store() {
+        use crate::{
+            DiskKeyValueBackend, KeyValueStore, MemoryKeyValueStore, PreimageAccessLog,
+            bundle::WitnessBundle,
+        };
+        use alloy_primitives::keccak256;
+        use kona_preimage::PreimageKey;
+        use kona_proof::boot::L2_CHAIN_ID_KEY;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let bundle_path = data_dir.path().join("bundle.bin");
+        let host = SingleChainHost {
+            l1_node_address: Some(vec!["http://localhost:8545".to_string()]),
+            l2_chain_id: Some(10),
+            data_dir: Some(data_dir.path().to_path_buf()),
+            persist: true,
+            kv_backend: DiskKeyValueBackend::Redb,
+            export_bundle: Some(bundle_path.clone()),
+            ..Default::default()
+        };
+
+        // The persistent redb store holds an exclusive lock on the data directory while open.
+        let kv_store = host.create_key_value_store().unwrap();
+        let mut access_log = PreimageAccessLog::default();
+        for preimage in [b"read".as_slice(), b"unread".as_slice()] {
+            let key = PreimageKey::new_keccak256(*keccak256(preimage));
+            kv_store.write().await.set(key.into(), preimage.to_vec()).unwrap();
+            if preimage == b"read" {
+                access_log.record(key, preimage.len());
+            }
+        }
+        host.export_witness_bundle(&kv_store, Some(&access_log)).await.unwrap();
+
+        let bundle =
+            MemoryKeyValueStore::from(WitnessBundle::read_from_file(&bundle_path).unwrap());
+        assert_eq!(bundle.get(*access_log.accesses.keys().next().unwrap()), Some(b"read".to_vec()));
+        assert_eq!(bundle.get(PreimageKey::new_keccak256(*keccak256(b"unread")).into()), None);
+        assert_eq!(
+            bundle.get(PreimageKey::new_local(L2_CHAIN_ID_KEY.to()).into()),
+            Some(10u64.to_be_bytes().to_vec())
+        );
+    }
 }
@@ -16,7 +16,7 @@ use alloy_rpc_types::{Block, debug::ExecutionWitness};
 use anyhow::{Result, anyhow, ensure};
 use async_trait::async_trait;
 use kona_preimage::{PreimageKey, PreimageKeyType};
-use kona_proof::{Hint, HintType};
+use kona_proof::{Hint, HintType, l1::blob_preimage_key};
 use kona_protocol::BlockInfo;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use tracing::warn;
@@ -132,6 +132,10 @@ impl HintHandler for SingleChainHintHandler {
                     PreimageKey::new(*blob_key_hash, PreimageKeyType::Blob).into(),
                     sidecar.kzg_proof.to_vec(),
                 )?;
+
+                // Write the whole blob, for clients that retrieve blobs in bulk. It is verified
+                // against the commitment and proof written above.
+                kv_lock.set(blob_preimage_key(hash).into(), sidecar.blob.to_vec())?;
             }
             HintType::L1Precompile => {
                 ensure!(hint.data.len() >= 20, "Invalid hint data length");
@@ -225,35 +229,10 @@ impl HintHandler for SingleChainHintHandler {
                     .set(PreimageKey::new_keccak256(*output_root).into(), raw_output.into())?;
             }
             HintType::L2Code => {
-                // geth hashdb scheme code hash key prefix
-                const CODE_PREFIX: u8 = b'c';
-
                 ensure!(hint.data.len() == 32, "Invalid hint data length");
 
                 let hash: B256 = hint.data.as_ref().try_into()?;
-
-                // Attempt to fetch the code from the L2 chain provider.
-                let code_key = [&[CODE_PREFIX], hash.as_slice()].concat();
-                let code = providers
-                    .l2
-                    .client()
-                    .request::<&[Bytes; 1], Bytes>("debug_dbGet", &[code_key.into()])
-                    .await;
-
-                // Check if the first attempt to fetch the code failed. If it did, try fetching the
-                // code hash preimage without the geth hashdb scheme prefix.
-                let code = match code {
-                    Ok(code) => code,
-                    Err(_) => providers
-                        .l2
-                        .client()
-                        .request::<&[B256; 1], Bytes>("debug_dbGet", &[hash])
-                        .await
-                        .map_err(|e| anyhow!("Error fetching code hash preimage: {e}"))?,
-                };
-
-                let mut kv_lock = kv.write().await;
-                kv_lock.set(PreimageKey::new_keccak256(*hash).into(), code.into())?;
+                providers.l2_state.fetch_code(hash, &kv).await?;
             }
             HintType::L2StateNode => {
                 ensure!(hint.data.len() == 32, "Invalid hint data length");
@@ -266,11 +245,7 @@ impl HintHandler for SingleChainHintHandler {
                     "`debug_executePayload` failed to return a complete witness."
                 );
 
-                // Fetch the preimage from the L2 chain provider.
-                let preimage: Bytes = providers.l2.client().request("debug_dbGet", &[hash]).await?;
-
-                let mut kv_write_lock = kv.write().await;
-                kv_write_lock.set(PreimageKey::new_keccak256(*hash).into(), preimage.into())?;
+                providers.l2_state.fetch_state_node(hash, &kv).await?;
             }
             HintType::L2AccountProof => {
                 ensure!(hint.data.len() == 8 + 20, "Invalid hint data length");
@@ -278,20 +253,7 @@ impl HintHandler for SingleChainHintHandler {
                 let block_number = u64::from_be_bytes(hint.data.as_ref()[..8].try_into()?);
                 let address = Address::from_slice(&hint.data.as_ref()[8..28]);
 
-                let proof_response = providers
-                    .l2
-                    .get_proof(address, Default::default())
-                    .block_id(block_number.into())
-                    .await?;
-
-                // Write the account proof nodes to the key-value store.
-                let mut kv_lock = kv.write().await;
-                proof_response.account_proof.into_iter().try_for_each(|node| {
-                    let node_hash = keccak256(node.as_ref());
-                    let key = PreimageKey::new_keccak256(*node_hash);
-                    kv_lock.set(key.into(), node.into())?;
-                    Ok::<(), anyhow::Error>(())
-                })?;
+                providers.l2_state.fetch_proof(address, vec![], block_number, &kv).await?;
             }
             HintType::L2AccountStorageProof => {
                 ensure!(hint.data.len() == 8 + 20 + 32, "Invalid hint data length");
@@ -300,30 +262,7 @@ impl HintHandler for SingleChainHintHandler {
                 let address = Address::from_slice(&hint.data.as_ref()[8..28]);
                 let slot = B256::from_slice(&hint.data.as_ref()[28..]);
 
-                let mut proof_response = providers
-                    .l2
-                    .get_proof(address, vec![slot])
-                    .block_id(block_number.into())
-                    .await?;
-
-                let mut kv_lock = kv.write().await;
-
-                // Write the account proof nodes to the key-value store.
-                proof_response.account_proof.into_iter().try_for_each(|node| {
-                    let node_hash = keccak256(node.as_ref());
-                    let key = PreimageKey::new_keccak256(*node_hash);
-                    kv_lock.set(key.into(), node.into())?;
-                    Ok::<(), anyhow::Error>(())
-                })?;
-
-                // Write the storage proof nodes to the key-value store.
-                let storage_proof = proof_response.storage_proof.remove(0);
-                storage_proof.proof.into_iter().try_for_each(|node| {
-                    let node_hash = keccak256(node.as_ref());
-                    let key = PreimageKey::new_keccak256(*node_hash);
-                    kv_lock.set(key.into(), node.into())?;
-                    Ok::<(), anyhow::Error>(())
-                })?;
+                providers.l2_state.fetch_proof(address, vec![slot], block_number, &kv).await?;
             }
             HintType::L2PayloadWitness => {
                 ensure!(hint.data.len() >= 32, "Invalid hint data length");
@@ -364,4 +303,24 @@ impl HintHandler for SingleChainHintHandler {
 
         Ok(())
     }
+
+    fn preimage_key(hint: &Hint<HintType>) -> Option<PreimageKey> {
+        match hint.ty {
+            HintType::L1BlockHeader |
+            HintType::L2BlockHeader |
+            HintType::L2Code |
+            HintType::L2StateNode
+                if hint.data.len() == 32 =>
+            {
+                Some(PreimageKey::new_keccak256(hint.data.as_ref().try_into().ok()?))
+            }
+            HintType::L1Blob if hint.data.len() == 48 => {
+                Some(blob_preimage_key(B256::from_slice(&hint.data[..32])))
+            }
+            HintType::L1Precompile => {
+                Some(PreimageKey::new(*keccak256(hint.data.as_ref()), PreimageKeyType::Precompile))
+            }
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,314 @@
+//! Contains the [ProofTrace] format, a recording of every oracle interaction of a client program
+//! run, and the [TraceProfile] summarizing its cost.
+
+use alloy_primitives::{B256, Bytes};
+use kona_client::single::ProgramPhase;
+use kona_preimage::PreimageKey;
+use kona_proof::HintType;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// The version of the [ProofTrace] format.
+pub const PROOF_TRACE_VERSION: u8 = 1;
+
+/// A single oracle interaction of the client program, in the order it occurred.
+///
+/// Timestamps and durations are in microseconds, relative to the start of the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum TraceEvent {
+    /// The client program entered a [ProgramPhase].
+    Phase {
+        /// The phase entered.
+        phase: ProgramPhase,
+        /// The time at which the phase was entered.
+        at_us: u64,
+    },
+    /// The client program sent a hint.
+    Hint {
+        /// The hint, as sent over the hint channel.
+        hint: String,
+        /// The time at which the hint was sent.
+        at_us: u64,
+        /// The time until the hint was acknowledged.
+        duration_us: u64,
+    },
+    /// The client program requested a preimage.
+    Preimage {
+        /// The preimage key requested.
+        key: PreimageKey,
+        /// The size of the preimage, in bytes.
+        size: usize,
+        /// The time at which the preimage was requested.
+        at_us: u64,
+        /// The time until the preimage was received.
+        duration_us: u64,
+    },
+}
+
+/// A recording of every oracle interaction of a client program run, along with every preimage
+/// served, so that the run can be replayed without a host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofTrace {
+    /// The version of the trace format.
+    pub version: u8,
+    /// The oracle interactions, in the order they occurred.
+    pub events: Vec<TraceEvent>,
+    /// The preimages served, keyed by their preimage key.
+    pub preimages: BTreeMap<B256, Bytes>,
+}
+
+impl Default for ProofTrace {
+    fn default() -> Self {
+        Self { version: PROOF_TRACE_VERSION, events: Vec::new(), preimages: BTreeMap::new() }
+    }
+}
+
+impl ProofTrace {
+    /// Returns the [TraceProfile] of the trace.
+    pub fn profile(&self) -> TraceProfile {
+        let mut profile = TraceProfile::default();
+        let mut phase = None;
+        let mut block = None;
+
+        for event in &self.events {
+            let segment = match event {
+                TraceEvent::Phase { phase: entered, .. } => {
+                    phase = Some(*entered);
+                    if *entered != ProgramPhase::DerivationAndExecution {
+                        block = None;
+                    }
+                    continue;
+                }
+                TraceEvent::Hint { hint, .. } => {
+                    // The executor sends a payload witness hint before executing each block, so
+                    // it marks the start of the block's segment.
+                    let payload_witness: &str = HintType::L2PayloadWitness.into();
+                    if hint.split_once(' ').is_some_and(|(ty, _)| ty == payload_witness) {
+                        block = Some(block.map_or(0, |b| b + 1));
+                    }
+                    profile.segment(phase, block)
+                }
+                TraceEvent::Preimage { .. } => profile.segment(phase, block),
+            };
+            segment.record(event);
+        }
+
+        profile
+    }
+
+    /// Writes the trace, as JSON, to the file at the given path.
+    pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    /// Reads a trace from the JSON file at the given path.
+    pub fn read_from_file(path: &Path) -> std::io::Result<Self> {
+        let trace: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        if trace.version != PROOF_TRACE_VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unsupported proof trace version {}", trace.version),
+            ));
+        }
+        Ok(trace)
+    }
+}
+
+/// The cost of a segment of a client program run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentCost {
+    /// The number of hints sent.
+    pub hints: u64,
+    /// The total time spent waiting on hints to be acknowledged, in microseconds.
+    pub hint_us: u64,
+    /// The number of preimages requested.
+    pub preimages: u64,
+    /// The total size of the preimages requested, in bytes.
+    pub preimage_bytes: u64,
+    /// The total time spent waiting on preimages, in microseconds.
+    pub preimage_us: u64,
+}
+
+impl SegmentCost {
+    /// Adds the cost of the given [TraceEvent] to the segment.
+    fn record(&mut self, event: &TraceEvent) {
+        match event {
+            TraceEvent::Phase { .. } => {}
+            TraceEvent::Hint { duration_us, .. } => {
+                self.hints += 1;
+                self.hint_us += duration_us;
+            }
+            TraceEvent::Preimage { size, duration_us, .. } => {
+                self.preimages += 1;
+                self.preimage_bytes += *size as u64;
+                self.preimage_us += duration_us;
+            }
+        }
+    }
+}
+
+/// A summary of the oracle cost of a client program run, per [ProgramPhase], and per executed L2
+/// block.
+///
+/// Blocks are identified by the order in which they were executed. A block's segment begins with
+/// the payload witness hint sent before its execution, and ends with the next one, so it also
+/// holds the cost of deriving the following block.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceProfile {
+    /// The cost of each [ProgramPhase].
+    pub phases: Vec<(ProgramPhase, SegmentCost)>,
+    /// The cost of each executed block, by execution order.
+    pub blocks: Vec<SegmentCost>,
+    /// The cost of the oracle interactions outside of any phase.
+    pub unphased: SegmentCost,
+}
+
+impl TraceProfile {
+    /// Returns the total cost of the run.
+    pub fn total(&self) -> SegmentCost {
+        self.phases.iter().map(|(_, cost)| cost).chain([&self.unphased]).fold(
+            SegmentCost::default(),
+            |mut total, cost| {
+                total.hints += cost.hints;
+                total.hint_us += cost.hint_us;
+                total.preimages += cost.preimages;
+                total.preimage_bytes += cost.preimage_bytes;
+                total.preimage_us += cost.preimage_us;
+                total
+            },
+        )
+    }
+
+    /// Returns the phase and block segments that an event in the given phase and block counts
+    /// towards.
+    fn segment(&mut self, phase: Option<ProgramPhase>, block: Option<usize>) -> SegmentRef<'_> {
+        let phase = match phase {
+            Some(phase) => {
+                if self.phases.last().is_none_or(|(last, _)| *last != phase) {
+                    self.phases.push((phase, SegmentCost::default()));
+                }
+                &mut self.phases.last_mut().expect("Phase was just pushed").1
+            }
+            None => &mut self.unphased,
+        };
+        let block = block.map(|block| {
+            if self.blocks.len() <= block {
+                self.blocks.resize(block + 1, SegmentCost::default());
+            }
+            &mut self.blocks[block]
+        });
+        SegmentRef { phase, block }
+    }
+}
+
+/// Mutable references to the segments that a single event counts towards.
+#[derive(Debug)]
+struct SegmentRef<'a> {
+    phase: &'a mut SegmentCost,
+    block: Option<&'a mut SegmentCost>,
+}
+
+impl SegmentRef<'_> {
+    fn record(self, event: &TraceEvent) {
+        self.phase.record(event);
+        if let Some(block) = self.block {
+            block.record(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_profile() {
+        let preimage = |size| TraceEvent::Preimage {
+            key: PreimageKey::new_keccak256([0; 32]),
+            size,
+            at_us: 0,
+            duration_us: 10,
+        };
+        let hint =
+            |hint: &str| TraceEvent::Hint { hint: hint.to_string(), at_us: 0, duration_us: 5 };
+
+        let trace = ProofTrace {
+            events: vec![
+                TraceEvent::Phase { phase: ProgramPhase::Prologue, at_us: 0 },
+                preimage(32),
+                TraceEvent::Phase { phase: ProgramPhase::DerivationAndExecution, at_us: 0 },
+                hint("l1-block-header 0x00"),
+                preimage(100),
+                hint("l2-payload-witness 0x00"),
+                preimage(200),
+                hint("l2-payload-witness 0x01"),
+                preimage(300),
+                preimage(400),
+                TraceEvent::Phase { phase: ProgramPhase::Epilogue, at_us: 0 },
+            ],
+            ..Default::default()
+        };
+
+        let profile = trace.profile();
+        assert_eq!(
+            profile.phases,
+            vec![
+                (
+                    ProgramPhase::Prologue,
+                    SegmentCost {
+                        preimages: 1,
+                        preimage_bytes: 32,
+                        preimage_us: 10,
+                        ..Default::default()
+                    }
+                ),
+                (
+                    ProgramPhase::DerivationAndExecution,
+                    SegmentCost {
+                        hints: 3,
+                        hint_us: 15,
+                        preimages: 4,
+                        preimage_bytes: 1000,
+                        preimage_us: 40
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            profile.blocks,
+            vec![
+                SegmentCost {
+                    hints: 1,
+                    hint_us: 5,
+                    preimages: 1,
+                    preimage_bytes: 200,
+                    preimage_us: 10
+                },
+                SegmentCost {
+                    hints: 1,
+                    hint_us: 5,
+                    preimages: 2,
+                    preimage_bytes: 700,
+                    preimage_us: 20
+                },
+            ]
+        );
+        assert_eq!(profile.total().preimage_bytes, 1032);
+    }
+
+    #[test]
+    fn test_write_and_read_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+
+        let key = PreimageKey::new_keccak256([1; 32]);
+        let mut trace = ProofTrace::default();
+        trace.events.push(TraceEvent::Phase { phase: ProgramPhase::Prologue, at_us: 0 });
+        trace.events.push(TraceEvent::Preimage { key, size: 1, at_us: 1, duration_us: 2 });
+        trace.preimages.insert(key.into(), Bytes::from_static(&[1]));
+        trace.write_to_file(&path).unwrap();
+
+        assert_eq!(ProofTrace::read_from_file(&path).unwrap(), trace);
+    }
+}
@@ -0,0 +1,12 @@
+//! This module contains the proof trace format, a recording of every oracle interaction of a client
+//! program run, along with the recorder used by native hosts and a tool that replays a trace
+//! through the client program without a host.
+
+mod format;
+pub use format::{PROOF_TRACE_VERSION, ProofTrace, SegmentCost, TraceEvent, TraceProfile};
+
+mod recorder;
+pub use recorder::{ProofTraceRecorder, TracingHintWriter, TracingOracleClient, run_native_client};
+
+mod replay;
+pub use replay::{ReplayTrace, ReplayTraceError, TraceReplayer};
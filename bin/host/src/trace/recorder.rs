@@ -0,0 +1,190 @@
+//! Contains the [ProofTraceRecorder], which records every oracle interaction of the client program
+//! into a [ProofTrace].
+
+use super::{ProofTrace, TraceEvent};
+use async_trait::async_trait;
+use kona_client::single::{FaultProofProgramError, ProgramPhase, ProgramPhaseObserver};
+use kona_preimage::{
+    Channel, HintWriter, HintWriterClient, OracleReader, PreimageKey, PreimageOracleClient,
+    errors::PreimageOracleResult,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tracing::info;
+
+/// Runs the single-chain client program natively, over the given channels. If `trace_path` is
+/// set, the oracle interactions of the run are recorded, and written to it as a [ProofTrace] once
+/// the client program exits.
+pub async fn run_native_client<C>(
+    preimage: C,
+    hint: C,
+    trace_path: Option<PathBuf>,
+) -> std::io::Result<Result<(), FaultProofProgramError>>
+where
+    C: Channel + Send + Sync + Clone + std::fmt::Debug,
+{
+    let Some(trace_path) = trace_path else {
+        return Ok(kona_client::single::run(
+            OracleReader::new(preimage),
+            HintWriter::new(hint),
+            None,
+        )
+        .await);
+    };
+
+    let recorder = ProofTraceRecorder::new();
+    let result = kona_client::single::run_observed(
+        recorder.oracle_client(OracleReader::new(preimage)),
+        recorder.hint_writer(HintWriter::new(hint)),
+        None,
+        recorder.clone(),
+    )
+    .await;
+
+    let trace = recorder.trace();
+    trace.write_to_file(&trace_path)?;
+    info!(
+        target: "trace_recorder",
+        "Recorded {} oracle interactions and {} preimages to {trace_path:?}",
+        trace.events.len(),
+        trace.preimages.len()
+    );
+    Ok(result)
+}
+
+/// Records the oracle interactions of a client program run, along with every preimage served,
+/// into a [ProofTrace].
+///
+/// The recorder wraps the client's [PreimageOracleClient] and [HintWriterClient] with
+/// [Self::oracle_client] and [Self::hint_writer], and is passed to the client program as its
+/// [ProgramPhaseObserver].
+#[derive(Debug, Clone)]
+pub struct ProofTraceRecorder {
+    /// The trace recorded so far.
+    trace: Arc<Mutex<ProofTrace>>,
+    /// The start of the run.
+    start: Instant,
+}
+
+impl Default for ProofTraceRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProofTraceRecorder {
+    /// Creates a new [ProofTraceRecorder] with an empty trace, starting the clock of the run.
+    pub fn new() -> Self {
+        Self { trace: Default::default(), start: Instant::now() }
+    }
+
+    /// Wraps the given [PreimageOracleClient] to record the preimages it serves.
+    pub fn oracle_client<P>(&self, inner: P) -> TracingOracleClient<P> {
+        TracingOracleClient { inner, recorder: self.clone() }
+    }
+
+    /// Wraps the given [HintWriterClient] to record the hints it sends.
+    pub fn hint_writer<H>(&self, inner: H) -> TracingHintWriter<H> {
+        TracingHintWriter { inner, recorder: self.clone() }
+    }
+
+    /// Returns a copy of the trace recorded so far.
+    pub fn trace(&self) -> ProofTrace {
+        self.trace.lock().expect("Lock poisoned").clone()
+    }
+
+    /// Returns the time elapsed since the start of the run, in microseconds.
+    fn elapsed_us(&self) -> u64 {
+        self.start.elapsed().as_micros() as u64
+    }
+
+    /// Appends an event to the trace.
+    fn push(&self, event: TraceEvent) {
+        self.trace.lock().expect("Lock poisoned").events.push(event);
+    }
+
+    /// Records that the preimage for `key` was served, taking `at_us` as the time it was
+    /// requested.
+    fn record_preimage(&self, key: PreimageKey, preimage: &[u8], at_us: u64) {
+        let duration_us = self.elapsed_us() - at_us;
+        let mut trace = self.trace.lock().expect("Lock poisoned");
+        trace.events.push(TraceEvent::Preimage { key, size: preimage.len(), at_us, duration_us });
+        trace.preimages.entry(key.into()).or_insert_with(|| preimage.to_vec().into());
+    }
+
+    /// Records that the hint, sent at `at_us`, was acknowledged after `duration_us`.
+    fn record_hint(&self, hint: &str, at_us: u64, duration_us: u64) {
+        self.push(TraceEvent::Hint { hint: hint.to_string(), at_us, duration_us });
+    }
+}
+
+impl ProgramPhaseObserver for ProofTraceRecorder {
+    fn enter(&self, phase: ProgramPhase) {
+        self.push(TraceEvent::Phase { phase, at_us: self.elapsed_us() });
+    }
+}
+
+/// A [PreimageOracleClient] that records the preimages served by the inner client to a
+/// [ProofTraceRecorder].
+#[derive(Debug, Clone)]
+pub struct TracingOracleClient<P> {
+    inner: P,
+    recorder: ProofTraceRecorder,
+}
+
+#[async_trait]
+impl<P> PreimageOracleClient for TracingOracleClient<P>
+where
+    P: PreimageOracleClient + Send + Sync,
+{
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let at_us = self.recorder.elapsed_us();
+        let preimage = self.inner.get(key).await?;
+        self.recorder.record_preimage(key, &preimage, at_us);
+        Ok(preimage)
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        let at_us = self.recorder.elapsed_us();
+        self.inner.get_exact(key, buf).await?;
+        self.recorder.record_preimage(key, buf, at_us);
+        Ok(())
+    }
+}
+
+/// A [HintWriterClient] that records the hints sent by the inner client to a
+/// [ProofTraceRecorder].
+#[derive(Debug, Clone)]
+pub struct TracingHintWriter<H> {
+    inner: H,
+    recorder: ProofTraceRecorder,
+}
+
+#[async_trait]
+impl<H> HintWriterClient for TracingHintWriter<H>
+where
+    H: HintWriterClient + Send + Sync,
+{
+    async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+        let at_us = self.recorder.elapsed_us();
+        self.inner.write(hint).await?;
+        self.recorder.record_hint(hint, at_us, self.recorder.elapsed_us() - at_us);
+        Ok(())
+    }
+
+    async fn write_batch(&self, hints: &[String]) -> PreimageOracleResult<()> {
+        // The batch is acknowledged as a whole, so the time it took is attributed to its first
+        // hint.
+        let at_us = self.recorder.elapsed_us();
+        self.inner.write_batch(hints).await?;
+        let mut duration_us = self.recorder.elapsed_us() - at_us;
+        for hint in hints {
+            self.recorder.record_hint(hint, at_us, duration_us);
+            duration_us = 0;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+//! Contains the [TraceReplayer], which serves a recorded [ProofTrace] back to the client program
+//! without a host, and the [ReplayTrace] command that runs the client program against it.
+
+use super::{ProofTrace, TraceEvent};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use clap::Parser;
+use kona_cli::cli_styles;
+use kona_client::single::{FaultProofProgramError, ProgramPhase, ProgramPhaseObserver};
+use kona_preimage::{
+    HintWriterClient, PreimageKey, PreimageOracleClient,
+    errors::{PreimageOracleError, PreimageOracleResult},
+};
+use serde::Serialize;
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tracing::info;
+
+/// The replay state of a [TraceReplayer].
+#[derive(Debug)]
+struct ReplayState {
+    /// The index of the next expected event.
+    cursor: usize,
+    /// The first divergence of the client program from the trace, if any.
+    divergence: Option<String>,
+}
+
+/// A [PreimageOracleClient] and [HintWriterClient] that serves a recorded [ProofTrace] back to the
+/// client program.
+///
+/// The client program must send the same hints, request the same preimages and enter the same
+/// [ProgramPhase]s, in the same order, as in the recorded run. Any divergence fails the request
+/// that diverged, and is reported by [Self::finish].
+#[derive(Debug, Clone)]
+pub struct TraceReplayer {
+    /// The trace being replayed.
+    trace: Arc<ProofTrace>,
+    /// The replay state.
+    state: Arc<Mutex<ReplayState>>,
+}
+
+impl TraceReplayer {
+    /// Creates a new [TraceReplayer] for the given trace.
+    pub fn new(trace: ProofTrace) -> Self {
+        Self {
+            trace: Arc::new(trace),
+            state: Arc::new(Mutex::new(ReplayState { cursor: 0, divergence: None })),
+        }
+    }
+
+    /// Returns `Ok(())` if the client program replayed the whole trace without diverging from
+    /// it, or the first divergence otherwise.
+    pub fn finish(&self) -> Result<(), String> {
+        let state = self.state.lock().expect("Lock poisoned");
+        if let Some(divergence) = &state.divergence {
+            return Err(divergence.clone());
+        }
+        let remaining = self.trace.events.len() - state.cursor;
+        if remaining > 0 {
+            return Err(format!("Client program exited with {remaining} trace events left"));
+        }
+        Ok(())
+    }
+
+    /// Advances past the next event of the trace if `matches` accepts it, and returns it.
+    /// Otherwise, records the divergence, described with `actual`.
+    fn advance<F>(&self, actual: &str, matches: F) -> Result<&TraceEvent, String>
+    where
+        F: FnOnce(&TraceEvent) -> bool,
+    {
+        let mut state = self.state.lock().expect("Lock poisoned");
+        if let Some(divergence) = &state.divergence {
+            return Err(divergence.clone());
+        }
+
+        let index = state.cursor;
+        match self.trace.events.get(index) {
+            Some(event) if matches(event) => {
+                state.cursor += 1;
+                Ok(event)
+            }
+            expected => {
+                let divergence = format!(
+                    "Trace replay diverged at event #{index}: expected {expected:?}, got {actual}"
+                );
+                state.divergence = Some(divergence.clone());
+                Err(divergence)
+            }
+        }
+    }
+
+    /// Advances past the next event of the trace, which must be a request for the preimage of
+    /// `key`, and returns the recorded preimage.
+    fn next_preimage(&self, key: PreimageKey) -> PreimageOracleResult<&[u8]> {
+        self.advance(
+            &format!("preimage request for {key}"),
+            |event| matches!(event, TraceEvent::Preimage { key: recorded, .. } if *recorded == key),
+        )
+        .map_err(PreimageOracleError::Other)?;
+
+        self.trace
+            .preimages
+            .get(&B256::from(key))
+            .map(|preimage| preimage.as_ref())
+            .ok_or(PreimageOracleError::KeyNotFound)
+    }
+}
+
+#[async_trait]
+impl PreimageOracleClient for TraceReplayer {
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        self.next_preimage(key).map(|preimage| preimage.to_vec())
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        let preimage = self.next_preimage(key)?;
+        if preimage.len() != buf.len() {
+            return Err(PreimageOracleError::BufferLengthMismatch(buf.len(), preimage.len()));
+        }
+        buf.copy_from_slice(preimage);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HintWriterClient for TraceReplayer {
+    async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+        self.advance(
+            &format!("hint {hint:?}"),
+            |event| matches!(event, TraceEvent::Hint { hint: recorded, .. } if recorded == hint),
+        )
+        .map_err(PreimageOracleError::Other)?;
+        Ok(())
+    }
+}
+
+impl ProgramPhaseObserver for TraceReplayer {
+    fn enter(&self, phase: ProgramPhase) {
+        // A divergence is recorded, and fails the next oracle request.
+        let _ = self.advance(
+            &format!("phase {phase:?}"),
+            |event| matches!(event, TraceEvent::Phase { phase: recorded, .. } if *recorded == phase),
+        );
+    }
+}
+
+/// Replays a proof trace recorded by a native host run with `--trace` through the client program,
+/// without a host.
+#[derive(Default, Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct ReplayTrace {
+    /// Path to the proof trace to replay.
+    #[arg(long, env)]
+    pub trace: PathBuf,
+    /// Write the per-phase and per-block oracle cost profile of the trace, as JSON, to the given
+    /// path.
+    #[arg(long, env)]
+    pub profile: Option<PathBuf>,
+}
+
+/// An error that can occur when replaying a proof trace.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayTraceError {
+    /// An IO error.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// The client program failed.
+    #[error("Client program error: {0}")]
+    ClientProgramError(#[from] FaultProofProgramError),
+    /// The client program diverged from the trace.
+    #[error("{0}")]
+    Diverged(String),
+}
+
+impl ReplayTrace {
+    /// Runs the [ReplayTrace] command.
+    pub async fn start(self) -> Result<(), ReplayTraceError> {
+        let trace = ProofTrace::read_from_file(&self.trace)?;
+        let profile = trace.profile();
+
+        let replayer = TraceReplayer::new(trace);
+        let result = kona_client::single::run_observed(
+            replayer.clone(),
+            replayer.clone(),
+            None,
+            replayer.clone(),
+        )
+        .await;
+
+        // A divergence explains a failure of the client program, so it is reported first.
+        replayer.finish().map_err(ReplayTraceError::Diverged)?;
+        result?;
+
+        let total = profile.total();
+        info!(
+            target: "trace_replay",
+            "Replayed {} hints and {} preimage requests ({} bytes) over {} blocks",
+            total.hints,
+            total.preimages,
+            total.preimage_bytes,
+            profile.blocks.len()
+        );
+        if let Some(ref profile_path) = self.profile {
+            std::fs::write(
+                profile_path,
+                serde_json::to_vec_pretty(&profile).map_err(std::io::Error::from)?,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::Bytes;
+
+    fn trace() -> ProofTrace {
+        let key = PreimageKey::new_keccak256([1; 32]);
+        ProofTrace {
+            events: vec![
+                TraceEvent::Phase { phase: ProgramPhase::Prologue, at_us: 0 },
+                TraceEvent::Hint {
+                    hint: "l1-block-header 0x01".to_string(),
+                    at_us: 1,
+                    duration_us: 1,
+                },
+                TraceEvent::Preimage { key, size: 2, at_us: 2, duration_us: 1 },
+            ],
+            preimages: [(key.into(), Bytes::from_static(&[1, 2]))].into(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replays_trace() {
+        let replayer = TraceReplayer::new(trace());
+        let key = PreimageKey::new_keccak256([1; 32]);
+
+        replayer.enter(ProgramPhase::Prologue);
+        assert!(replayer.finish().is_err());
+        replayer.write("l1-block-header 0x01").await.unwrap();
+        let mut buf = [0; 2];
+        replayer.get_exact(key, &mut buf).await.unwrap();
+        assert_eq!(buf, [1, 2]);
+        replayer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_detects_divergence() {
+        let replayer = TraceReplayer::new(trace());
+
+        // The client program skips the hint, and requests a preimage first.
+        replayer.enter(ProgramPhase::Prologue);
+        assert!(replayer.get(PreimageKey::new_keccak256([1; 32])).await.is_err());
+
+        // Once diverged, every later request fails.
+        assert!(replayer.write("l1-block-header 0x01").await.is_err());
+        assert!(replayer.finish().unwrap_err().contains("event #1"));
+    }
+}
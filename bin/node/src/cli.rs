@@ -1,7 +1,7 @@
 //! Contains the node CLI.
 
 use crate::{
-    commands::{NetCommand, NodeCommand, RegistryCommand},
+    commands::{DecodeCommand, NetCommand, NodeCommand, RegistryCommand},
     flags::{GlobalArgs, MetricsArgs},
 };
 use anyhow::Result;
@@ -18,6 +18,8 @@ pub enum Commands {
     Net(NetCommand),
     /// Lists the OP Stack chains available in the superchain-registry.
     Registry(RegistryCommand),
+    /// Decodes batcher transactions into channels and batches.
+    Decode(DecodeCommand),
 }
 
 /// The node CLI.
@@ -45,6 +47,7 @@ impl Cli {
             Commands::Registry(ref registry) => {
                 registry.init_telemetry(&self.global, &self.metrics)?
             }
+            Commands::Decode(ref decode) => decode.init_telemetry(&self.global, &self.metrics)?,
         }
 
         // Run the subcommand.
@@ -52,6 +55,7 @@ impl Cli {
             Commands::Node(node) => Self::run_until_ctrl_c(node.run(&self.global)),
             Commands::Net(net) => Self::run_until_ctrl_c(net.run(&self.global)),
             Commands::Registry(registry) => registry.run(&self.global),
+            Commands::Decode(decode) => Self::run_until_ctrl_c(decode.run(&self.global)),
         }
     }
 
@@ -0,0 +1,538 @@
+//! Decode Subcommand
+
+use crate::flags::{GlobalArgs, MetricsArgs};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::{Address, B256, Bytes, FixedBytes, hex};
+use anyhow::{Result, anyhow, bail};
+use clap::{ArgGroup, Parser};
+use kona_derive::{
+    errors::{PipelineError, PipelineErrorKind},
+    sources::{BlobData, EthereumDataSource},
+    traits::{ChainProvider, DataAvailabilityProvider},
+};
+use kona_genesis::{MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig};
+use kona_protocol::{Batch, BatchReader, BlockInfo, Channel, ChannelId, Frame};
+use kona_providers_alloy::{AlloyChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use serde::Serialize;
+use std::{collections::HashMap, fs::File, path::PathBuf};
+use tracing::debug;
+use url::Url;
+
+/// The size of the cache of the L1 chain provider.
+const L1_PROVIDER_CACHE_SIZE: usize = 32;
+
+/// The `decode` Subcommand
+///
+/// The `decode` subcommand decodes the frames posted by the batcher into channels, and the
+/// channels into single and span batches, and prints them as JSON. The batcher data is either
+/// fetched from a range of L1 blocks, or passed as raw batcher transaction data or blobs. Errors
+/// are reported at the layer they occur in, without aborting the decoding of the other inputs.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node decode [FLAGS] [OPTIONS]
+/// ```
+#[derive(Parser, Debug, Clone)]
+#[command(about = "Decodes batcher transactions into channels and batches")]
+#[command(group(
+    ArgGroup::new("input").required(true).multiple(true).args(["start_block", "txs", "blobs"])
+))]
+pub struct DecodeCommand {
+    /// URL of the L1 execution client RPC API.
+    #[arg(long, visible_alias = "l1", env = "L1_ETH_RPC")]
+    pub l1_eth_rpc: Option<Url>,
+    /// URL of the L1 beacon API.
+    #[arg(long, visible_alias = "l1.beacon", env = "L1_BEACON")]
+    pub l1_beacon: Option<Url>,
+    /// The first L1 block of the range to decode the batcher transactions of.
+    #[arg(long, requires_all = ["l1_eth_rpc", "l1_beacon"])]
+    pub start_block: Option<u64>,
+    /// The last L1 block of the range to decode the batcher transactions of. Defaults to the
+    /// start block.
+    #[arg(long, requires = "start_block")]
+    pub end_block: Option<u64>,
+    /// The address of the batcher. Defaults to the batcher address of the genesis system config.
+    #[arg(long)]
+    pub batcher_address: Option<Address>,
+    /// Hex-encoded data of a batcher transaction, prefixed with the derivation version.
+    #[arg(long = "tx", value_name = "HEX")]
+    pub txs: Vec<Bytes>,
+    /// A hex-encoded blob of a batcher transaction.
+    #[arg(long = "blob", value_name = "HEX")]
+    pub blobs: Vec<Bytes>,
+    /// Path to a custom L2 rollup configuration file
+    /// (overrides the default rollup configuration from the registry)
+    #[arg(long, visible_alias = "rollup-cfg")]
+    pub l2_config_file: Option<PathBuf>,
+}
+
+impl DecodeCommand {
+    /// Initializes the telemetry stack and Prometheus metrics recorder.
+    pub fn init_telemetry(&self, args: &GlobalArgs, metrics: &MetricsArgs) -> anyhow::Result<()> {
+        args.init_tracing(None)?;
+        metrics.init_metrics()
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let cfg = self.get_l2_config(args)?;
+        let mut decoder = ChannelDecoder::new(&cfg);
+
+        for (index, tx) in self.txs.iter().enumerate() {
+            decoder.add_data(DataKind::Tx, None, index, tx);
+        }
+        for (index, blob) in self.blobs.iter().enumerate() {
+            let data = Blob::try_from(blob.as_ref())
+                .map_err(|_| format!("Invalid blob length: {} bytes", blob.len()))
+                .and_then(|blob| BlobData::from_blob(&blob).decode().map_err(|e| e.to_string()));
+            match data {
+                Ok(data) => decoder.add_data(DataKind::Blob, None, index, &data),
+                Err(e) => decoder.add_error(DataKind::Blob, None, index, e),
+            }
+        }
+        if let Some(start_block) = self.start_block {
+            self.decode_l1_blocks(&cfg, start_block, &mut decoder).await?;
+        }
+
+        println!("{}", serde_json::to_string_pretty(&decoder.finish())?);
+        Ok(())
+    }
+
+    /// Fetches the batcher data of the L1 blocks in the range and adds it to the decoder.
+    async fn decode_l1_blocks(
+        &self,
+        cfg: &RollupConfig,
+        start_block: u64,
+        decoder: &mut ChannelDecoder<'_>,
+    ) -> Result<()> {
+        let end_block = self.end_block.unwrap_or(start_block);
+        if end_block < start_block {
+            bail!("End block {end_block} is before start block {start_block}");
+        }
+        let (Some(l1_eth_rpc), Some(l1_beacon)) = (&self.l1_eth_rpc, &self.l1_beacon) else {
+            bail!("An L1 RPC and beacon URL are required to decode L1 blocks");
+        };
+        let batcher_address = self
+            .batcher_address
+            .or_else(|| cfg.genesis.system_config.as_ref().map(|c| c.batcher_address))
+            .ok_or(anyhow!("No batcher address found for chain ID: {}", cfg.l2_chain_id))?;
+
+        let mut chain_provider =
+            AlloyChainProvider::new_http(l1_eth_rpc.clone(), L1_PROVIDER_CACHE_SIZE);
+        let blob_provider =
+            OnlineBlobProvider::init(OnlineBeaconClient::new_http(l1_beacon.to_string())).await;
+        let mut source =
+            EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, cfg);
+
+        for number in start_block..=end_block {
+            let block = match chain_provider.block_info_by_number(number).await {
+                Ok(block) => block,
+                Err(e) => {
+                    decoder.add_error(DataKind::L1, Some(number), 0, e.to_string());
+                    continue;
+                }
+            };
+            debug!("Decoding batcher transactions of L1 block #{}", number);
+
+            let mut index = 0;
+            loop {
+                match source.next(&block, batcher_address).await {
+                    Ok(data) => decoder.add_data(DataKind::L1, Some(block), index, &data),
+                    Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => break,
+                    Err(e) => {
+                        decoder.add_error(DataKind::L1, Some(block.number), index, e.to_string());
+                        break;
+                    }
+                }
+                index += 1;
+            }
+            source.clear();
+        }
+        Ok(())
+    }
+
+    /// Get the L2 rollup config, either from a file or the superchain registry.
+    pub fn get_l2_config(&self, args: &GlobalArgs) -> Result<RollupConfig> {
+        match &self.l2_config_file {
+            Some(path) => {
+                debug!("Loading l2 config from file: {:?}", path);
+                let file = File::open(path)
+                    .map_err(|e| anyhow!("Failed to open l2 config file: {}", e))?;
+                serde_json::from_reader(file)
+                    .map_err(|e| anyhow!("Failed to parse l2 config: {}", e))
+            }
+            None => {
+                debug!("Loading l2 config from superchain registry");
+                args.rollup_config()
+                    .ok_or(anyhow!("Failed to find l2 config for chain ID {}", args.l2_chain_id))
+            }
+        }
+    }
+}
+
+/// Decodes batcher data into frames, assembles the frames into channels, and reads the batches
+/// from the channels once all data was added.
+#[derive(Debug)]
+struct ChannelDecoder<'a> {
+    /// The rollup config.
+    cfg: &'a RollupConfig,
+    /// The decoded batcher data, in the order it was added.
+    data: Vec<DataOutput>,
+    /// The channels, in the order they were opened.
+    channels: Vec<ChannelState>,
+    /// The index of each channel in `channels`.
+    channel_indices: HashMap<ChannelId, usize>,
+}
+
+/// A channel that is being assembled by the [ChannelDecoder].
+#[derive(Debug)]
+struct ChannelState {
+    /// The channel.
+    channel: Channel,
+    /// The L1 block the channel was opened in, if the batcher data was fetched from L1.
+    open_block: Option<BlockInfo>,
+    /// The errors of the frames that could not be added to the channel.
+    errors: Vec<String>,
+}
+
+impl<'a> ChannelDecoder<'a> {
+    /// Creates a new [ChannelDecoder].
+    fn new(cfg: &'a RollupConfig) -> Self {
+        Self { cfg, data: Vec::new(), channels: Vec::new(), channel_indices: HashMap::new() }
+    }
+
+    /// Parses the frames of the batcher data and adds them to their channels.
+    fn add_data(&mut self, kind: DataKind, l1_block: Option<BlockInfo>, index: usize, data: &[u8]) {
+        let mut output = DataOutput::new(kind, l1_block, index);
+        match Frame::parse_frames(data) {
+            Ok(frames) => {
+                for frame in frames {
+                    output.frames.push(FrameOutput::from(&frame));
+                    self.add_frame(frame, l1_block);
+                }
+            }
+            Err(e) => output.error = Some(e.to_string()),
+        }
+        self.data.push(output);
+    }
+
+    /// Records batcher data that could not be retrieved or decoded, with the number of the L1
+    /// block it was fetched from, if any.
+    fn add_error(&mut self, kind: DataKind, l1_block: Option<u64>, index: usize, error: String) {
+        self.data.push(DataOutput {
+            kind,
+            l1_block,
+            index,
+            frames: Vec::new(),
+            error: Some(error),
+        });
+    }
+
+    /// Adds the frame to its channel, opening the channel if it does not exist yet.
+    fn add_frame(&mut self, frame: Frame, l1_block: Option<BlockInfo>) {
+        let index = *self.channel_indices.entry(frame.id).or_insert_with(|| {
+            self.channels.push(ChannelState {
+                channel: Channel::new(frame.id, l1_block.unwrap_or_default()),
+                open_block: l1_block,
+                errors: Vec::new(),
+            });
+            self.channels.len() - 1
+        });
+        let state = &mut self.channels[index];
+        let number = frame.number;
+        if let Err(e) = state.channel.add_frame(frame, l1_block.unwrap_or_default()) {
+            state.errors.push(format!("Failed to add frame {number}: {e}"));
+        }
+    }
+
+    /// Reads the batches from the channels, and returns the decoded data and channels.
+    fn finish(self) -> DecodeOutput {
+        let channels =
+            self.channels.into_iter().map(|state| Self::read_channel(self.cfg, state)).collect();
+        DecodeOutput { data: self.data, channels }
+    }
+
+    /// Reads the batches of a channel, if it is ready.
+    fn read_channel(cfg: &RollupConfig, state: ChannelState) -> ChannelOutput {
+        let ChannelState { channel, open_block, errors } = state;
+        let mut output = ChannelOutput {
+            id: hex::encode_prefixed(channel.id()),
+            open_block: open_block.map(|b| b.number),
+            frames: channel.len(),
+            ready: channel.is_ready(),
+            frame_errors: errors,
+            batches: Vec::new(),
+            error: None,
+        };
+
+        let Some(data) = channel.frame_data().filter(|_| output.ready) else {
+            output.error = Some("Channel is missing frames".to_string());
+            return output;
+        };
+
+        // Without an L1 origin, the channel may have been posted after the Fjord hardfork, so
+        // allow the larger channel size.
+        let max_rlp_bytes = open_block.map_or(MAX_RLP_BYTES_PER_CHANNEL_FJORD, |b| {
+            cfg.max_rlp_bytes_per_channel(b.timestamp)
+        });
+        let mut reader = BatchReader::new(data, max_rlp_bytes as usize);
+        loop {
+            match reader.try_next_batch(cfg) {
+                Ok(Some(batch)) => output.batches.push(batch.into()),
+                Ok(None) => break,
+                Err(e) => {
+                    output.error = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        output
+    }
+}
+
+/// The output of the [DecodeCommand].
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DecodeOutput {
+    /// The decoded batcher data.
+    data: Vec<DataOutput>,
+    /// The channels assembled from the frames of the batcher data.
+    channels: Vec<ChannelOutput>,
+}
+
+/// The kind of batcher data.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum DataKind {
+    /// Raw batcher transaction data.
+    Tx,
+    /// A raw blob.
+    Blob,
+    /// Batcher data fetched from an L1 block.
+    L1,
+}
+
+/// Batcher data, decoded into frames.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DataOutput {
+    /// The kind of the data.
+    kind: DataKind,
+    /// The number of the L1 block the data was included in, if it was fetched from L1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    l1_block: Option<u64>,
+    /// The index of the data in the inputs or the L1 block.
+    index: usize,
+    /// The frames of the data.
+    frames: Vec<FrameOutput>,
+    /// The error retrieving the data or parsing its frames, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl DataOutput {
+    /// Creates a new [DataOutput] without frames.
+    fn new(kind: DataKind, l1_block: Option<BlockInfo>, index: usize) -> Self {
+        Self { kind, l1_block: l1_block.map(|b| b.number), index, frames: Vec::new(), error: None }
+    }
+}
+
+/// A frame of batcher data.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FrameOutput {
+    /// The id of the channel of the frame.
+    channel_id: String,
+    /// The number of the frame.
+    number: u16,
+    /// The length of the frame data.
+    data_length: usize,
+    /// Whether the frame is the last frame of its channel.
+    is_last: bool,
+}
+
+impl From<&Frame> for FrameOutput {
+    fn from(frame: &Frame) -> Self {
+        Self {
+            channel_id: hex::encode_prefixed(frame.id),
+            number: frame.number,
+            data_length: frame.data.len(),
+            is_last: frame.is_last,
+        }
+    }
+}
+
+/// A channel and the batches read from it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChannelOutput {
+    /// The id of the channel.
+    id: String,
+    /// The number of the L1 block the channel was opened in, if the data was fetched from L1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    open_block: Option<u64>,
+    /// The number of frames of the channel.
+    frames: usize,
+    /// Whether all frames of the channel were received.
+    ready: bool,
+    /// The errors of the frames that could not be added to the channel.
+    frame_errors: Vec<String>,
+    /// The batches read from the channel.
+    batches: Vec<BatchOutput>,
+    /// The error reading the batches of the channel, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// A batch read from a channel.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum BatchOutput {
+    /// A single batch.
+    #[serde(rename_all = "camelCase")]
+    Single {
+        /// The parent hash of the block.
+        parent_hash: B256,
+        /// The number of the L1 origin of the block.
+        epoch_num: u64,
+        /// The hash of the L1 origin of the block.
+        epoch_hash: B256,
+        /// The timestamp of the block.
+        timestamp: u64,
+        /// The transactions of the block.
+        transactions: Vec<Bytes>,
+    },
+    /// A span batch.
+    #[serde(rename_all = "camelCase")]
+    Span {
+        /// The first 20 bytes of the parent hash of the first block.
+        parent_check: FixedBytes<20>,
+        /// The first 20 bytes of the hash of the L1 origin of the last block.
+        l1_origin_check: FixedBytes<20>,
+        /// The blocks of the span.
+        blocks: Vec<SpanBlockOutput>,
+    },
+}
+
+/// A block of a span batch.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpanBlockOutput {
+    /// The number of the L1 origin of the block.
+    epoch_num: u64,
+    /// The timestamp of the block.
+    timestamp: u64,
+    /// The transactions of the block.
+    transactions: Vec<Bytes>,
+}
+
+impl From<Batch> for BatchOutput {
+    fn from(batch: Batch) -> Self {
+        match batch {
+            Batch::Single(batch) => Self::Single {
+                parent_hash: batch.parent_hash,
+                epoch_num: batch.epoch_num,
+                epoch_hash: batch.epoch_hash,
+                timestamp: batch.timestamp,
+                transactions: batch.transactions,
+            },
+            Batch::Span(batch) => Self::Span {
+                parent_check: batch.parent_check,
+                l1_origin_check: batch.l1_origin_check,
+                blocks: batch
+                    .batches
+                    .into_iter()
+                    .map(|b| SpanBlockOutput {
+                        epoch_num: b.epoch_num,
+                        timestamp: b.timestamp,
+                        transactions: b.transactions,
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::DERIVATION_VERSION_0;
+
+    /// Returns the compressed data of a channel with a single span batch, taken from the test data
+    /// of `kona-protocol`, split into two frames and encoded as batcher data.
+    fn batcher_data() -> [Vec<u8>; 2] {
+        let raw = include_str!("../../../../crates/protocol/protocol/testdata/batch.hex");
+        let data = hex::decode(raw.trim()).unwrap();
+        let (first, second) = data.split_at(data.len() / 2);
+        [(0, first, false), (1, second, true)].map(|(number, data, is_last)| {
+            let frame = Frame { id: [0xaa; 16], number, data: data.to_vec(), is_last };
+            [&[DERIVATION_VERSION_0][..], &frame.encode()].concat()
+        })
+    }
+
+    #[test]
+    fn test_decode_channel() {
+        let cfg = RollupConfig::default();
+        let [first, second] = batcher_data();
+        let mut decoder = ChannelDecoder::new(&cfg);
+        decoder.add_data(DataKind::Tx, None, 0, &first);
+        decoder.add_data(DataKind::Tx, None, 1, &second);
+
+        let output = decoder.finish();
+        assert_eq!(output.data.len(), 2);
+        assert!(output.data.iter().all(|d| d.frames.len() == 1 && d.error.is_none()));
+        assert_eq!(output.channels.len(), 1);
+
+        let channel = &output.channels[0];
+        assert!(channel.ready);
+        assert_eq!(channel.frames, 2);
+        assert!(channel.error.is_none());
+        assert_eq!(channel.batches.len(), 1);
+        assert!(
+            matches!(&channel.batches[0], BatchOutput::Span { blocks, .. } if !blocks.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_decode_incomplete_channel() {
+        let cfg = RollupConfig::default();
+        let [first, _] = batcher_data();
+        let mut decoder = ChannelDecoder::new(&cfg);
+        decoder.add_data(DataKind::Tx, None, 0, &first);
+        decoder.add_data(DataKind::Tx, None, 1, &first);
+
+        let output = decoder.finish();
+        let channel = &output.channels[0];
+        assert!(!channel.ready);
+        assert!(channel.batches.is_empty());
+        assert_eq!(channel.frame_errors, ["Failed to add frame 0: Frame number 0 already exists"]);
+        assert_eq!(channel.error.as_deref(), Some("Channel is missing frames"));
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let cfg = RollupConfig::default();
+        let mut decoder = ChannelDecoder::new(&cfg);
+        decoder.add_data(DataKind::Tx, None, 0, &[0x01, 0x02]);
+
+        let output = decoder.finish();
+        assert!(output.channels.is_empty());
+        assert!(output.data[0].frames.is_empty());
+        assert_eq!(output.data[0].error.as_deref(), Some("Unsupported derivation version"));
+    }
+
+    #[test]
+    fn test_decode_missing_l1_block() {
+        let cfg = RollupConfig::default();
+        let mut decoder = ChannelDecoder::new(&cfg);
+        decoder.add_error(DataKind::L1, Some(10), 0, "Block not found".to_string());
+        decoder.add_data(DataKind::Tx, None, 0, &batcher_data()[0]);
+
+        let output = decoder.finish();
+        assert_eq!(output.data.len(), 2);
+        assert_eq!(output.data[0].l1_block, Some(10));
+        assert_eq!(output.data[0].error.as_deref(), Some("Block not found"));
+        assert_eq!(output.channels.len(), 1);
+    }
+}
@@ -8,3 +8,6 @@ pub use net::NetCommand;
 
 mod registry;
 pub use registry::RegistryCommand;
+
+mod decode;
+pub use decode::DecodeCommand;
@@ -2,16 +2,20 @@
 
 #![allow(missing_docs)]
 
-use alloy_consensus::{Header, Sealable};
-use alloy_primitives::{B256, Bytes, address, b256, hex};
+use alloy_consensus::{EMPTY_ROOT_HASH, Header, Sealable};
+use alloy_primitives::{Address, B256, Bytes, U256, address, b256, hex};
 use alloy_rlp::Decodable;
 use alloy_rpc_types_engine::PayloadAttributes;
-use criterion::{Bencher, Criterion, criterion_group, criterion_main};
-use kona_executor::{StatelessL2BlockExecutor, TrieDBProvider};
+use criterion::{BatchSize, Bencher, Criterion, criterion_group, criterion_main};
+use kona_executor::{NoopTrieDBProvider, StatelessL2BlockExecutor, TrieDB, TrieDBProvider};
 use kona_genesis::{HardForkConfig, OP_MAINNET_BASE_FEE_CONFIG, RollupConfig};
 use kona_mpt::{NoopTrieHinter, TrieNode, TrieProvider};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use pprof::criterion::{Output, PProfProfiler};
+use revm::{
+    db::BundleState,
+    primitives::{AccountInfo, Bytecode, KECCAK_EMPTY},
+};
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -190,9 +194,50 @@ fn execution(c: &mut Criterion) {
     });
 }
 
+/// Returns a [BundleState] that creates `accounts` accounts, each with `slots` storage slots.
+fn new_accounts_bundle(accounts: u64, slots: u64) -> BundleState {
+    let state = (0..accounts).map(|i| {
+        let info =
+            AccountInfo { balance: U256::from(i), nonce: 1, code_hash: KECCAK_EMPTY, code: None };
+        let storage =
+            (0..slots).map(|j| (U256::from(j), (U256::ZERO, U256::from(i + j + 1)))).collect();
+        (Address::from_word(U256::from(i).into()), None, Some(info), storage)
+    });
+    type Reverts = Vec<Vec<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>>;
+    BundleState::new(state, Reverts::new(), Vec::<(B256, Bytecode)>::new())
+}
+
+/// Benches the state root computation of [TrieDB] over freshly created accounts.
+///
+/// The storage tries of the accounts are only blinded in parallel with the `std` feature. Compare
+/// both with `cargo bench --bench execution -- state_root`, run with and without
+/// `--features std`.
+fn state_root(c: &mut Criterion) {
+    let mut g = c.benchmark_group("state_root");
+    g.sample_size(10);
+
+    for (accounts, slots) in [(16, 1024), (256, 64), (1024, 4)] {
+        let bundle = new_accounts_bundle(accounts, slots);
+        g.bench_function(format!("State root - {accounts} accounts x {slots} slots"), |b| {
+            b.iter_batched(
+                || {
+                    TrieDB::new(
+                        EMPTY_ROOT_HASH,
+                        Header::default().seal_slow(),
+                        NoopTrieDBProvider,
+                        NoopTrieHinter,
+                    )
+                },
+                |mut trie_db| trie_db.state_root(&bundle).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
 criterion_group! {
     name = execution_benches;
     config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
-    targets = execution
+    targets = execution, state_root
 }
 criterion_main!(execution_benches);
@@ -0,0 +1,94 @@
+//! Contains the [TrieDBCacheStats] of a [TrieDB], and the [FetchCounter] used to collect them.
+//!
+//! [TrieDB]: crate::TrieDB
+
+use alloy_primitives::B256;
+use core::cell::Cell;
+use kona_mpt::{TrieNode, TrieProvider};
+
+/// Counters of the lookups that a [TrieDB] served from its in-memory state, rather than by
+/// fetching preimages from its [TrieDBProvider].
+///
+/// Only lookups that reach the [TrieDB] are counted. Accounts and storage slots held in the
+/// cache of the [State] wrapping the [TrieDB] are served without a lookup.
+///
+/// [TrieDB]: crate::TrieDB
+/// [TrieDBProvider]: crate::TrieDBProvider
+/// [State]: revm::State
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TrieDBCacheStats {
+    /// The number of account lookups served from the opened trie.
+    pub account_hits: u64,
+    /// The number of account lookups that fetched trie nodes.
+    pub account_misses: u64,
+    /// The number of storage slot lookups served from the opened storage tries.
+    pub storage_hits: u64,
+    /// The number of storage slot lookups that fetched trie nodes.
+    pub storage_misses: u64,
+    /// The number of bytecode lookups served from the bytecode cache.
+    pub code_hits: u64,
+    /// The number of bytecode lookups that fetched the bytecode.
+    pub code_misses: u64,
+    /// The total number of trie nodes fetched, including while updating the tries.
+    pub trie_nodes_fetched: u64,
+}
+
+impl TrieDBCacheStats {
+    /// Returns the fraction of the account, storage slot and bytecode lookups that were served
+    /// without fetching any preimage, or `None` if there were no lookups.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let hits = self.account_hits + self.storage_hits + self.code_hits;
+        let lookups = hits + self.account_misses + self.storage_misses + self.code_misses;
+        (lookups > 0).then(|| hits as f64 / lookups as f64)
+    }
+
+    /// Records an account lookup that fetched `fetched` trie nodes.
+    pub(crate) const fn record_account(&mut self, fetched: u64) {
+        if fetched == 0 {
+            self.account_hits += 1;
+        } else {
+            self.account_misses += 1;
+        }
+        self.trie_nodes_fetched += fetched;
+    }
+
+    /// Records a storage slot lookup that fetched `fetched` trie nodes.
+    pub(crate) const fn record_storage(&mut self, fetched: u64) {
+        if fetched == 0 {
+            self.storage_hits += 1;
+        } else {
+            self.storage_misses += 1;
+        }
+        self.trie_nodes_fetched += fetched;
+    }
+}
+
+/// A [TrieProvider] that counts the trie nodes fetched through it from the inner provider.
+#[derive(Debug)]
+pub(crate) struct FetchCounter<'a, F> {
+    /// The inner provider.
+    inner: &'a F,
+    /// The number of trie nodes fetched.
+    fetched: Cell<u64>,
+}
+
+impl<'a, F: TrieProvider> FetchCounter<'a, F> {
+    /// Creates a new [FetchCounter] wrapping the given provider.
+    pub(crate) const fn new(inner: &'a F) -> Self {
+        Self { inner, fetched: Cell::new(0) }
+    }
+
+    /// Returns the number of trie nodes fetched so far.
+    pub(crate) fn fetched(&self) -> u64 {
+        self.fetched.get()
+    }
+}
+
+impl<F: TrieProvider> TrieProvider for FetchCounter<'_, F> {
+    type Error = F::Error;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        self.fetched.set(self.fetched.get() + 1);
+        self.inner.trie_node_by_hash(key)
+    }
+}
@@ -10,13 +10,20 @@ use alloy_trie::TrieAccount;
 use kona_mpt::{Nibbles, TrieHinter, TrieNode, TrieNodeError};
 use revm::{
     Database,
-    db::{BundleState, states::StorageSlot},
+    db::BundleState,
     primitives::{AccountInfo, BLOCK_HASH_HISTORY, Bytecode, HashMap},
 };
 
 mod traits;
 pub use traits::{NoopTrieDBProvider, TrieDBProvider};
 
+mod cache;
+use cache::FetchCounter;
+pub use cache::TrieDBCacheStats;
+
+mod root;
+use root::update_storage_tries;
+
 /// A Trie DB that caches open state in-memory.
 ///
 /// When accounts that don't already exist within the cached [TrieNode] are queried, the database
@@ -30,10 +37,10 @@ pub use traits::{NoopTrieDBProvider, TrieDBProvider};
 /// **Behavior**:
 /// - When an account is queried and the trie path has not already been opened by [Self::basic], we
 ///   fall through to the `PreimageFetcher` to fetch the preimages of the trie nodes on the path to
-///   the account. After it has been fetched, the path will be cached until the next call to
-///   [Self::state_root].
+///   the account. After it has been fetched, the path stays open across blocks, until the trie is
+///   re-blinded by [Self::flush].
 /// - When querying for the code hash of an account, the `CodeHashFetcher` is consulted to fetch the
-///   code hash of the account.
+///   code hash of the account. Fetched bytecode is cached until the next call to [Self::flush].
 /// - When a [BundleState] changeset is committed to the parent [State] database, the changes are
 ///   first applied to the [State]'s cache, then the trie hash is recomputed with
 ///   [Self::state_root].
@@ -85,6 +92,10 @@ where
     storage_roots: HashMap<Address, TrieNode>,
     /// The parent block hash of the current block.
     parent_block_header: Sealed<Header>,
+    /// Bytecode fetched from the [TrieDBProvider], keyed by code hash.
+    bytecode_cache: HashMap<B256, Bytecode>,
+    /// The cache statistics since they were last taken.
+    cache_stats: TrieDBCacheStats,
     /// The [TrieDBProvider]
     pub fetcher: F,
     /// The [TrieHinter]
@@ -102,6 +113,8 @@ where
             root_node: TrieNode::new_blinded(root),
             storage_roots: Default::default(),
             parent_block_header,
+            bytecode_cache: Default::default(),
+            cache_stats: Default::default(),
             fetcher,
             hinter,
         }
@@ -136,6 +149,27 @@ where
         self.parent_block_header = parent_block_header;
     }
 
+    /// Returns the [TrieDBCacheStats] since they were last taken.
+    pub const fn cache_stats(&self) -> &TrieDBCacheStats {
+        &self.cache_stats
+    }
+
+    /// Takes the [TrieDBCacheStats], resetting them.
+    pub fn take_cache_stats(&mut self) -> TrieDBCacheStats {
+        core::mem::take(&mut self.cache_stats)
+    }
+
+    /// Discards every opened trie node, storage root and cached bytecode, re-blinding the trie at
+    /// the state root of the parent block header.
+    ///
+    /// This must be called after changing the parent block header to a block that is not the
+    /// last one executed on top of the trie, or after a failed state root computation.
+    pub fn flush(&mut self) {
+        self.root_node = TrieNode::new_blinded(self.parent_block_header.state_root);
+        self.storage_roots.clear();
+        self.bytecode_cache.clear();
+    }
+
     /// Applies a [BundleState] changeset to the [TrieNode] and recomputes the state root hash.
     ///
     /// ## Takes
@@ -183,8 +217,10 @@ where
 
         // Fetch the account from the trie.
         let hashed_address_nibbles = Nibbles::unpack(keccak256(address.as_slice()));
-        let Some(trie_account_rlp) = self.root_node.open(&hashed_address_nibbles, &self.fetcher)?
-        else {
+        let fetcher = FetchCounter::new(&self.fetcher);
+        let trie_account_rlp = self.root_node.open(&hashed_address_nibbles, &fetcher)?;
+        self.cache_stats.record_account(fetcher.fetched());
+        let Some(trie_account_rlp) = trie_account_rlp else {
             return Ok(None);
         };
 
@@ -206,110 +242,62 @@ where
     fn update_accounts(&mut self, bundle: &BundleState) -> TrieDBResult<()> {
         // Sort the storage keys prior to applying the changeset, to ensure that the order of
         // application is deterministic between runs.
-        let mut sorted_state =
-            bundle.state().iter().map(|(k, v)| (k, keccak256(*k), v)).collect::<Vec<_>>();
+        let mut sorted_state = bundle
+            .state()
+            .iter()
+            .filter(|(_, v)| !v.status.is_not_modified())
+            .map(|(k, v)| (k, keccak256(*k), v))
+            .collect::<Vec<_>>();
         sorted_state.sort_by_key(|(_, hashed_addr, _)| *hashed_addr);
 
-        for (address, hashed_address, bundle_account) in sorted_state {
-            if bundle_account.status.is_not_modified() {
-                continue;
-            }
+        // Apply the storage changes of the accounts that were not destroyed, and recompute their
+        // storage roots. The storage tries are independent of each other, so they are updated
+        // together.
+        let fetcher = FetchCounter::new(&self.fetcher);
+        let (addresses, mut updates): (Vec<_>, Vec<_>) = sorted_state
+            .iter()
+            .filter(|(_, _, bundle_account)| !bundle_account.was_destroyed())
+            .map(|(address, _, bundle_account)| {
+                let storage_root = self
+                    .storage_roots
+                    .remove(*address)
+                    .unwrap_or_else(|| TrieNode::new_blinded(EMPTY_ROOT_HASH));
+                (**address, (storage_root, &bundle_account.storage))
+            })
+            .unzip();
+        let storage_roots = update_storage_tries(&mut updates, &fetcher, &self.hinter);
+        self.storage_roots
+            .extend(addresses.into_iter().zip(updates).map(|(address, (trie, _))| (address, trie)));
+        let mut storage_roots = storage_roots?.into_iter();
 
+        for (address, hashed_address, bundle_account) in sorted_state {
             // Compute the path to the account in the trie.
             let account_path = Nibbles::unpack(hashed_address.as_slice());
 
             // If the account was destroyed, delete it from the trie.
             if bundle_account.was_destroyed() {
-                self.root_node.delete(&account_path, &self.fetcher, &self.hinter)?;
+                self.root_node.delete(&account_path, &fetcher, &self.hinter)?;
                 self.storage_roots.remove(address);
                 continue;
             }
 
             let account_info =
                 bundle_account.account_info().ok_or(TrieDBError::MissingAccountInfo)?;
-            let mut trie_account = TrieAccount {
+            let trie_account = TrieAccount {
                 balance: account_info.balance,
                 nonce: account_info.nonce,
                 code_hash: account_info.code_hash,
-                ..Default::default()
+                storage_root: storage_roots.next().expect("Storage root was recomputed"),
             };
 
-            // Update the account's storage root
-            let acc_storage_root = self
-                .storage_roots
-                .entry(*address)
-                .or_insert_with(|| TrieNode::new_blinded(EMPTY_ROOT_HASH));
-
-            // Sort the hashed storage keys prior to applying the changeset, to ensure that the
-            // order of application is deterministic between runs.
-            let mut sorted_storage = bundle_account
-                .storage
-                .iter()
-                .map(|(k, v)| (keccak256(k.to_be_bytes::<32>()), v))
-                .collect::<Vec<_>>();
-            sorted_storage.sort_by_key(|(slot, _)| *slot);
-
-            sorted_storage.into_iter().try_for_each(|(hashed_key, value)| {
-                Self::change_storage(
-                    acc_storage_root,
-                    hashed_key,
-                    value,
-                    &self.fetcher,
-                    &self.hinter,
-                )
-            })?;
-
-            // Recompute the account storage root.
-            let root = acc_storage_root.blind();
-            trie_account.storage_root = root;
-
             // RLP encode the trie account for insertion.
             let mut account_buf = Vec::with_capacity(trie_account.length());
             trie_account.encode(&mut account_buf);
 
             // Insert or update the account in the trie.
-            self.root_node.insert(&account_path, account_buf.into(), &self.fetcher)?;
-        }
-
-        Ok(())
-    }
-
-    /// Modifies a storage slot of an account in the Merkle Patricia Trie.
-    ///
-    /// ## Takes
-    /// - `storage_root`: The storage root of the account.
-    /// - `hashed_key`: The hashed storage slot key.
-    /// - `value`: The new value of the storage slot.
-    /// - `fetcher`: The trie node fetcher.
-    /// - `hinter`: The trie hinter.
-    ///
-    /// ## Returns
-    /// - `Ok(())` if the storage slot was successfully modified.
-    /// - `Err(_)` if the storage slot could not be modified.
-    fn change_storage(
-        storage_root: &mut TrieNode,
-        hashed_key: B256,
-        value: &StorageSlot,
-        fetcher: &F,
-        hinter: &H,
-    ) -> TrieDBResult<()> {
-        if !value.is_changed() {
-            return Ok(());
-        }
-
-        // RLP encode the storage slot value.
-        let mut rlp_buf = Vec::with_capacity(value.present_value.length());
-        value.present_value.encode(&mut rlp_buf);
-
-        // Insert or update the storage slot in the trie.
-        let hashed_slot_key = Nibbles::unpack(hashed_key.as_slice());
-        if value.present_value.is_zero() {
-            // If the storage slot is being set to zero, prune it from the trie.
-            storage_root.delete(&hashed_slot_key, fetcher, hinter)?;
-        } else {
-            // Otherwise, update the storage slot.
-            storage_root.insert(&hashed_slot_key, rlp_buf.into(), fetcher)?;
+            self.root_node.insert(&account_path, account_buf.into(), &fetcher)?;
         }
+        self.cache_stats.trie_nodes_fetched += fetcher.fetched();
 
         Ok(())
     }
@@ -345,10 +333,19 @@ where
     }
 
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
-        self.fetcher
+        if let Some(code) = self.bytecode_cache.get(&code_hash) {
+            self.cache_stats.code_hits += 1;
+            return Ok(code.clone());
+        }
+
+        let code = self
+            .fetcher
             .bytecode_by_hash(code_hash)
             .map(Bytecode::new_raw)
-            .map_err(|e| TrieDBError::Provider(e.to_string()))
+            .map_err(|e| TrieDBError::Provider(e.to_string()))?;
+        self.cache_stats.code_misses += 1;
+        self.bytecode_cache.insert(code_hash, code.clone());
+        Ok(code)
     }
 
     fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
@@ -368,7 +365,10 @@ where
             Some(storage_root) => {
                 // Fetch the storage slot from the trie.
                 let hashed_slot_key = keccak256(index.to_be_bytes::<32>().as_slice());
-                match storage_root.open(&Nibbles::unpack(hashed_slot_key), &self.fetcher)? {
+                let fetcher = FetchCounter::new(&self.fetcher);
+                let slot_value = storage_root.open(&Nibbles::unpack(hashed_slot_key), &fetcher)?;
+                self.cache_stats.record_storage(fetcher.fetched());
+                match slot_value {
                     Some(slot_value) => {
                         // Decode the storage slot value.
                         let int_slot = U256::decode(&mut slot_value.as_ref())
@@ -412,7 +412,10 @@ mod tests {
     use super::*;
     use alloy_consensus::Sealable;
     use alloy_primitives::b256;
+    use alloy_trie::root::{state_root_unhashed, storage_root_unhashed};
     use kona_mpt::NoopTrieHinter;
+    use revm::primitives::KECCAK_EMPTY;
+    use std::collections::BTreeMap;
 
     fn new_test_db() -> TrieDB<NoopTrieDBProvider, NoopTrieHinter> {
         TrieDB::new(
@@ -474,4 +477,78 @@ mod tests {
             b256!("78dec18c6d7da925bbe773c315653cdc70f6444ed6c1de9ac30bdb36cff74c3b")
         );
     }
+
+    #[test]
+    fn test_state_root_matches_reference() {
+        type Reverts = Vec<Vec<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>>;
+        let info = |i: u64| AccountInfo {
+            balance: U256::from(i),
+            nonce: 1,
+            code_hash: KECCAK_EMPTY,
+            code: None,
+        };
+        let address = |i: u64| Address::from_word(U256::from(i).into());
+
+        // Create accounts with increasingly large storage, and some without storage.
+        let mut storage = BTreeMap::<Address, BTreeMap<U256, U256>>::new();
+        for i in 0..32u64 {
+            let slots = (0..i * 4).map(|j| (U256::from(j), U256::from(i + j + 1)));
+            storage.insert(address(i), slots.collect());
+        }
+        let bundle = BundleState::new(
+            storage.iter().enumerate().map(|(i, (address, slots))| {
+                let slots =
+                    slots.iter().map(|(slot, value)| (*slot, (U256::ZERO, *value))).collect();
+                (*address, None, Some(info(i as u64)), slots)
+            }),
+            Reverts::new(),
+            Vec::new(),
+        );
+
+        let reference_root = |storage: &BTreeMap<Address, BTreeMap<U256, U256>>| {
+            state_root_unhashed(storage.iter().enumerate().map(|(i, (address, slots))| {
+                let storage_root = storage_root_unhashed(
+                    slots.iter().map(|(slot, value)| (B256::from(*slot), *value)),
+                );
+                let info = info(i as u64);
+                let account = TrieAccount {
+                    nonce: info.nonce,
+                    balance: info.balance,
+                    storage_root,
+                    code_hash: info.code_hash,
+                };
+                (*address, account)
+            }))
+        };
+
+        let mut db = TrieDB::new(
+            EMPTY_ROOT_HASH,
+            Header::default().seal_slow(),
+            NoopTrieDBProvider,
+            NoopTrieHinter,
+        );
+        assert_eq!(db.state_root(&bundle).unwrap(), reference_root(&storage));
+
+        // Clear every other slot, on top of the opened tries.
+        let cleared = storage
+            .iter_mut()
+            .map(|(address, slots)| {
+                let cleared = slots
+                    .iter()
+                    .step_by(2)
+                    .map(|(slot, value)| (*slot, (*value, U256::ZERO)))
+                    .collect::<HashMap<_, _>>();
+                slots.retain(|slot, _| !cleared.contains_key(slot));
+                (*address, cleared)
+            })
+            .collect::<Vec<_>>();
+        let bundle = BundleState::new(
+            cleared.into_iter().enumerate().map(|(i, (address, slots))| {
+                (address, Some(info(i as u64)), Some(info(i as u64)), slots)
+            }),
+            Reverts::new(),
+            Vec::new(),
+        );
+        assert_eq!(db.state_root(&bundle).unwrap(), reference_root(&storage));
+    }
 }
@@ -0,0 +1,281 @@
+//! Contains the computation of the storage roots of the accounts updated by a [BundleState].
+//!
+//! With the `std` feature, the independent storage tries are updated and blinded in parallel.
+//! Otherwise, they are updated and blinded one after the other. Both produce the same roots.
+//!
+//! [BundleState]: revm::db::BundleState
+
+use crate::errors::TrieDBResult;
+use alloc::vec::Vec;
+use alloy_primitives::{B256, keccak256};
+use alloy_rlp::Encodable;
+use kona_mpt::{Nibbles, TrieHinter, TrieNode, TrieProvider};
+use revm::db::states::{StorageSlot, StorageWithOriginalValues};
+
+/// The storage trie of an account, along with the storage changes to apply to it.
+pub(crate) type StorageTrieUpdate<'a> = (TrieNode, &'a StorageWithOriginalValues);
+
+/// Applies the storage changes to each of the given storage tries, and blinds them, returning
+/// their roots in the same order.
+#[cfg(not(feature = "std"))]
+pub(crate) fn update_storage_tries<F, H>(
+    updates: &mut [StorageTrieUpdate<'_>],
+    fetcher: &F,
+    hinter: &H,
+) -> TrieDBResult<Vec<B256>>
+where
+    F: TrieProvider,
+    H: TrieHinter,
+{
+    updates
+        .iter_mut()
+        .map(|(trie, storage)| update_storage_trie(trie, storage, fetcher, hinter))
+        .collect()
+}
+
+/// Applies the storage changes to each of the given storage tries, and blinds them, on a pool of
+/// scoped threads, returning their roots in the same order.
+///
+/// The workers cannot fetch trie nodes, as the provider is not shared across threads. A worker
+/// applies the changes to a copy of its trie, which is discarded if a trie node had to be
+/// fetched, and the changes are then applied again, on the calling thread, with the given
+/// provider and hinter.
+#[cfg(feature = "std")]
+pub(crate) fn update_storage_tries<F, H>(
+    updates: &mut [StorageTrieUpdate<'_>],
+    fetcher: &F,
+    hinter: &H,
+) -> TrieDBResult<Vec<B256>>
+where
+    F: TrieProvider,
+    H: TrieHinter,
+{
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use kona_mpt::NoopTrieHinter;
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get()).min(updates.len());
+    if threads <= 1 {
+        return updates
+            .iter_mut()
+            .map(|(trie, storage)| update_storage_trie(trie, storage, fetcher, hinter))
+            .collect();
+    }
+
+    // Each worker picks up the next trie that no other worker has taken, so that a single large
+    // storage trie does not hold up the tries queued behind it.
+    let next = AtomicUsize::new(0);
+    let shared = &*updates;
+    let updated = std::thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut updated = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((trie, storage)) = shared.get(index) else { break updated };
+                        let mut trie = trie.clone();
+                        let root = update_storage_trie(
+                            &mut trie,
+                            storage,
+                            &UnavailableTrieProvider,
+                            &NoopTrieHinter,
+                        );
+                        updated.push((index, root.ok().map(|root| (trie, root))));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Storage root worker panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    let mut roots = alloc::vec![B256::ZERO; updates.len()];
+    let mut missing = Vec::new();
+    for (index, result) in updated {
+        match result {
+            Some((trie, root)) => {
+                updates[index].0 = trie;
+                roots[index] = root;
+            }
+            None => missing.push(index),
+        }
+    }
+
+    // Apply the changes that required trie nodes to be fetched in a deterministic order.
+    missing.sort_unstable();
+    for index in missing {
+        let (trie, storage) = &mut updates[index];
+        roots[index] = update_storage_trie(trie, storage, fetcher, hinter)?;
+    }
+    Ok(roots)
+}
+
+/// A [TrieProvider] that fails to fetch any trie node, for the workers that update storage tries
+/// without access to the provider of the [TrieDB].
+///
+/// [TrieDB]: crate::TrieDB
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+struct UnavailableTrieProvider;
+
+#[cfg(feature = "std")]
+impl TrieProvider for UnavailableTrieProvider {
+    type Error = &'static str;
+
+    fn trie_node_by_hash(&self, _: B256) -> Result<TrieNode, Self::Error> {
+        Err("Trie nodes cannot be fetched from a storage root worker")
+    }
+}
+
+/// Applies the storage changes to the given storage trie, and blinds it, returning its root.
+fn update_storage_trie<F, H>(
+    trie: &mut TrieNode,
+    storage: &StorageWithOriginalValues,
+    fetcher: &F,
+    hinter: &H,
+) -> TrieDBResult<B256>
+where
+    F: TrieProvider,
+    H: TrieHinter,
+{
+    // Sort the hashed storage keys prior to applying the changeset, to ensure that the order of
+    // application is deterministic between runs.
+    let mut sorted_storage =
+        storage.iter().map(|(k, v)| (keccak256(k.to_be_bytes::<32>()), v)).collect::<Vec<_>>();
+    sorted_storage.sort_by_key(|(slot, _)| *slot);
+
+    sorted_storage.into_iter().try_for_each(|(hashed_key, value)| {
+        change_storage(trie, hashed_key, value, fetcher, hinter)
+    })?;
+    Ok(trie.blind())
+}
+
+/// Modifies a storage slot of an account in the Merkle Patricia Trie.
+///
+/// ## Takes
+/// - `storage_root`: The storage root of the account.
+/// - `hashed_key`: The hashed storage slot key.
+/// - `value`: The new value of the storage slot.
+/// - `fetcher`: The trie node fetcher.
+/// - `hinter`: The trie hinter.
+///
+/// ## Returns
+/// - `Ok(())` if the storage slot was successfully modified.
+/// - `Err(_)` if the storage slot could not be modified.
+fn change_storage<F, H>(
+    storage_root: &mut TrieNode,
+    hashed_key: B256,
+    value: &StorageSlot,
+    fetcher: &F,
+    hinter: &H,
+) -> TrieDBResult<()>
+where
+    F: TrieProvider,
+    H: TrieHinter,
+{
+    if !value.is_changed() {
+        return Ok(());
+    }
+
+    // RLP encode the storage slot value.
+    let mut rlp_buf = Vec::with_capacity(value.present_value.length());
+    value.present_value.encode(&mut rlp_buf);
+
+    // Insert or update the storage slot in the trie.
+    let hashed_slot_key = Nibbles::unpack(hashed_key.as_slice());
+    if value.present_value.is_zero() {
+        // If the storage slot is being set to zero, prune it from the trie.
+        storage_root.delete(&hashed_slot_key, fetcher, hinter)?;
+    } else {
+        // Otherwise, update the storage slot.
+        storage_root.insert(&hashed_slot_key, rlp_buf.into(), fetcher)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_trie::root::storage_root_unhashed;
+    use kona_mpt::NoopTrieHinter;
+    use revm::primitives::HashMap;
+    use std::collections::BTreeMap;
+
+    /// A [TrieProvider] that serves fully opened storage tries by their roots.
+    struct StorageTrieProvider(HashMap<B256, TrieNode>);
+
+    impl TrieProvider for StorageTrieProvider {
+        type Error = &'static str;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            self.0.get(&key).cloned().ok_or("Unknown trie node")
+        }
+    }
+
+    #[test]
+    fn test_update_storage_tries_matches_sequential_path() {
+        let mut provider = StorageTrieProvider(HashMap::default());
+        let mut tries = Vec::new();
+        let mut changes = Vec::new();
+        let mut expected = Vec::new();
+        for i in 0..16u64 {
+            // Fill the storage with increasingly many slots.
+            let slots = (0..i * 8).map(|j| (U256::from(j), U256::from(j + 1))).collect::<Vec<_>>();
+            let storage = slots
+                .iter()
+                .map(|(slot, value)| (*slot, StorageSlot::new_changed(U256::ZERO, *value)))
+                .collect();
+            let mut trie = TrieNode::Empty;
+            update_storage_trie(&mut trie, &storage, &provider, &NoopTrieHinter).unwrap();
+
+            // Half of the tries are blinded, so that their trie nodes must be fetched.
+            if i % 2 == 1 {
+                let root = trie.blind();
+                provider.0.insert(root, trie);
+                trie = TrieNode::new_blinded(root);
+            }
+            tries.push(trie);
+
+            // Clear every third slot, update the others, and add new slots.
+            let mut storage = BTreeMap::from_iter(slots);
+            let mut change = StorageWithOriginalValues::default();
+            for (slot, value) in storage.iter_mut() {
+                let present =
+                    if slot.to::<u64>() % 3 == 0 { U256::ZERO } else { *value * U256::from(2) };
+                change.insert(*slot, StorageSlot::new_changed(*value, present));
+                *value = present;
+            }
+            for j in i * 8..i * 8 + 4 {
+                change.insert(U256::from(j), StorageSlot::new_changed(U256::ZERO, U256::from(j)));
+                storage.insert(U256::from(j), U256::from(j));
+            }
+            changes.push(change);
+            expected.push(storage_root_unhashed(
+                storage
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(slot, value)| (B256::from(slot), value)),
+            ));
+        }
+
+        let mut sequential = tries.clone();
+        let sequential_roots = sequential
+            .iter_mut()
+            .zip(&changes)
+            .map(|(trie, storage)| update_storage_trie(trie, storage, &provider, &NoopTrieHinter))
+            .collect::<TrieDBResult<Vec<_>>>()
+            .unwrap();
+
+        let mut updates = tries.into_iter().zip(&changes).collect::<Vec<_>>();
+        let roots = update_storage_tries(&mut updates, &provider, &NoopTrieHinter).unwrap();
+
+        assert_eq!(roots, expected);
+        assert_eq!(roots, sequential_roots);
+        assert_eq!(updates.into_iter().map(|(trie, _)| trie).collect::<Vec<_>>(), sequential);
+    }
+}
@@ -1,7 +1,10 @@
 //! Contains the builder pattern for the [StatelessL2BlockExecutor].
 
 use super::StatelessL2BlockExecutor;
-use crate::db::{TrieDB, TrieDBProvider};
+use crate::{
+    db::{TrieDB, TrieDBProvider},
+    tracer::{ExecutionTracer, TracerConfig},
+};
 use alloy_consensus::{Header, Sealable, Sealed};
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
@@ -28,6 +31,8 @@ where
     parent_header: Option<Sealed<Header>>,
     /// The [KonaHandleRegister] to use during execution.
     handler_register: Option<KonaHandleRegister<F, H>>,
+    /// The [TracerConfig] to trace the executed transactions with.
+    tracer_config: Option<TracerConfig>,
 }
 
 impl<'a, F, H> StatelessL2BlockExecutorBuilder<'a, F, H>
@@ -37,7 +42,14 @@ where
 {
     /// Instantiate a new builder with the given [RollupConfig].
     pub fn new(config: &'a RollupConfig, provider: F, hinter: H) -> Self {
-        Self { config, provider, hinter, parent_header: None, handler_register: None }
+        Self {
+            config,
+            provider,
+            hinter,
+            parent_header: None,
+            handler_register: None,
+            tracer_config: None,
+        }
     }
 
     /// Set the [Header] to begin execution from.
@@ -52,6 +64,12 @@ where
         self
     }
 
+    /// Trace the executed transactions with the given [TracerConfig].
+    pub const fn with_tracer(mut self, tracer_config: TracerConfig) -> Self {
+        self.tracer_config = Some(tracer_config);
+        self
+    }
+
     /// Build the [StatelessL2BlockExecutor] from the builder configuration.
     pub fn build(self) -> StatelessL2BlockExecutor<'a, F, H> {
         let parent_header = self.parent_header.unwrap_or_else(|| {
@@ -65,6 +83,10 @@ where
             config: self.config,
             trie_db,
             handler_register: self.handler_register,
+            cache_state: None,
+            block_hashes: Default::default(),
+            cache_stats: Default::default(),
+            tracer: self.tracer_config.map(ExecutionTracer::new),
         }
     }
 }
@@ -3,14 +3,15 @@
 use crate::{
     ExecutorError, ExecutorResult, TrieDBProvider,
     constants::{L2_TO_L1_BRIDGE, OUTPUT_ROOT_VERSION, SHA256_EMPTY},
-    db::TrieDB,
+    db::{TrieDB, TrieDBCacheStats},
     errors::TrieDBError,
     syscalls::{
         ensure_create2_deployer_canyon, pre_block_beacon_root_contract_call,
         pre_block_block_hash_contract_call,
     },
+    tracer::{ExecutionTracer, TransactionTrace},
 };
-use alloc::{string::ToString, vec::Vec};
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
 use alloy_consensus::{
     EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH, Header, Sealable, Sealed, Transaction,
 };
@@ -22,7 +23,7 @@ use op_alloy_consensus::{OpReceiptEnvelope, OpTxEnvelope};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use revm::{
     Evm,
-    db::{State, states::bundle_state::BundleRetention},
+    db::{CacheState, State, states::bundle_state::BundleRetention},
     primitives::{EnvWithHandlerCfg, calc_excess_blob_gas},
 };
 
@@ -46,6 +47,11 @@ pub struct ExecutionArtifacts {
 
 /// The block executor for the L2 client program. Operates off of a [TrieDB] backed [State],
 /// allowing for stateless block execution of OP Stack blocks.
+///
+/// When consecutive blocks are executed, the opened trie, the fetched bytecode and the [State]'s
+/// account cache are carried over from one block to the next. They are only discarded when the
+/// parent header is moved off of the last executed block, when execution fails, or on an explicit
+/// [Self::flush_cache].
 #[derive(Debug)]
 pub struct StatelessL2BlockExecutor<'a, F, H>
 where
@@ -58,6 +64,14 @@ where
     trie_db: TrieDB<F, H>,
     /// The [KonaHandleRegister] to use during execution.
     handler_register: Option<KonaHandleRegister<F, H>>,
+    /// The [State] cache left by the last executed block.
+    cache_state: Option<CacheState>,
+    /// The block hashes looked up while executing the previous blocks.
+    block_hashes: BTreeMap<u64, B256>,
+    /// The [TrieDBCacheStats] of the last executed block.
+    cache_stats: TrieDBCacheStats,
+    /// The [ExecutionTracer] tracing the executed transactions, if tracing is enabled.
+    tracer: Option<ExecutionTracer>,
 }
 
 impl<'a, F, H> StatelessL2BlockExecutor<'a, F, H>
@@ -74,6 +88,46 @@ where
         StatelessL2BlockExecutorBuilder::new(config, provider, hinter)
     }
 
+    /// Returns the header of the block that the next payload is executed on top of.
+    pub const fn parent_header(&self) -> &Sealed<Header> {
+        self.trie_db.parent_block_header()
+    }
+
+    /// Sets the header of the block that the next payload is executed on top of.
+    ///
+    /// If it is not the header of the last executed block, as on a reorg, the caches are flushed
+    /// and the trie is re-blinded at the header's state root.
+    pub fn update_parent_header(&mut self, parent_header: Sealed<Header>) {
+        if parent_header.seal() != self.trie_db.parent_block_header().seal() {
+            self.trie_db.set_parent_block_header(parent_header);
+            self.flush_cache();
+        }
+    }
+
+    /// Discards the opened trie, the fetched bytecode and the [State] cache carried over from
+    /// the previously executed blocks, re-blinding the trie at the parent header's state root.
+    pub fn flush_cache(&mut self) {
+        self.trie_db.flush();
+        self.cache_state = None;
+        self.block_hashes.clear();
+    }
+
+    /// Returns the [TrieDBCacheStats] of the last executed block.
+    pub const fn cache_stats(&self) -> &TrieDBCacheStats {
+        &self.cache_stats
+    }
+
+    /// Returns the [ExecutionTracer], if tracing is enabled.
+    pub const fn tracer(&self) -> Option<&ExecutionTracer> {
+        self.tracer.as_ref()
+    }
+
+    /// Takes the [TransactionTrace]s of the transactions executed since the traces were last
+    /// taken. Empty if tracing is not enabled.
+    pub fn take_traces(&mut self) -> Vec<TransactionTrace> {
+        self.tracer.as_mut().map(ExecutionTracer::take_traces).unwrap_or_default()
+    }
+
     /// Fetches the L2 to L1 message passer account from the cache or underlying trie.
     fn message_passer_account(
         db: &mut TrieDB<F, H>,
@@ -97,16 +151,31 @@ where
     ///     - Reject any EIP-4844 transactions, as they are not supported on the OP Stack.
     ///     - If the transaction is a deposit, cache the depositor account prior to execution.
     ///     - Construct the EVM with the given configuration.
-    ///     - Execute the transaction.
+    ///     - Execute the transaction, recording its trace if tracing is enabled.
     ///     - Accumulate the gas used by the transaction to the block-scoped cumulative gas used
     ///       counter.
     ///     - Create a receipt envelope for the transaction.
     /// 4. Merge all state transitions into the cache state.
     /// 5. Compute the [state root, transactions root, receipts root, logs bloom] for the processed
     ///    block.
+    ///
+    /// If execution fails, the caches are flushed, as they may hold partially applied state
+    /// transitions.
     pub fn execute_payload(
         &mut self,
         payload: OpPayloadAttributes,
+    ) -> ExecutorResult<ExecutionArtifacts> {
+        let result = self.execute_payload_cached(payload);
+        if result.is_err() {
+            self.flush_cache();
+        }
+        result
+    }
+
+    /// Executes the given block on top of the caches left by the previously executed blocks.
+    fn execute_payload_cached(
+        &mut self,
+        payload: OpPayloadAttributes,
     ) -> ExecutorResult<ExecutionArtifacts> {
         // Prepare the `revm` environment.
         let base_fee_params = Self::active_base_fee_params(
@@ -146,8 +215,14 @@ where
             .hint_execution_witness(parent_block_hash, &payload)
             .map_err(|e| TrieDBError::Provider(e.to_string()))?;
 
-        let mut state =
-            State::builder().with_database(&mut self.trie_db).with_bundle_update().build();
+        let mut state_builder = State::builder()
+            .with_database(&mut self.trie_db)
+            .with_bundle_update()
+            .with_block_hashes(core::mem::take(&mut self.block_hashes));
+        if let Some(cache_state) = self.cache_state.take() {
+            state_builder = state_builder.with_cached_prestate(cache_state);
+        }
+        let mut state = state_builder.build();
 
         // Apply the pre-block EIP-4788 contract call.
         pre_block_beacon_root_contract_call(
@@ -227,10 +302,9 @@ where
             }
 
             // Modify the transaction environment with the current transaction.
-            evm = evm
-                .modify()
-                .with_tx_env(Self::prepare_tx_env(&transaction, raw_transaction)?)
-                .build();
+            let tx_env = Self::prepare_tx_env(&transaction, raw_transaction)?;
+            let traced_tx_env = self.tracer.is_some().then(|| tx_env.clone());
+            evm = evm.modify().with_tx_env(tx_env).build();
 
             // If the transaction is a deposit, cache the depositor account.
             //
@@ -253,7 +327,16 @@ where
                 target: "client_executor",
                 "Executing transaction: {tx_hash}",
             );
-            let result = evm.transact_commit().map_err(ExecutorError::ExecutionError)?;
+            let result = match (self.tracer.as_mut(), traced_tx_env) {
+                (Some(tracer), Some(tx_env)) => tracer.trace_transaction(
+                    &mut **evm.db_mut(),
+                    &initialized_cfg,
+                    &initialized_block_env,
+                    tx_env,
+                    tx_hash,
+                )?,
+                _ => evm.transact_commit().map_err(ExecutorError::ExecutionError)?,
+            };
             debug!(
                 target: "client_executor",
                 "Transaction executed: {tx_hash} | Gas used: {gas_used} | Success: {status}",
@@ -414,6 +497,23 @@ where
 
         // Update the parent block hash in the state database.
         state.database.set_parent_block_header(header.clone());
+
+        // Carry the caches over to the next block.
+        self.cache_stats = state.database.take_cache_stats();
+        self.cache_state = Some(core::mem::take(&mut state.cache));
+        self.block_hashes = core::mem::take(&mut state.block_hashes);
+        info!(
+            target: "client_executor",
+            "Trie DB cache | Account hits: {account_hits}/{accounts} | Storage hits: {storage_hits}/{slots} | Code hits: {code_hits}/{codes} | Trie nodes fetched: {fetched}",
+            account_hits = self.cache_stats.account_hits,
+            accounts = self.cache_stats.account_hits + self.cache_stats.account_misses,
+            storage_hits = self.cache_stats.storage_hits,
+            slots = self.cache_stats.storage_hits + self.cache_stats.storage_misses,
+            code_hits = self.cache_stats.code_hits,
+            codes = self.cache_stats.code_hits + self.cache_stats.code_misses,
+            fetched = self.cache_stats.trie_nodes_fetched,
+        );
+
         Ok(ExecutionArtifacts { block_header: header, receipts })
     }
 
@@ -515,9 +615,24 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::test_utils::run_test_fixture;
+    use super::*;
+    use crate::{
+        CallConfig, PreStateConfig, PreStateFrame, TracerConfig, test_utils::run_test_fixture,
+    };
+    use alloy_consensus::Sealable;
+    use alloy_primitives::{Address, TxKind, address, hex};
+    use alloy_rlp::{Decodable, Encodable};
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use kona_genesis::HardForkConfig;
+    use kona_mpt::{NoopTrieHinter, TrieNode, TrieProvider};
+    use op_alloy_consensus::TxDeposit;
     use rstest::rstest;
-    use std::path::PathBuf;
+    use std::{
+        collections::HashMap,
+        path::PathBuf,
+        string::String,
+        sync::{Arc, RwLock},
+    };
 
     // To create new test fixtures, uncomment the following test and run it with parameters filled.
     //
@@ -548,4 +663,238 @@ mod test {
 
         run_test_fixture(fixture_dir).await;
     }
+
+    /// A counter contract, that increments slot `0` and stores the block number at the slot of
+    /// the block number on every call.
+    const COUNTER_RUNTIME: [u8; 13] = hex!("600054600101600055434355" "00");
+
+    /// The init code of the [COUNTER_RUNTIME] contract.
+    const COUNTER_INIT: [u8; 24] = hex!("600d80600b6000396000f3" "600054600101600055434355" "00");
+
+    /// A [TrieDBProvider] serving the preimages shared with the test.
+    #[derive(Debug, Clone, Default)]
+    struct MemoryTrieDBProvider(Arc<RwLock<HashMap<B256, Bytes>>>);
+
+    impl MemoryTrieDBProvider {
+        /// Inserts the preimages of every opened node of the trie.
+        fn insert_trie(&self, node: &TrieNode) {
+            let mut encoded = Vec::new();
+            node.encode(&mut encoded);
+            if encoded.len() >= B256::ZERO.len() {
+                self.0.write().unwrap().insert(keccak256(&encoded), encoded.into());
+            }
+            match node {
+                TrieNode::Branch { stack } => stack.iter().for_each(|node| self.insert_trie(node)),
+                TrieNode::Extension { node, .. } => self.insert_trie(node),
+                _ => {}
+            }
+        }
+
+        fn get(&self, hash: B256) -> Result<Bytes, String> {
+            self.0.read().unwrap().get(&hash).cloned().ok_or_else(|| "Missing preimage".into())
+        }
+    }
+
+    impl TrieProvider for MemoryTrieDBProvider {
+        type Error = String;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            TrieNode::decode(&mut self.get(key)?.as_ref()).map_err(|e| e.to_string())
+        }
+    }
+
+    impl TrieDBProvider for MemoryTrieDBProvider {
+        fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+            self.get(code_hash)
+        }
+
+        fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+            Header::decode(&mut self.get(hash)?.as_ref()).map_err(|e| e.to_string())
+        }
+    }
+
+    /// Returns the attributes of a block at `timestamp`, made of the given deposits.
+    fn deposit_payload(
+        timestamp: u64,
+        deposits: &[(Address, TxKind, &[u8])],
+    ) -> OpPayloadAttributes {
+        let transactions = deposits
+            .iter()
+            .enumerate()
+            .map(|(i, (from, to, input))| {
+                let deposit = TxDeposit {
+                    source_hash: B256::with_last_byte(i as u8),
+                    from: *from,
+                    to: *to,
+                    mint: Some(1_000_000_000_000_000),
+                    value: U256::from(1),
+                    gas_limit: 1_000_000,
+                    is_system_transaction: false,
+                    input: Bytes::copy_from_slice(input),
+                };
+                OpTxEnvelope::Deposit(deposit.seal_slow()).encoded_2718().into()
+            })
+            .collect();
+
+        OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: Some(Vec::new()),
+                parent_beacon_block_root: None,
+            },
+            transactions: Some(transactions),
+            no_tx_pool: None,
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+        }
+    }
+
+    #[test]
+    fn test_cached_execution_matches_fresh_execution() {
+        let config = RollupConfig {
+            hardforks: HardForkConfig {
+                regolith_time: Some(0),
+                canyon_time: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let genesis = Header {
+            state_root: EMPTY_ROOT_HASH,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        }
+        .seal_slow();
+
+        let deployer = address!("0000000000000000000000000000000000000d01");
+        let user = address!("0000000000000000000000000000000000000e01");
+        let counter = deployer.create(0);
+        let payloads = [
+            deposit_payload(2, &[(deployer, TxKind::Create, &COUNTER_INIT)]),
+            deposit_payload(4, &[(user, TxKind::Call(counter), &[])]),
+            deposit_payload(6, &[(user, TxKind::Call(counter), &[]), (deployer, user.into(), &[])]),
+            deposit_payload(8, &[(user, TxKind::Call(counter), &[])]),
+        ];
+
+        let provider = MemoryTrieDBProvider::default();
+        provider.0.write().unwrap().insert(keccak256(COUNTER_RUNTIME), COUNTER_RUNTIME.into());
+        let mut cached =
+            StatelessL2BlockExecutor::builder(&config, provider.clone(), NoopTrieHinter)
+                .with_parent_header(genesis.clone())
+                .build();
+
+        let mut headers = vec![genesis];
+        for (i, payload) in payloads.iter().enumerate() {
+            // A fresh executor fetches the parent state from the preimages of the previous
+            // blocks' tries.
+            let mut fresh =
+                StatelessL2BlockExecutor::builder(&config, provider.clone(), NoopTrieHinter)
+                    .with_parent_header(headers[i].clone())
+                    .build();
+            let expected = fresh.execute_payload(payload.clone()).unwrap();
+
+            cached.update_parent_header(headers[i].clone());
+            let artifacts = cached.execute_payload(payload.clone()).unwrap();
+            assert_eq!(artifacts, expected);
+
+            // The cached executor opened the whole trie from the empty genesis state, so it never
+            // fetches a trie node.
+            assert_eq!(cached.cache_stats().trie_nodes_fetched, 0);
+            if i > 1 {
+                assert!(fresh.cache_stats().trie_nodes_fetched > 0);
+            }
+
+            provider.insert_trie(cached.trie_db.root());
+            cached.trie_db.storage_roots().values().for_each(|root| provider.insert_trie(root));
+            headers.push(artifacts.block_header);
+        }
+
+        // Moving the parent header off of the last executed block flushes the caches, and the
+        // trie is re-opened from the parent state root.
+        cached.update_parent_header(headers[2].clone());
+        assert_eq!(cached.trie_db.root(), &TrieNode::new_blinded(headers[2].state_root));
+        let artifacts = cached.execute_payload(payloads[2].clone()).unwrap();
+        assert_eq!(artifacts.block_header, headers[3]);
+        assert!(cached.cache_stats().trie_nodes_fetched > 0);
+
+        // A failed execution flushes the caches, without moving the parent header.
+        let invalid = OpPayloadAttributes { gas_limit: None, ..payloads[3].clone() };
+        assert!(cached.execute_payload(invalid).is_err());
+        assert_eq!(cached.trie_db.root(), &TrieNode::new_blinded(headers[3].state_root));
+        let artifacts = cached.execute_payload(payloads[3].clone()).unwrap();
+        assert_eq!(artifacts.block_header, headers[4]);
+    }
+
+    #[test]
+    fn test_trace_transactions() {
+        let config = RollupConfig {
+            hardforks: HardForkConfig {
+                regolith_time: Some(0),
+                canyon_time: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let genesis = Header {
+            state_root: EMPTY_ROOT_HASH,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        }
+        .seal_slow();
+
+        let deployer = address!("0000000000000000000000000000000000000d01");
+        let user = address!("0000000000000000000000000000000000000e01");
+        let counter = deployer.create(0);
+
+        let provider = MemoryTrieDBProvider::default();
+        provider.0.write().unwrap().insert(keccak256(COUNTER_RUNTIME), COUNTER_RUNTIME.into());
+        let tracer_config = TracerConfig {
+            call: Some(CallConfig::default()),
+            prestate: Some(PreStateConfig { diff_mode: true }),
+        };
+        let mut executor = StatelessL2BlockExecutor::builder(&config, provider, NoopTrieHinter)
+            .with_parent_header(genesis)
+            .with_tracer(tracer_config)
+            .build();
+        executor
+            .execute_payload(deposit_payload(2, &[(deployer, TxKind::Create, &COUNTER_INIT)]))
+            .unwrap();
+        executor
+            .execute_payload(deposit_payload(4, &[(user, TxKind::Call(counter), &[])]))
+            .unwrap();
+
+        let traces = executor.take_traces();
+        assert_eq!(traces.len(), 2);
+        assert!(executor.take_traces().is_empty());
+
+        let create = traces[0].call_trace.as_ref().unwrap();
+        assert_eq!(create.typ, "CREATE");
+        assert_eq!(create.from, deployer);
+        assert_eq!(create.to, Some(counter));
+        assert_eq!(create.output, Some(Bytes::from_static(&COUNTER_RUNTIME)));
+        assert!(create.error.is_none());
+
+        let call = traces[1].call_trace.as_ref().unwrap();
+        assert_eq!(call.typ, "CALL");
+        assert_eq!((call.from, call.to), (user, Some(counter)));
+        assert!(call.calls.is_empty() && call.error.is_none());
+        let json = serde_json::to_value(call).unwrap();
+        assert_eq!(json["type"], "CALL");
+        assert_eq!(json["gasUsed"], format!("{:#x}", call.gas_used));
+
+        // The call increments slot `0` and stores the block number at the slot of the block
+        // number.
+        let Some(PreStateFrame::Diff { pre, post }) = &traces[1].prestate_trace else {
+            panic!("Expected a diff mode prestate trace");
+        };
+        assert_eq!(pre[&counter].storage[&B256::ZERO], B256::ZERO);
+        assert_eq!(pre[&counter].code, Some(Bytes::from_static(&COUNTER_RUNTIME)));
+        assert_eq!(post[&counter].storage[&B256::ZERO], B256::with_last_byte(1));
+        assert_eq!(post[&counter].storage[&B256::with_last_byte(2)], B256::with_last_byte(2));
+        assert!(post[&counter].code.is_none());
+    }
 }
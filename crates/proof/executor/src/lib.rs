@@ -5,7 +5,7 @@
     issue_tracker_base_url = "https://github.com/op-rs/kona/issues/"
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 extern crate alloc;
 
@@ -22,7 +22,18 @@ pub use executor::{
 };
 
 mod db;
-pub use db::{NoopTrieDBProvider, TrieDB, TrieDBProvider};
+pub use db::{NoopTrieDBProvider, TrieDB, TrieDBCacheStats, TrieDBProvider};
+
+mod witness;
+pub use witness::{
+    ExecutionWitness, ExecutionWitnessRecorder, RecordingTrieDBProvider, RecordingTrieHinter,
+};
+
+mod tracer;
+pub use tracer::{
+    AccountState, CallConfig, CallFrame, CallLogFrame, CallTracer, ExecutionTracer, PreStateConfig,
+    PreStateFrame, TracerConfig, TransactionTrace,
+};
 
 mod constants;
 mod syscalls;
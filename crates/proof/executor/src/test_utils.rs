@@ -269,7 +269,7 @@ impl TrieDBProvider for ExecutorTestFixtureCreator {
     }
 }
 
-struct DiskTrieNodeProvider {
+pub(crate) struct DiskTrieNodeProvider {
     kv_store: DB,
 }
 
@@ -317,11 +317,13 @@ impl TrieDBProvider for DiskTrieNodeProvider {
     }
 }
 
-/// Executes a [ExecutorTestFixture] stored at the passed `fixture_path` and asserts that the
-/// produced block hash matches the expected block hash.
-pub(crate) async fn run_test_fixture(fixture_path: PathBuf) {
+/// Unpacks the [ExecutorTestFixture] stored at the passed `fixture_path`, returning it along with
+/// a provider for its preimages. The returned directory must outlive the provider.
+pub(crate) async fn load_test_fixture(
+    fixture_path: PathBuf,
+) -> (tempfile::TempDir, ExecutorTestFixture, DiskTrieNodeProvider) {
     // First, untar the fixture.
-    let mut fixture_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let fixture_dir = tempfile::tempdir().expect("Failed to create temporary directory");
     let untar = tokio::process::Command::new("tar")
         .arg("-xvf")
         .arg(fixture_path.as_path())
@@ -342,6 +344,14 @@ pub(crate) async fn run_test_fixture(fixture_path: PathBuf) {
         serde_json::from_slice(&fs::read(fixture_dir.path().join("fixture.json")).await.unwrap())
             .expect("Failed to deserialize fixture");
 
+    (fixture_dir, fixture, provider)
+}
+
+/// Executes a [ExecutorTestFixture] stored at the passed `fixture_path` and asserts that the
+/// produced block hash matches the expected block hash.
+pub(crate) async fn run_test_fixture(fixture_path: PathBuf) {
+    let (_fixture_dir, fixture, provider) = load_test_fixture(fixture_path).await;
+
     let mut executor =
         StatelessL2BlockExecutor::builder(&fixture.rollup_config, provider, NoopTrieHinter)
             .with_parent_header(fixture.parent_header.seal_slow())
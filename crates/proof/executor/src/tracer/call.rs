@@ -0,0 +1,439 @@
+//! Contains the [CallTracer], an [Inspector] that records the call tree of a transaction in the
+//! shape of geth's `callTracer`.
+
+use super::CallConfig;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{Address, B256, Bytes, Log, U64, U256};
+use revm::{
+    Database, EvmContext, Inspector,
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme,
+        InstructionResult, Interpreter, InterpreterResult,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// A call frame of geth's `callTracer`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// The type of the call, e.g. `CALL`, `DELEGATECALL` or `CREATE2`.
+    #[serde(rename = "type")]
+    pub typ: String,
+    /// The caller.
+    pub from: Address,
+    /// The callee, or the created contract. `None` if contract creation failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// The value transferred by the call, if the call carries a value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// The gas provided to the call.
+    pub gas: U64,
+    /// The gas used by the call.
+    pub gas_used: U64,
+    /// The input of the call, or the init code of the created contract.
+    pub input: Bytes,
+    /// The output of the call, or the code of the created contract, if it is not empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    /// The error the call failed with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The reason the call reverted with, if it reverted with an `Error(string)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// The calls made by the call, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+    /// The logs emitted by the call, if the tracer records them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<CallLogFrame>,
+}
+
+/// A log emitted within a [CallFrame].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallLogFrame {
+    /// The address of the contract that emitted the log.
+    pub address: Address,
+    /// The topics of the log.
+    pub topics: Vec<B256>,
+    /// The data of the log.
+    pub data: Bytes,
+    /// The number of calls made by the frame before the log was emitted.
+    pub position: U64,
+}
+
+/// An [Inspector] that records the call tree of a transaction as a tree of [CallFrame]s.
+#[derive(Debug, Default, Clone)]
+pub struct CallTracer {
+    /// The [CallConfig].
+    config: CallConfig,
+    /// The frames of the calls that have been entered but not yet exited.
+    stack: Vec<CallFrame>,
+    /// The top-level frame, once it has exited.
+    root: Option<CallFrame>,
+}
+
+impl CallTracer {
+    /// Creates a new [CallTracer] with the given [CallConfig].
+    pub const fn new(config: CallConfig) -> Self {
+        Self { config, stack: Vec::new(), root: None }
+    }
+
+    /// Consumes the tracer, returning the top-level [CallFrame] of the traced transaction.
+    ///
+    /// As in geth, the logs of failed calls are dropped, since their state changes are reverted.
+    pub fn into_frame(mut self) -> CallFrame {
+        let mut root = self.root.take().unwrap_or_default();
+        if self.config.only_top_call {
+            root.calls.clear();
+        }
+        clear_failed_logs(&mut root);
+        root
+    }
+
+    /// Enters a new call frame.
+    fn enter(&mut self, frame: CallFrame) {
+        self.stack.push(frame);
+    }
+
+    /// Exits the current call frame with the given [InterpreterResult].
+    fn exit(&mut self, result: &InterpreterResult, created: Option<Option<Address>>) {
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.gas_used = U64::from(result.gas.spent());
+        if let Some(address) = created {
+            frame.to = address;
+        }
+        // As in geth, empty outputs are omitted, and failed calls only report the output they
+        // reverted with.
+        let output = (!result.output.is_empty()).then(|| result.output.clone());
+        if result.result.is_ok() {
+            frame.output = output;
+        } else {
+            frame.error = Some(error_message(result.result));
+            if created.is_some() {
+                frame.to = None;
+            }
+            if result.result.is_revert() {
+                frame.revert_reason = output.as_ref().and_then(|output| revert_reason(output));
+                frame.output = output;
+            }
+        }
+
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTracer {
+    fn log(&mut self, _: &mut Interpreter, _: &mut EvmContext<DB>, log: &Log) {
+        if !self.config.with_log {
+            return;
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.logs.push(CallLogFrame {
+                address: log.address,
+                topics: log.topics().to_vec(),
+                data: log.data.data.clone(),
+                position: U64::from(frame.calls.len()),
+            });
+        }
+    }
+
+    fn call(&mut self, _: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let (typ, value) = match inputs.scheme {
+            CallScheme::Call | CallScheme::ExtCall => ("CALL", Some(inputs.call_value())),
+            CallScheme::CallCode => ("CALLCODE", Some(inputs.call_value())),
+            CallScheme::DelegateCall | CallScheme::ExtDelegateCall => {
+                ("DELEGATECALL", Some(inputs.call_value()))
+            }
+            CallScheme::StaticCall | CallScheme::ExtStaticCall => ("STATICCALL", None),
+        };
+        // A delegate call runs in the context of the delegating contract, which geth reports as
+        // the caller rather than the `msg.sender` that the call inherits.
+        let from = match inputs.scheme {
+            CallScheme::DelegateCall | CallScheme::ExtDelegateCall => inputs.target_address,
+            _ => inputs.caller,
+        };
+        self.enter(CallFrame {
+            typ: typ.to_string(),
+            from,
+            to: Some(inputs.bytecode_address),
+            value,
+            gas: U64::from(inputs.gas_limit),
+            input: inputs.input.clone(),
+            ..Default::default()
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _: &mut EvmContext<DB>,
+        _: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit(&outcome.result, None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let typ = match inputs.scheme {
+            CreateScheme::Create => "CREATE",
+            CreateScheme::Create2 { .. } => "CREATE2",
+        };
+        self.enter(CallFrame {
+            typ: typ.to_string(),
+            from: inputs.caller,
+            value: Some(inputs.value),
+            gas: U64::from(inputs.gas_limit),
+            input: inputs.init_code.clone(),
+            ..Default::default()
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _: &mut EvmContext<DB>,
+        _: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit(&outcome.result, Some(outcome.address));
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.calls.push(CallFrame {
+                typ: "SELFDESTRUCT".to_string(),
+                from: contract,
+                to: Some(target),
+                value: Some(value),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// Drops the logs of the failed frames in the tree rooted at `frame`.
+fn clear_failed_logs(frame: &mut CallFrame) {
+    if frame.error.is_some() {
+        clear_logs(frame);
+    } else {
+        frame.calls.iter_mut().for_each(clear_failed_logs);
+    }
+}
+
+/// Drops the logs of every frame in the tree rooted at `frame`.
+fn clear_logs(frame: &mut CallFrame) {
+    frame.logs.clear();
+    frame.calls.iter_mut().for_each(clear_logs);
+}
+
+/// Decodes the reason of a revert with an `Error(string)` payload, as geth does.
+fn revert_reason(output: &[u8]) -> Option<String> {
+    const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+    let data = output.strip_prefix(&ERROR_SELECTOR)?;
+    let word = |offset: usize| -> Option<usize> {
+        let word = data.get(offset..offset.checked_add(32)?)?;
+        U256::from_be_slice(word).try_into().ok()
+    };
+    let offset = word(0)?;
+    let len = word(offset)?;
+    let start = offset.checked_add(32)?;
+    let reason = data.get(start..start.checked_add(len)?)?;
+    String::from_utf8(reason.to_vec()).ok()
+}
+
+/// Returns the geth error message of a failed call.
+fn error_message(result: InstructionResult) -> String {
+    match result {
+        r if r.is_revert() => "execution reverted".to_string(),
+        InstructionResult::OutOfGas |
+        InstructionResult::MemoryOOG |
+        InstructionResult::MemoryLimitOOG |
+        InstructionResult::PrecompileOOG |
+        InstructionResult::InvalidOperandOOG => "out of gas".to_string(),
+        InstructionResult::OpcodeNotFound | InstructionResult::InvalidFEOpcode => {
+            "invalid opcode".to_string()
+        }
+        InstructionResult::InvalidJump => "invalid jump destination".to_string(),
+        InstructionResult::StateChangeDuringStaticCall => "write protection".to_string(),
+        InstructionResult::CallTooDeep => "max call depth exceeded".to_string(),
+        InstructionResult::OutOfFunds => "insufficient balance for transfer".to_string(),
+        InstructionResult::CreateCollision => "contract address collision".to_string(),
+        InstructionResult::CreateContractSizeLimit => "max code size exceeded".to_string(),
+        r => format!("{r:?}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::{TxKind, address, hex};
+    use revm::{
+        Evm,
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{AccountInfo, Bytecode},
+    };
+    use serde_json::{Value, json};
+
+    const SENDER: Address = address!("00000000000000000000000000000000000000aa");
+    const PROXY: Address = address!("0000000000000000000000000000000000001000");
+    const IMPLEMENTATION: Address = address!("0000000000000000000000000000000000002000");
+    const REVERTER: Address = address!("0000000000000000000000000000000000003000");
+
+    /// The `Error("no")` payload that the [REVERTER] reverts with.
+    const REVERT_DATA: [u8; 100] = hex!(
+        "08c379a0"
+        "0000000000000000000000000000000000000000000000000000000000000020"
+        "0000000000000000000000000000000000000000000000000000000000000002"
+        "6e6f000000000000000000000000000000000000000000000000000000000000"
+    );
+
+    /// Returns a database holding a proxy that delegate calls an implementation, which calls a
+    /// contract that reverts, and then creates an empty contract.
+    fn db() -> CacheDB<EmptyDB> {
+        // DELEGATECALL(0xffff, IMPLEMENTATION, 0, 0, 0, 0); STOP
+        let proxy = [
+            &hex!("6000600060006000")[..],
+            &[0x73],
+            IMPLEMENTATION.as_slice(),
+            &hex!("61fffff45000"),
+        ]
+        .concat();
+        // CALL(0xffff, REVERTER, 0, 0, 0, 0, 0); CREATE(0, 0, 1); STOP
+        let implementation = [
+            &hex!("60006000600060006000")[..],
+            &[0x73],
+            REVERTER.as_slice(),
+            &hex!("61fffff150"),
+            &hex!("600160006000f05000"),
+        ]
+        .concat();
+        // CODECOPY(0, 12, 100); REVERT(0, 100)
+        let reverter = [&hex!("6064600c60003960646000fd")[..], &REVERT_DATA].concat();
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in
+            [(PROXY, proxy), (IMPLEMENTATION, implementation), (REVERTER, reverter)]
+        {
+            let code = Bytecode::new_raw(code.into());
+            db.insert_account_info(
+                address,
+                AccountInfo {
+                    nonce: 1,
+                    code_hash: code.hash_slow(),
+                    code: Some(code),
+                    ..Default::default()
+                },
+            );
+        }
+        db
+    }
+
+    /// Removes the gas fields of the serialized call frames, which depend on the gas schedule
+    /// rather than on the shape of the trace.
+    fn strip_gas(frame: &mut Value) {
+        if let Value::Object(fields) = frame {
+            fields.remove("gas");
+            fields.remove("gasUsed");
+            fields.get_mut("calls").into_iter().for_each(|calls| {
+                calls.as_array_mut().into_iter().flatten().for_each(strip_gas);
+            });
+        }
+    }
+
+    #[test]
+    fn test_call_trace_matches_geth() {
+        let mut evm = Evm::builder()
+            .with_db(db())
+            .with_external_context(CallTracer::new(CallConfig::default()))
+            .modify_tx_env(|tx| {
+                tx.caller = SENDER;
+                tx.transact_to = TxKind::Call(PROXY);
+                tx.gas_limit = 1_000_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let mut trace = serde_json::to_value(evm.into_context().external.into_frame()).unwrap();
+        strip_gas(&mut trace);
+
+        // The `callTracer` result of geth for the same transaction, without its gas fields.
+        let expected = json!({
+            "type": "CALL",
+            "from": SENDER,
+            "to": PROXY,
+            "value": "0x0",
+            "input": "0x",
+            "calls": [{
+                "type": "DELEGATECALL",
+                "from": PROXY,
+                "to": IMPLEMENTATION,
+                "value": "0x0",
+                "input": "0x",
+                "calls": [
+                    {
+                        "type": "CALL",
+                        "from": PROXY,
+                        "to": REVERTER,
+                        "value": "0x0",
+                        "input": "0x",
+                        "output": Bytes::from(REVERT_DATA),
+                        "error": "execution reverted",
+                        "revertReason": "no"
+                    },
+                    {
+                        "type": "CREATE",
+                        "from": PROXY,
+                        "to": PROXY.create(1),
+                        "value": "0x0",
+                        "input": "0x00"
+                    }
+                ]
+            }]
+        });
+        assert_eq!(trace, expected);
+    }
+
+    #[test]
+    fn test_failed_create_has_no_address() {
+        let mut evm = Evm::builder()
+            .with_db(db())
+            .with_external_context(CallTracer::new(CallConfig::default()))
+            .modify_tx_env(|tx| {
+                tx.caller = SENDER;
+                // REVERT(0, 0)
+                tx.transact_to = TxKind::Create;
+                tx.data = Bytes::from_static(&hex!("60006000fd"));
+                tx.gas_limit = 1_000_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(!evm.transact().unwrap().result.is_success());
+
+        let frame = evm.into_context().external.into_frame();
+        assert_eq!(frame.typ, "CREATE");
+        assert_eq!(frame.to, None);
+        assert_eq!(frame.output, None);
+        assert_eq!(frame.error.as_deref(), Some("execution reverted"));
+    }
+}
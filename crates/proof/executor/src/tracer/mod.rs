@@ -0,0 +1,159 @@
+//! Contains the [ExecutionTracer], which traces the transactions executed by the
+//! [StatelessL2BlockExecutor] in the shape of geth's built-in `callTracer` and `prestateTracer`.
+//!
+//! [StatelessL2BlockExecutor]: crate::StatelessL2BlockExecutor
+
+use crate::{ExecutorError, ExecutorResult, TrieDBError};
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U64};
+use revm::{
+    Database, DatabaseCommit, Evm,
+    db::State,
+    inspector_handle_register,
+    primitives::{
+        BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, ResultAndState, TxEnv,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+mod call;
+pub use call::{CallFrame, CallLogFrame, CallTracer};
+
+mod prestate;
+pub use prestate::{AccountState, PreStateFrame};
+
+/// The configuration of geth's `callTracer`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallConfig {
+    /// Whether to only trace the top-level call of the transaction.
+    #[serde(default)]
+    pub only_top_call: bool,
+    /// Whether to record the logs emitted by each call.
+    #[serde(default)]
+    pub with_log: bool,
+}
+
+/// The configuration of geth's `prestateTracer`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreStateConfig {
+    /// Whether to return the state diff of the transaction, rather than the prestate of every
+    /// account it touched.
+    #[serde(default)]
+    pub diff_mode: bool,
+}
+
+/// The tracers to run over each transaction executed by the [StatelessL2BlockExecutor].
+///
+/// [StatelessL2BlockExecutor]: crate::StatelessL2BlockExecutor
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TracerConfig {
+    /// The configuration of the `callTracer`, if it is enabled.
+    pub call: Option<CallConfig>,
+    /// The configuration of the `prestateTracer`, if it is enabled.
+    pub prestate: Option<PreStateConfig>,
+}
+
+/// The traces of a single transaction.
+///
+/// Each trace serializes to the same JSON as the result of the corresponding geth tracer, so it
+/// can be diffed against `debug_traceTransaction` of the reference client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The `callTracer` result, if the call tracer is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_trace: Option<CallFrame>,
+    /// The `prestateTracer` result, if the prestate tracer is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prestate_trace: Option<PreStateFrame>,
+}
+
+/// Traces the transactions executed by the [StatelessL2BlockExecutor], collecting a
+/// [TransactionTrace] for each of them.
+///
+/// Traced transactions are executed with a [CallTracer] inspecting the EVM. The
+/// [KonaHandleRegister] of the executor is bound to an EVM without an inspector, and is not
+/// applied to them.
+///
+/// [StatelessL2BlockExecutor]: crate::StatelessL2BlockExecutor
+/// [KonaHandleRegister]: crate::KonaHandleRegister
+#[derive(Debug, Default, Clone)]
+pub struct ExecutionTracer {
+    /// The [TracerConfig].
+    config: TracerConfig,
+    /// The traces collected so far.
+    traces: Vec<TransactionTrace>,
+}
+
+impl ExecutionTracer {
+    /// Creates a new [ExecutionTracer] with the given [TracerConfig].
+    pub const fn new(config: TracerConfig) -> Self {
+        Self { config, traces: Vec::new() }
+    }
+
+    /// Returns the [TracerConfig].
+    pub const fn config(&self) -> &TracerConfig {
+        &self.config
+    }
+
+    /// Returns the traces collected so far.
+    pub fn traces(&self) -> &[TransactionTrace] {
+        &self.traces
+    }
+
+    /// Takes the traces collected so far, leaving the tracer empty.
+    pub fn take_traces(&mut self) -> Vec<TransactionTrace> {
+        core::mem::take(&mut self.traces)
+    }
+
+    /// Executes the transaction on top of the given [State] with a [CallTracer] inspecting the
+    /// EVM, and commits its state transitions after recording its [TransactionTrace].
+    pub(crate) fn trace_transaction<DB>(
+        &mut self,
+        state: &mut State<DB>,
+        cfg: &CfgEnvWithHandlerCfg,
+        block: &BlockEnv,
+        tx: TxEnv,
+        tx_hash: B256,
+    ) -> ExecutorResult<ExecutionResult>
+    where
+        DB: Database<Error = TrieDBError>,
+    {
+        let gas_limit = tx.gas_limit;
+        let mut evm = Evm::builder()
+            .with_db(&mut *state)
+            .with_external_context(CallTracer::new(self.config.call.unwrap_or_default()))
+            .with_env_with_handler_cfg(EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block.clone(),
+                tx,
+            ))
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let ResultAndState { result, state: changes } =
+            evm.transact().map_err(ExecutorError::ExecutionError)?;
+        let call_tracer = evm.into_context().external;
+
+        // The top-level frame accounts for the whole transaction, as in geth, rather than only
+        // for the gas available to its execution.
+        let call_trace = self.config.call.is_some().then(|| {
+            let mut frame = call_tracer.into_frame();
+            frame.gas = U64::from(gas_limit);
+            frame.gas_used = U64::from(result.gas_used());
+            frame
+        });
+        let prestate_trace = self
+            .config
+            .prestate
+            .map(|config| PreStateFrame::from_changes(state, &changes, config.diff_mode))
+            .transpose()?;
+        self.traces.push(TransactionTrace { tx_hash, call_trace, prestate_trace });
+
+        state.commit(changes);
+        Ok(result)
+    }
+}
@@ -0,0 +1,264 @@
+//! Contains the [PreStateFrame], the result of geth's `prestateTracer`.
+
+use crate::{ExecutorResult, TrieDBError};
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_primitives::{Address, B256, Bytes, U256};
+use revm::{
+    Database,
+    db::State,
+    primitives::{AccountInfo, EvmState, KECCAK_EMPTY},
+};
+use serde::{Deserialize, Serialize};
+
+/// The state of an account in a [PreStateFrame].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountState {
+    /// The balance of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The nonce of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// The code of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage slots of the account.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+/// The result of geth's `prestateTracer`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PreStateFrame {
+    /// The state of every account touched by the transaction, and of the storage slots it
+    /// accessed, before the transaction.
+    Default(BTreeMap<Address, AccountState>),
+    /// The accounts modified by the transaction, before and after the transaction.
+    Diff {
+        /// The state of the modified accounts before the transaction, with only the modified
+        /// storage slots.
+        pre: BTreeMap<Address, AccountState>,
+        /// The modified fields of the modified accounts after the transaction. Accounts that
+        /// were self-destructed are absent.
+        post: BTreeMap<Address, AccountState>,
+    },
+}
+
+impl PreStateFrame {
+    /// Builds the [PreStateFrame] of a transaction from the state changes it produced, before
+    /// they are committed to the [State].
+    pub(crate) fn from_changes<DB>(
+        state: &mut State<DB>,
+        changes: &EvmState,
+        diff_mode: bool,
+    ) -> ExecutorResult<Self>
+    where
+        DB: Database<Error = TrieDBError>,
+    {
+        let mut pre = BTreeMap::new();
+        let mut post = BTreeMap::new();
+        for (address, account) in changes {
+            let info = state.load_cache_account(*address)?.account_info();
+            let mut pre_state = match info.as_ref() {
+                Some(info) => account_state(state, info)?,
+                None => AccountState { balance: Some(U256::ZERO), ..Default::default() },
+            };
+
+            if !diff_mode {
+                pre_state.storage = account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (word(slot), word(&value.original_value)))
+                    .collect();
+                pre.insert(*address, pre_state);
+                continue;
+            }
+
+            let changed_slots =
+                account.storage.iter().filter(|(_, value)| value.is_changed()).collect::<Vec<_>>();
+            let info_changed = info.as_ref().is_none_or(|info| {
+                info.balance != account.info.balance ||
+                    info.nonce != account.info.nonce ||
+                    info.code_hash != account.info.code_hash
+            });
+            if !account.is_selfdestructed() && !info_changed && changed_slots.is_empty() {
+                continue;
+            }
+
+            // Accounts that are empty both before and after the transaction, such as touched
+            // accounts that never existed, are left out of the diff.
+            let existed = info.as_ref().is_some_and(|info| !info.is_empty());
+            if !existed && (account.is_selfdestructed() || account.info.is_empty()) {
+                continue;
+            }
+
+            if existed {
+                pre_state.storage = changed_slots
+                    .iter()
+                    .map(|(slot, value)| (word(slot), word(&value.original_value)))
+                    .collect();
+                pre.insert(*address, pre_state.clone());
+            }
+            if account.is_selfdestructed() {
+                continue;
+            }
+
+            let post_state = account_state(state, &account.info)?;
+            post.insert(
+                *address,
+                AccountState {
+                    balance: post_state.balance.filter(|b| pre_state.balance != Some(*b)),
+                    nonce: post_state.nonce.filter(|n| pre_state.nonce != Some(*n)),
+                    code: post_state.code.filter(|c| pre_state.code.as_ref() != Some(c)),
+                    storage: changed_slots
+                        .iter()
+                        .filter(|(_, value)| !value.present_value.is_zero())
+                        .map(|(slot, value)| (word(slot), word(&value.present_value)))
+                        .collect(),
+                },
+            );
+        }
+
+        Ok(if diff_mode { Self::Diff { pre, post } } else { Self::Default(pre) })
+    }
+}
+
+/// Returns the [AccountState] of the given [AccountInfo], without its storage, loading its code
+/// from the [State] if it is not held by the [AccountInfo].
+fn account_state<DB>(state: &mut State<DB>, info: &AccountInfo) -> ExecutorResult<AccountState>
+where
+    DB: Database<Error = TrieDBError>,
+{
+    let code = match &info.code {
+        Some(code) => code.original_bytes(),
+        None if info.code_hash != KECCAK_EMPTY => {
+            state.code_by_hash(info.code_hash)?.original_bytes()
+        }
+        None => Bytes::new(),
+    };
+    Ok(AccountState {
+        balance: Some(info.balance),
+        nonce: (info.nonce > 0).then_some(info.nonce),
+        code: (!code.is_empty()).then_some(code),
+        storage: BTreeMap::new(),
+    })
+}
+
+/// Returns a storage slot key or value as a big-endian word.
+fn word(value: &U256) -> B256 {
+    B256::from(value.to_be_bytes::<32>())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::{address, b256};
+    use revm::{
+        db::{CacheDB, EmptyDBTyped},
+        primitives::{Account, AccountStatus, EvmStorageSlot, HashMap},
+    };
+
+    const TOUCHED: Address = address!("0000000000000000000000000000000000001000");
+    const FUNDED: Address = address!("0000000000000000000000000000000000002000");
+    const MODIFIED: Address = address!("0000000000000000000000000000000000003000");
+
+    /// Returns a [State] in which only [MODIFIED] exists, and the changes of a transaction that
+    /// touches [TOUCHED] without funding it, funds [FUNDED], and modifies [MODIFIED].
+    fn state_and_changes() -> (State<CacheDB<EmptyDBTyped<TrieDBError>>>, EvmState) {
+        let mut db = CacheDB::new(EmptyDBTyped::default());
+        db.insert_account_info(
+            MODIFIED,
+            AccountInfo { balance: U256::from(10), nonce: 1, ..Default::default() },
+        );
+        let state = State::builder().with_database(db).build();
+
+        let account = |balance: u64, nonce: u64, storage: HashMap<U256, EvmStorageSlot>| Account {
+            info: AccountInfo { balance: U256::from(balance), nonce, ..Default::default() },
+            storage,
+            status: AccountStatus::Touched,
+        };
+        let changes = HashMap::from_iter([
+            (TOUCHED, account(0, 0, HashMap::default())),
+            (FUNDED, account(5, 0, HashMap::default())),
+            (
+                MODIFIED,
+                account(
+                    7,
+                    1,
+                    HashMap::from_iter([
+                        (U256::from(1), EvmStorageSlot::new_changed(U256::from(5), U256::from(6))),
+                        (U256::from(2), EvmStorageSlot::new(U256::from(3))),
+                    ]),
+                ),
+            ),
+        ]);
+        (state, changes)
+    }
+
+    #[test]
+    fn test_prestate_of_touched_accounts() {
+        let (mut state, changes) = state_and_changes();
+        let frame = PreStateFrame::from_changes(&mut state, &changes, false).unwrap();
+
+        let empty = AccountState { balance: Some(U256::ZERO), ..Default::default() };
+        let modified = AccountState {
+            balance: Some(U256::from(10)),
+            nonce: Some(1),
+            code: None,
+            storage: BTreeMap::from([
+                (
+                    b256!("0000000000000000000000000000000000000000000000000000000000000001"),
+                    b256!("0000000000000000000000000000000000000000000000000000000000000005"),
+                ),
+                (
+                    b256!("0000000000000000000000000000000000000000000000000000000000000002"),
+                    b256!("0000000000000000000000000000000000000000000000000000000000000003"),
+                ),
+            ]),
+        };
+        assert_eq!(
+            frame,
+            PreStateFrame::Default(BTreeMap::from([
+                (TOUCHED, empty.clone()),
+                (FUNDED, empty),
+                (MODIFIED, modified),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_diff_of_modified_accounts() {
+        let (mut state, changes) = state_and_changes();
+        let frame = PreStateFrame::from_changes(&mut state, &changes, true).unwrap();
+
+        let slot = b256!("0000000000000000000000000000000000000000000000000000000000000001");
+        let pre = BTreeMap::from([(
+            MODIFIED,
+            AccountState {
+                balance: Some(U256::from(10)),
+                nonce: Some(1),
+                code: None,
+                storage: BTreeMap::from([(
+                    slot,
+                    b256!("0000000000000000000000000000000000000000000000000000000000000005"),
+                )]),
+            },
+        )]);
+        let post = BTreeMap::from([
+            (FUNDED, AccountState { balance: Some(U256::from(5)), ..Default::default() }),
+            (
+                MODIFIED,
+                AccountState {
+                    balance: Some(U256::from(7)),
+                    storage: BTreeMap::from([(
+                        slot,
+                        b256!("0000000000000000000000000000000000000000000000000000000000000006"),
+                    )]),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        assert_eq!(frame, PreStateFrame::Diff { pre, post });
+    }
+}
@@ -0,0 +1,294 @@
+//! Contains the [ExecutionWitnessRecorder], which captures every preimage touched by the
+//! [StatelessL2BlockExecutor] while it executes a payload, and the [ExecutionWitness] it produces.
+//!
+//! [StatelessL2BlockExecutor]: crate::StatelessL2BlockExecutor
+
+use crate::TrieDBProvider;
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use alloy_consensus::Header;
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy_rlp::Encodable;
+use kona_mpt::{TrieHinter, TrieNode, TrieProvider};
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use serde::{Deserialize, Serialize};
+use spin::Mutex;
+
+/// The execution witness of a block, in the shape returned by `debug_executionWitness`.
+///
+/// Every preimage in the witness is keyed by its `keccak256` hash, so the witness can be loaded
+/// into a key-value store of preimages without further context.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionWitness {
+    /// The RLP-encoded trie nodes of the state and storage tries touched during execution,
+    /// including during the state root computation.
+    pub state: Vec<Bytes>,
+    /// The bytecode of the contracts touched during execution.
+    pub codes: Vec<Bytes>,
+    /// The preimages of the hashed account and storage trie keys touched during execution, i.e.
+    /// the addresses of the accounts and the storage slots.
+    pub keys: Vec<Bytes>,
+    /// The RLP-encoded headers of the ancestor blocks touched during execution.
+    pub headers: Vec<Bytes>,
+}
+
+/// The preimages recorded by an [ExecutionWitnessRecorder], deduplicated by their hash.
+#[derive(Debug, Default)]
+struct RecordedPreimages {
+    state: BTreeMap<B256, Bytes>,
+    codes: BTreeMap<B256, Bytes>,
+    keys: BTreeMap<B256, Bytes>,
+    headers: BTreeMap<B256, Bytes>,
+}
+
+/// Records every trie node, bytecode, header and trie key touched by the
+/// [StatelessL2BlockExecutor], to produce an [ExecutionWitness].
+///
+/// The recorder hands out a [RecordingTrieDBProvider] and a [RecordingTrieHinter], which wrap the
+/// provider and hinter that the executor is built with, and record into the same witness.
+///
+/// **Example**:
+/// ```rust
+/// use alloy_consensus::{Header, Sealable};
+/// use alloy_primitives::B256;
+/// use kona_executor::{ExecutionWitnessRecorder, NoopTrieDBProvider, TrieDB};
+/// use kona_mpt::NoopTrieHinter;
+///
+/// let recorder = ExecutionWitnessRecorder::default();
+/// let trie_db = TrieDB::new(
+///     B256::default(),
+///     Header::default().seal_slow(),
+///     recorder.provider(NoopTrieDBProvider),
+///     recorder.hinter(NoopTrieHinter),
+/// );
+///
+/// // Execute a payload with the recording provider and hinter...
+///
+/// let witness = recorder.witness();
+/// ```
+///
+/// [StatelessL2BlockExecutor]: crate::StatelessL2BlockExecutor
+#[derive(Debug, Default, Clone)]
+pub struct ExecutionWitnessRecorder {
+    /// The preimages recorded so far.
+    preimages: Arc<Mutex<RecordedPreimages>>,
+}
+
+impl ExecutionWitnessRecorder {
+    /// Wraps the given [TrieDBProvider] in a [RecordingTrieDBProvider] that records into this
+    /// recorder.
+    pub fn provider<F: TrieDBProvider>(&self, inner: F) -> RecordingTrieDBProvider<F> {
+        RecordingTrieDBProvider { inner, recorder: self.clone() }
+    }
+
+    /// Wraps the given [TrieHinter] in a [RecordingTrieHinter] that records into this recorder.
+    pub fn hinter<H: TrieHinter>(&self, inner: H) -> RecordingTrieHinter<H> {
+        RecordingTrieHinter { inner, recorder: self.clone() }
+    }
+
+    /// Returns the [ExecutionWitness] of everything recorded so far, with the preimages of each
+    /// kind ordered by their hash.
+    pub fn witness(&self) -> ExecutionWitness {
+        let preimages = self.preimages.lock();
+        ExecutionWitness {
+            state: preimages.state.values().cloned().collect(),
+            codes: preimages.codes.values().cloned().collect(),
+            keys: preimages.keys.values().cloned().collect(),
+            headers: preimages.headers.values().cloned().collect(),
+        }
+    }
+
+    /// Clears everything recorded so far, so that the recorder can be reused for the next block.
+    pub fn clear(&self) {
+        *self.preimages.lock() = RecordedPreimages::default();
+    }
+
+    /// Records the RLP encoding of a trie node, unless it is inlined within its parent.
+    fn record_trie_node(&self, node: &TrieNode) {
+        let mut encoded = Vec::with_capacity(node.length());
+        node.encode(&mut encoded);
+        if encoded.len() >= B256::ZERO.len() {
+            self.preimages.lock().state.insert(keccak256(&encoded), encoded.into());
+        }
+    }
+
+    /// Records a trie key preimage.
+    fn record_key(&self, key: &[u8]) {
+        self.preimages.lock().keys.insert(keccak256(key), Bytes::copy_from_slice(key));
+    }
+}
+
+/// A [TrieDBProvider] that records every preimage it serves into an [ExecutionWitnessRecorder].
+#[derive(Debug, Clone)]
+pub struct RecordingTrieDBProvider<F> {
+    /// The inner provider.
+    inner: F,
+    /// The recorder.
+    recorder: ExecutionWitnessRecorder,
+}
+
+impl<F> RecordingTrieDBProvider<F> {
+    /// Consumes the provider, returning the inner provider.
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F: TrieDBProvider> TrieProvider for RecordingTrieDBProvider<F> {
+    type Error = F::Error;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        let node = self.inner.trie_node_by_hash(key)?;
+        self.recorder.record_trie_node(&node);
+        Ok(node)
+    }
+}
+
+impl<F: TrieDBProvider> TrieDBProvider for RecordingTrieDBProvider<F> {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        let code = self.inner.bytecode_by_hash(code_hash)?;
+        self.recorder.preimages.lock().codes.insert(code_hash, code.clone());
+        Ok(code)
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        let header = self.inner.header_by_hash(hash)?;
+        let mut encoded = Vec::with_capacity(header.length());
+        header.encode(&mut encoded);
+        self.recorder.preimages.lock().headers.insert(hash, encoded.into());
+        Ok(header)
+    }
+}
+
+/// A [TrieHinter] that records the account and storage trie keys it is hinted into an
+/// [ExecutionWitnessRecorder], before forwarding the hints to the inner hinter.
+#[derive(Debug, Clone)]
+pub struct RecordingTrieHinter<H> {
+    /// The inner hinter.
+    inner: H,
+    /// The recorder.
+    recorder: ExecutionWitnessRecorder,
+}
+
+impl<H> RecordingTrieHinter<H> {
+    /// Consumes the hinter, returning the inner hinter.
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: TrieHinter> TrieHinter for RecordingTrieHinter<H> {
+    type Error = H::Error;
+
+    fn hint_trie_node(&self, hash: B256) -> Result<(), Self::Error> {
+        self.inner.hint_trie_node(hash)
+    }
+
+    fn hint_account_proof(&self, address: Address, block_number: u64) -> Result<(), Self::Error> {
+        self.recorder.record_key(address.as_slice());
+        self.inner.hint_account_proof(address, block_number)
+    }
+
+    fn hint_storage_proof(
+        &self,
+        address: Address,
+        slot: U256,
+        block_number: u64,
+    ) -> Result<(), Self::Error> {
+        self.recorder.record_key(address.as_slice());
+        self.recorder.record_key(&slot.to_be_bytes::<32>());
+        self.inner.hint_storage_proof(address, slot, block_number)
+    }
+
+    fn hint_execution_witness(
+        &self,
+        parent_hash: B256,
+        op_payload_attributes: &OpPayloadAttributes,
+    ) -> Result<(), Self::Error> {
+        self.inner.hint_execution_witness(parent_hash, op_payload_attributes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{StatelessL2BlockExecutor, test_utils::load_test_fixture};
+    use alloc::string::{String, ToString};
+    use alloy_consensus::Sealable;
+    use alloy_rlp::Decodable;
+    use kona_mpt::NoopTrieHinter;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    /// A [TrieDBProvider] that serves nothing but the preimages of an [ExecutionWitness].
+    struct WitnessProvider(BTreeMap<B256, Bytes>);
+
+    impl WitnessProvider {
+        fn new(witness: ExecutionWitness) -> Self {
+            let preimages = witness.state.into_iter().chain(witness.codes).chain(witness.headers);
+            Self(preimages.map(|preimage| (keccak256(&preimage), preimage)).collect())
+        }
+
+        fn get(&self, hash: B256) -> Result<&Bytes, String> {
+            self.0.get(&hash).ok_or_else(|| "Preimage not in witness".to_string())
+        }
+    }
+
+    impl TrieProvider for WitnessProvider {
+        type Error = String;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            TrieNode::decode(&mut self.get(key)?.as_ref()).map_err(|e| e.to_string())
+        }
+    }
+
+    impl TrieDBProvider for WitnessProvider {
+        fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+            self.get(code_hash).cloned()
+        }
+
+        fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+            Header::decode(&mut self.get(hash)?.as_ref()).map_err(|e| e.to_string())
+        }
+    }
+
+    #[rstest]
+    #[case::small_block(10311000)] // Unichain Mainnet
+    #[case::medium_block(132795025)] // OP Mainnet
+    #[tokio::test]
+    async fn test_witness_is_sufficient_for_execution(#[case] block_number: u64) {
+        let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata")
+            .join(format!("block-{block_number}.tar.gz"));
+        let (_fixture_dir, fixture, provider) = load_test_fixture(fixture_path).await;
+
+        let recorder = ExecutionWitnessRecorder::default();
+        let mut executor = StatelessL2BlockExecutor::builder(
+            &fixture.rollup_config,
+            recorder.provider(provider),
+            recorder.hinter(NoopTrieHinter),
+        )
+        .with_parent_header(fixture.parent_header.clone().seal_slow())
+        .build();
+        executor.execute_payload(fixture.executing_payload.clone()).unwrap();
+
+        let witness = recorder.witness();
+        assert!(!witness.state.is_empty());
+        assert!(!witness.keys.is_empty());
+        assert_eq!(
+            serde_json::from_str::<ExecutionWitness>(&serde_json::to_string(&witness).unwrap())
+                .unwrap(),
+            witness
+        );
+
+        // Re-execute the block with nothing but the witness.
+        let mut executor = StatelessL2BlockExecutor::builder(
+            &fixture.rollup_config,
+            WitnessProvider::new(witness),
+            NoopTrieHinter,
+        )
+        .with_parent_header(fixture.parent_header.seal_slow())
+        .build();
+        let artifacts = executor.execute_payload(fixture.executing_payload).unwrap();
+        assert_eq!(artifacts.block_header.hash(), fixture.expected_block_hash);
+    }
+}
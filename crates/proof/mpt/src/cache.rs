@@ -0,0 +1,221 @@
+//! Contains the [TrieNodeCache], a shared cache of decoded [TrieNode]s keyed by their hash.
+
+use crate::TrieNode;
+use alloc::sync::Arc;
+use alloy_primitives::B256;
+use core::mem::size_of;
+use lru::LruCache;
+use spin::Mutex;
+
+/// The hit and miss counters of a [TrieNodeCache].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TrieNodeCacheStats {
+    /// The number of lookups served from the cache.
+    pub hits: u64,
+    /// The number of lookups that fetched the trie node from the inner provider.
+    pub misses: u64,
+}
+
+/// The state of a [TrieNodeCache], behind its lock.
+#[derive(Debug)]
+struct CacheState {
+    /// The decoded trie nodes, by hash, in least recently used order.
+    nodes: LruCache<B256, TrieNode>,
+    /// The estimated total size in memory of the cached trie nodes.
+    size: usize,
+    /// The maximum estimated total size in memory of the cached trie nodes.
+    max_size: usize,
+    /// The hit and miss counters.
+    stats: TrieNodeCacheStats,
+}
+
+/// A cache of decoded [TrieNode]s, keyed by their hash, that is shared by all of its clones.
+///
+/// The cache is bounded by the estimated size in memory of the decoded trie nodes, which is
+/// larger than the length of their RLP encodings, e.g. a branch node holds 17 decoded children.
+/// Once it is full, the least recently used trie nodes are evicted first.
+///
+/// Trie nodes are content-addressed, so a single cache can be shared by every
+/// [TrieProvider](crate::TrieProvider) that serves nodes of the same tries, e.g. by the `TrieDB`s
+/// of consecutive blocks. The upper levels of the state trie, which are reused by every block, are
+/// then only fetched and decoded once.
+///
+/// **Example**:
+/// ```rust
+/// use kona_mpt::{TrieNode, TrieNodeCache};
+///
+/// let cache = TrieNodeCache::new(16 * 1024 * 1024);
+/// let node = cache.get_or_fetch(Default::default(), || Ok::<_, ()>(TrieNode::Empty)).unwrap();
+/// assert_eq!(node, TrieNode::Empty);
+/// assert_eq!(cache.stats().misses, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrieNodeCache {
+    /// The spin-locked state of the cache.
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl TrieNodeCache {
+    /// Creates a new [TrieNodeCache] that holds trie nodes up to an estimated total size in memory
+    /// of `max_size` bytes.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CacheState {
+                nodes: LruCache::unbounded(),
+                size: 0,
+                max_size,
+                stats: TrieNodeCacheStats::default(),
+            })),
+        }
+    }
+
+    /// Returns the cached [TrieNode] with the given hash, if any, marking it as most recently
+    /// used.
+    pub fn get(&self, hash: &B256) -> Option<TrieNode> {
+        self.state.lock().nodes.get(hash).cloned()
+    }
+
+    /// Inserts the [TrieNode] with the given hash into the cache, evicting the least recently
+    /// used trie nodes until it fits. Trie nodes larger than the cache are not inserted.
+    pub fn insert(&self, hash: B256, node: TrieNode) {
+        let mut state = self.state.lock();
+        let node_size = decoded_size(&node);
+        if node_size > state.max_size {
+            return;
+        }
+
+        if let Some(replaced) = state.nodes.put(hash, node) {
+            state.size -= decoded_size(&replaced);
+        }
+        state.size += node_size;
+        while state.size > state.max_size {
+            let Some((_, evicted)) = state.nodes.pop_lru() else {
+                break;
+            };
+            state.size -= decoded_size(&evicted);
+        }
+    }
+
+    /// Returns the cached [TrieNode] with the given hash, or fetches it with `fetch` and caches
+    /// it.
+    pub fn get_or_fetch<E>(
+        &self,
+        hash: B256,
+        fetch: impl FnOnce() -> Result<TrieNode, E>,
+    ) -> Result<TrieNode, E> {
+        {
+            let mut state = self.state.lock();
+            if let Some(node) = state.nodes.get(&hash).cloned() {
+                state.stats.hits += 1;
+                return Ok(node);
+            }
+            state.stats.misses += 1;
+        }
+
+        // The lock is not held while fetching, since fetching may reenter the cache.
+        let node = fetch()?;
+        self.insert(hash, node.clone());
+        Ok(node)
+    }
+
+    /// Returns the number of cached trie nodes.
+    pub fn len(&self) -> usize {
+        self.state.lock().nodes.len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.state.lock().nodes.is_empty()
+    }
+
+    /// Returns the estimated total size in memory of the cached trie nodes.
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+
+    /// Returns the [TrieNodeCacheStats] of the lookups made through [Self::get_or_fetch].
+    pub fn stats(&self) -> TrieNodeCacheStats {
+        self.state.lock().stats
+    }
+
+    /// Removes every trie node from the cache.
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.nodes.clear();
+        state.size = 0;
+    }
+}
+
+/// Returns an estimate of the memory held by a decoded [TrieNode], including its children.
+fn decoded_size(node: &TrieNode) -> usize {
+    size_of::<TrieNode>() +
+        match node {
+            TrieNode::Empty | TrieNode::Blinded { .. } => 0,
+            TrieNode::Leaf { prefix, value } => prefix.len() + value.len(),
+            TrieNode::Extension { prefix, node } => prefix.len() + decoded_size(node),
+            TrieNode::Branch { stack } => stack.iter().map(decoded_size).sum(),
+        }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{TrieProvider, test_util::TrieNodeProvider};
+    use alloc::{collections::BTreeMap, vec, vec::Vec};
+    use alloy_primitives::{Bytes, keccak256};
+    use alloy_rlp::Encodable;
+    use alloy_trie::Nibbles;
+
+    /// Returns a leaf node with a 32 byte value, and its hash.
+    fn leaf(byte: u8) -> (B256, Bytes) {
+        let node = TrieNode::Leaf { prefix: Nibbles::unpack([byte; 32]), value: [byte; 32].into() };
+        let mut rlp_buf = Vec::with_capacity(node.length());
+        node.encode(&mut rlp_buf);
+        (keccak256(&rlp_buf), rlp_buf.into())
+    }
+
+    #[test]
+    fn test_shared_across_providers() {
+        let (hash, encoded) = leaf(0x11);
+        let cache = TrieNodeCache::new(1024);
+        let first = TrieNodeProvider::new(BTreeMap::from([(hash, encoded)]));
+        let second = TrieNodeProvider::new(BTreeMap::new());
+
+        let node = cache.get_or_fetch(hash, || first.trie_node_by_hash(hash)).unwrap();
+        // The second provider has no preimages, and is served from the shared cache.
+        assert_eq!(cache.get_or_fetch(hash, || second.trie_node_by_hash(hash)).unwrap(), node);
+        assert_eq!(cache.stats(), TrieNodeCacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.size(), decoded_size(&node));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let leaves = [leaf(0x11), leaf(0x22), leaf(0x33)];
+        let provider = TrieNodeProvider::new(leaves.iter().cloned().collect());
+        let node_size = decoded_size(&provider.trie_node_by_hash(leaves[0].0).unwrap());
+        let cache = TrieNodeCache::new(2 * node_size);
+        let fetch = |hash| cache.get_or_fetch(hash, || provider.trie_node_by_hash(hash)).unwrap();
+
+        fetch(leaves[0].0);
+        fetch(leaves[1].0);
+        fetch(leaves[0].0);
+        fetch(leaves[2].0);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.size(), 2 * node_size);
+        assert!(cache.get(&leaves[0].0).is_some());
+        assert!(cache.get(&leaves[1].0).is_none());
+        assert!(cache.get(&leaves[2].0).is_some());
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[test]
+    fn test_decoded_size_exceeds_rlp_length() {
+        let branch = TrieNode::Branch { stack: vec![TrieNode::Empty; 17] };
+        assert_eq!(decoded_size(&branch), 18 * size_of::<TrieNode>());
+        assert!(decoded_size(&branch) > branch.length());
+    }
+}
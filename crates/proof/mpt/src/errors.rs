@@ -20,6 +20,9 @@ pub enum TrieNodeError {
     /// Key does not exist in trie.
     #[error("Key does not exist in trie.")]
     KeyNotFound,
+    /// A Merkle proof does not prove a path against the root it is verified against.
+    #[error("Invalid Merkle proof")]
+    InvalidProof,
     /// Trie node is not a leaf node.
     #[error("Trie provider error: {0}")]
     Provider(String),
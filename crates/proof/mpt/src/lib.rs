@@ -21,6 +21,12 @@ pub use node::TrieNode;
 mod list_walker;
 pub use list_walker::OrderedListWalker;
 
+mod trie_walker;
+pub use trie_walker::TrieWalker;
+
+mod cache;
+pub use cache::{TrieNodeCache, TrieNodeCacheStats};
+
 mod noop;
 pub use noop::{NoopTrieHinter, NoopTrieProvider};
 
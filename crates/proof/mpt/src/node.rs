@@ -180,6 +180,128 @@ impl TrieNode {
         }
     }
 
+    /// Generates a Merkle proof of inclusion or exclusion for the given path in the trie rooted at
+    /// Self. Preimages for blinded nodes along the path are fetched using the `fetcher`, and
+    /// persisted in the inner [TrieNode] elements.
+    ///
+    /// The proof is in the format returned by `eth_getProof`: the RLP encodings of the nodes
+    /// along the path, ordered from the root. Nodes that are inlined within their parent are not
+    /// included separately. The proof of any path in an empty trie is empty.
+    ///
+    /// ## Takes
+    /// - `self` - The root trie node
+    /// - `path` - The nibbles representation of the path to prove
+    /// - `fetcher` - The preimage fetcher for intermediate blinded nodes
+    ///
+    /// ## Returns
+    /// - `Err(_)` - Could not open the nodes along the path.
+    /// - `Ok(proof)` - The RLP encoded nodes along the path.
+    pub fn proof<F: TrieProvider>(
+        &mut self,
+        path: &Nibbles,
+        fetcher: &F,
+    ) -> TrieNodeResult<Vec<Bytes>> {
+        let mut proof = Vec::new();
+        self.unblind(fetcher)?;
+        if !matches!(self, Self::Empty) {
+            let mut rlp_buf = Vec::with_capacity(self.length());
+            self.encode(&mut rlp_buf);
+            proof.push(rlp_buf.into());
+        }
+        self.collect_proof(path, fetcher, &mut proof)?;
+        Ok(proof)
+    }
+
+    /// Appends the RLP encodings of the children of Self along the given path that are not
+    /// inlined within their parent to the `proof`.
+    fn collect_proof<F: TrieProvider>(
+        &mut self,
+        path: &Nibbles,
+        fetcher: &F,
+        proof: &mut Vec<Bytes>,
+    ) -> TrieNodeResult<()> {
+        let (child, child_path) = match self {
+            Self::Branch { stack } if !path.is_empty() => {
+                let branch_nibble = path[0] as usize;
+                (&mut stack[branch_nibble], path.slice(BRANCH_NODE_NIBBLES..))
+            }
+            Self::Extension { prefix, node } if path.starts_with(prefix) => {
+                (node.as_mut(), path.slice(prefix.len()..))
+            }
+            _ => return Ok(()),
+        };
+
+        child.unblind(fetcher)?;
+        if child.length() >= B256::ZERO.len() {
+            let mut rlp_buf = Vec::with_capacity(child.length());
+            child.encode(&mut rlp_buf);
+            proof.push(rlp_buf.into());
+        }
+        child.collect_proof(&child_path, fetcher, proof)
+    }
+
+    /// Verifies a Merkle proof of inclusion or exclusion for the given path against the given
+    /// root, in the format generated by [Self::proof] and returned by `eth_getProof`.
+    ///
+    /// ## Takes
+    /// - `root` - The root hash of the trie
+    /// - `path` - The nibbles representation of the proven path
+    /// - `proof` - The RLP encoded nodes along the path, ordered from the root
+    ///
+    /// ## Returns
+    /// - `Err(_)` - The proof is invalid for the given root and path.
+    /// - `Ok(None)` - The proof proves that the path does not exist in the trie.
+    /// - `Ok(Some(_))` - The proof proves that the path exists in the trie, with the value.
+    pub fn verify_proof(
+        root: B256,
+        path: &Nibbles,
+        proof: &[Bytes],
+    ) -> TrieNodeResult<Option<Bytes>> {
+        let mut proof = proof.iter();
+        let mut node = Self::new_blinded(root);
+        let mut path = path.clone();
+
+        let value = loop {
+            match node {
+                Self::Blinded { commitment } => {
+                    let Some(encoded) = proof.next() else {
+                        // Only the empty trie may be proven without any node.
+                        if commitment == EMPTY_ROOT_HASH {
+                            break None;
+                        }
+                        return Err(TrieNodeError::InvalidProof);
+                    };
+                    if keccak256(encoded) != commitment {
+                        return Err(TrieNodeError::InvalidProof);
+                    }
+                    node = Self::decode(&mut encoded.as_ref()).map_err(TrieNodeError::RLPError)?;
+                }
+                Self::Branch { mut stack } => {
+                    if path.is_empty() || stack.len() != BRANCH_LIST_LENGTH {
+                        return Err(TrieNodeError::InvalidProof);
+                    }
+                    node = core::mem::replace(&mut stack[path[0] as usize], Self::Empty);
+                    path = path.slice(BRANCH_NODE_NIBBLES..);
+                }
+                Self::Extension { prefix, node: child } => {
+                    if !path.starts_with(&prefix) {
+                        break None;
+                    }
+                    node = *child;
+                    path = path.slice(prefix.len()..);
+                }
+                Self::Leaf { prefix, value } => break (path == prefix).then_some(value),
+                Self::Empty => break None,
+            }
+        };
+
+        // Every node of the proof must lie along the proven path.
+        if proof.next().is_some() {
+            return Err(TrieNodeError::InvalidProof);
+        }
+        Ok(value)
+    }
+
     /// Inserts a [TrieNode] at the given path into the trie rooted at Self.
     ///
     /// ## Takes
@@ -628,7 +750,7 @@ mod test {
     use alloc::{collections::BTreeMap, vec, vec::Vec};
     use alloy_primitives::{b256, bytes, hex, keccak256};
     use alloy_rlp::{Decodable, EMPTY_STRING_CODE, Encodable};
-    use alloy_trie::{HashBuilder, Nibbles};
+    use alloy_trie::{HashBuilder, Nibbles, proof::ProofRetainer};
     use rand::prelude::IteratorRandom;
 
     #[test]
@@ -797,7 +919,104 @@ mod test {
         assert_eq!(node, expected);
     }
 
+    #[test]
+    fn test_proof_static() {
+        let keys = [[0x11; 32], [0x12; 32], [0x21; 32]];
+        let mut hb = HashBuilder::default()
+            .with_proof_retainer(ProofRetainer::from_iter(keys.iter().map(Nibbles::unpack)));
+        for key in keys {
+            hb.add_leaf(Nibbles::unpack(key), key.as_ref());
+        }
+        let root = hb.root();
+
+        // Generate the proofs from a blinded root, fetching the nodes along each path.
+        let preimages = hb.take_proof_nodes().into_inner().into_iter().fold(
+            BTreeMap::default(),
+            |mut acc, (_, value)| {
+                acc.insert(keccak256(value.as_ref()), value);
+                acc
+            },
+        );
+        let fetcher = TrieNodeProvider::new(preimages);
+        let mut blinded = TrieNode::new_blinded(root);
+
+        let included = Nibbles::unpack([0x12; 32]);
+        let proof = blinded.proof(&included, &fetcher).unwrap();
+        assert_eq!(
+            TrieNode::verify_proof(root, &included, &proof).unwrap(),
+            Some(Bytes::from([0x12; 32]))
+        );
+
+        let excluded = Nibbles::unpack([0x13; 32]);
+        let proof = blinded.proof(&excluded, &fetcher).unwrap();
+        assert_eq!(TrieNode::verify_proof(root, &excluded, &proof).unwrap(), None);
+
+        // A proof does not verify against another root, or with tampered or extra nodes.
+        assert_eq!(
+            TrieNode::verify_proof(B256::ZERO, &excluded, &proof),
+            Err(TrieNodeError::InvalidProof)
+        );
+        let mut tampered = proof.clone();
+        let mut last = tampered.pop().unwrap().to_vec();
+        *last.last_mut().unwrap() ^= 1;
+        tampered.push(last.into());
+        assert_eq!(
+            TrieNode::verify_proof(root, &excluded, &tampered),
+            Err(TrieNodeError::InvalidProof)
+        );
+        let mut extended = proof;
+        extended.push(extended[0].clone());
+        assert_eq!(
+            TrieNode::verify_proof(root, &excluded, &extended),
+            Err(TrieNodeError::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn test_proof_empty_trie() {
+        let path = Nibbles::unpack([0xFF; 32]);
+        let proof = TrieNode::Empty.proof(&path, &NoopTrieProvider).unwrap();
+        assert!(proof.is_empty());
+        assert_eq!(TrieNode::verify_proof(EMPTY_ROOT_HASH, &path, &proof).unwrap(), None);
+        assert_eq!(
+            TrieNode::verify_proof(B256::ZERO, &path, &proof),
+            Err(TrieNodeError::InvalidProof)
+        );
+    }
+
     proptest::proptest! {
+        /// Differential test for generating proofs of inclusion and exclusion from a `TrieNode`, and
+        /// verifying them with `alloy_trie`.
+        #[test]
+        fn diff_alloy_trie_proof(
+            keys in proptest::collection::vec(proptest::prelude::any::<[u8; 32]>(), 1..1024),
+            absent in proptest::collection::vec(proptest::prelude::any::<[u8; 32]>(), 1..16)
+        ) {
+            let mut node = TrieNode::Empty;
+            for key in keys.iter() {
+                node.insert(&Nibbles::unpack(key), (*key).into(), &NoopTrieProvider).unwrap();
+            }
+            let root = node.blind();
+
+            for key in keys.iter().take(16) {
+                let path = Nibbles::unpack(key);
+                let proof = node.proof(&path, &NoopTrieProvider).unwrap();
+                assert_eq!(
+                    TrieNode::verify_proof(root, &path, &proof).unwrap(),
+                    Some(Bytes::from(*key))
+                );
+                alloy_trie::proof::verify_proof(root, path, Some(key.to_vec()), proof.iter())
+                    .unwrap();
+            }
+
+            for key in absent.iter().filter(|key| !keys.contains(key)) {
+                let path = Nibbles::unpack(key);
+                let proof = node.proof(&path, &NoopTrieProvider).unwrap();
+                assert_eq!(TrieNode::verify_proof(root, &path, &proof).unwrap(), None);
+                alloy_trie::proof::verify_proof(root, path, None, proof.iter()).unwrap();
+            }
+        }
+
         /// Differential test for inserting an arbitrary number of keys into an empty `TrieNode` / `HashBuilder`.
         #[test]
         fn diff_hash_builder_insert(mut keys in proptest::collection::vec(proptest::prelude::any::<[u8; 32]>(), 1..4096)) {
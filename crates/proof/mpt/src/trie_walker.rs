@@ -0,0 +1,206 @@
+//! This module contains the [TrieWalker] struct, which allows for lazily iterating over the leaves
+//! of a Merkle Patricia Trie in key order.
+
+use crate::{TrieNode, TrieNodeError, TrieNodeResult, TrieProvider};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::Bytes;
+use alloy_trie::Nibbles;
+
+/// The number of children of a branch node.
+const BRANCH_CHILDREN: usize = 16;
+
+/// A [TrieWalker] lazily iterates over the leaves of a Merkle Patricia Trie, in ascending order of
+/// their keys, yielding the full key nibbles and the value of each leaf.
+///
+/// Blinded nodes are unblinded with the [TrieProvider] only once the walk reaches them, and
+/// subtrees that lie entirely outside of the optional `[start, end)` key range are never fetched.
+/// If a node cannot be fetched, the error is yielded and the iteration ends.
+#[derive(Debug, Clone)]
+pub struct TrieWalker<'a, F: TrieProvider> {
+    /// The preimage fetcher for blinded nodes.
+    fetcher: &'a F,
+    /// The nodes yet to be walked, with the key nibbles leading up to them. The next node to walk
+    /// is at the top of the stack.
+    stack: Vec<(Nibbles, TrieNode)>,
+    /// The inclusive lower bound of the keys to yield, if any.
+    start: Option<Nibbles>,
+    /// The exclusive upper bound of the keys to yield, if any.
+    end: Option<Nibbles>,
+}
+
+impl<'a, F> TrieWalker<'a, F>
+where
+    F: TrieProvider,
+{
+    /// Creates a new [TrieWalker] over all leaves of the trie rooted at `root`.
+    pub fn new(root: TrieNode, fetcher: &'a F) -> Self {
+        Self { fetcher, stack: vec![(Nibbles::default(), root)], start: None, end: None }
+    }
+
+    /// Only yields the leaves whose keys are greater than or equal to `start`.
+    pub fn with_start(mut self, start: Nibbles) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Only yields the leaves whose keys are less than `end`.
+    pub fn with_end(mut self, end: Nibbles) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Returns whether the given key lies within the bounds of the walk.
+    fn contains(&self, key: &Nibbles) -> bool {
+        self.start.as_ref().is_none_or(|start| key >= start) &&
+            self.end.as_ref().is_none_or(|end| key < end)
+    }
+
+    /// Returns whether every key that starts with the given path lies outside of the bounds of
+    /// the walk.
+    fn is_out_of_bounds(&self, path: &Nibbles) -> bool {
+        self.start.as_ref().is_some_and(|start| path < start && !start.starts_with(path)) ||
+            self.end.as_ref().is_some_and(|end| path >= end)
+    }
+
+    /// Pushes the node at `path` onto the stack, unless its subtree lies outside of the bounds of
+    /// the walk.
+    fn push(&mut self, path: Nibbles, node: TrieNode) {
+        if !matches!(node, TrieNode::Empty) && !self.is_out_of_bounds(&path) {
+            self.stack.push((path, node));
+        }
+    }
+}
+
+impl<F> Iterator for TrieWalker<'_, F>
+where
+    F: TrieProvider,
+{
+    type Item = TrieNodeResult<(Nibbles, Bytes)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, mut node)) = self.stack.pop() {
+            if let Err(e) = node.unblind(self.fetcher) {
+                self.stack.clear();
+                return Some(Err(e));
+            }
+
+            match node {
+                TrieNode::Leaf { prefix, value } => {
+                    let key = path.join(&prefix);
+                    if self.contains(&key) {
+                        return Some(Ok((key, value)));
+                    }
+                }
+                TrieNode::Extension { prefix, node } => {
+                    self.push(path.join(&prefix), *node);
+                }
+                TrieNode::Branch { stack } => {
+                    // Push the children in reverse, so that the lowest nibble is walked first.
+                    for (nibble, child) in stack.into_iter().take(BRANCH_CHILDREN).enumerate().rev()
+                    {
+                        let mut child_path = path.clone();
+                        child_path.push(nibble as u8);
+                        self.push(child_path, child);
+                    }
+                }
+                TrieNode::Empty => { /* Nothing to yield. */ }
+                TrieNode::Blinded { .. } => {
+                    self.stack.clear();
+                    return Some(Err(TrieNodeError::InvalidNodeType));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NoopTrieProvider, test_util::TrieNodeProvider};
+    use alloc::{collections::BTreeMap, vec::Vec};
+    use alloy_primitives::{B256, keccak256};
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+
+    #[test]
+    fn test_walk_blinded_trie() {
+        let keys = [[0x11; 32], [0x12; 32], [0x21; 32], [0xF0; 32]];
+        let mut hb = HashBuilder::default()
+            .with_proof_retainer(ProofRetainer::from_iter(keys.iter().map(Nibbles::unpack)));
+        for key in keys {
+            hb.add_leaf(Nibbles::unpack(key), key.as_ref());
+        }
+        let root = hb.root();
+
+        let preimages = hb.take_proof_nodes().into_inner().into_iter().fold(
+            BTreeMap::default(),
+            |mut acc, (_, value)| {
+                acc.insert(keccak256(value.as_ref()), value);
+                acc
+            },
+        );
+        let fetcher = TrieNodeProvider::new(preimages);
+
+        let walked = TrieWalker::new(TrieNode::new_blinded(root), &fetcher)
+            .collect::<TrieNodeResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            walked,
+            keys.iter().map(|key| (Nibbles::unpack(key), Bytes::from(*key))).collect::<Vec<_>>()
+        );
+
+        let walked = TrieWalker::new(TrieNode::new_blinded(root), &fetcher)
+            .with_start(Nibbles::unpack([0x12]))
+            .with_end(Nibbles::unpack([0x21; 32]))
+            .collect::<TrieNodeResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(walked, [(Nibbles::unpack([0x12; 32]), Bytes::from([0x12; 32]))]);
+    }
+
+    #[test]
+    fn test_walk_missing_preimage() {
+        let fetcher = TrieNodeProvider::new(BTreeMap::default());
+        let mut walker = TrieWalker::new(TrieNode::new_blinded(B256::ZERO), &fetcher);
+        assert!(matches!(walker.next(), Some(Err(TrieNodeError::Provider(_)))));
+        assert!(walker.next().is_none());
+    }
+
+    #[test]
+    fn test_walk_empty_trie() {
+        assert!(TrieWalker::new(TrieNode::Empty, &NoopTrieProvider).next().is_none());
+    }
+
+    proptest::proptest! {
+        /// Walks a trie with arbitrary keys and bounds, and checks that exactly the keys within
+        /// the bounds are yielded, in order.
+        #[test]
+        fn walk_in_bounds(
+            keys in proptest::collection::btree_set(proptest::prelude::any::<[u8; 32]>(), 1..1024),
+            start in proptest::prelude::any::<[u8; 32]>(),
+            end in proptest::prelude::any::<[u8; 32]>()
+        ) {
+            let mut node = TrieNode::Empty;
+            for key in keys.iter() {
+                node.insert(&Nibbles::unpack(key), (*key).into(), &NoopTrieProvider).unwrap();
+            }
+
+            let walked = TrieWalker::new(node.clone(), &NoopTrieProvider)
+                .map(|leaf| leaf.unwrap().0)
+                .collect::<Vec<_>>();
+            assert_eq!(walked, keys.iter().map(Nibbles::unpack).collect::<Vec<_>>());
+
+            let walked = TrieWalker::new(node, &NoopTrieProvider)
+                .with_start(Nibbles::unpack(start))
+                .with_end(Nibbles::unpack(end))
+                .map(|leaf| leaf.unwrap().0)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                walked,
+                keys.iter()
+                    .filter(|key| **key >= start && **key < end)
+                    .map(Nibbles::unpack)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
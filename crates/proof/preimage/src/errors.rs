@@ -22,6 +22,9 @@ pub enum PreimageOracleError {
     /// Buffer length mismatch.
     #[error("Buffer length mismatch. Expected {0}, got {1}.")]
     BufferLengthMismatch(usize, usize),
+    /// The preimage does not match its key.
+    #[error("Invalid preimage: {0}")]
+    InvalidPreimage(String),
     /// Other errors.
     #[error("Error in preimage server: {0}")]
     Other(String),
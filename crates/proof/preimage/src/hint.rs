@@ -3,9 +3,23 @@ use crate::{
     errors::{PreimageOracleError, PreimageOracleResult},
     traits::{HintRouter, HintWriterClient},
 };
-use alloc::{boxed::Box, format, string::String, vec};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use async_trait::async_trait;
 
+/// The flag set in the length prefix of a hint frame that carries a batch of hints rather than a
+/// single hint. Single hints never approach 2GiB in size, so the bit is unused by the unbatched
+/// format.
+///
+/// The payload of a batch frame is a 4-byte big-endian hint count, followed by each hint in the
+/// unbatched format: a 4-byte big-endian length prefix followed by the hint string.
+pub const HINT_BATCH_FLAG: u32 = 1 << 31;
+
 /// A [HintWriter] is a high-level interface to the hint channel. It provides a way to write hints
 /// to the host.
 #[derive(Debug, Clone, Copy)]
@@ -20,6 +34,27 @@ impl<C> HintWriter<C> {
     }
 }
 
+impl<C> HintWriter<C>
+where
+    C: Channel + Send + Sync,
+{
+    /// Writes a length-prefixed frame to the host, and blocks until the host acknowledges it.
+    async fn write_frame(&self, len_prefix: u32, payload: &[u8]) -> PreimageOracleResult<()> {
+        self.channel.write(len_prefix.to_be_bytes().as_ref()).await?;
+        self.channel.write(payload).await?;
+
+        trace!(target: "hint_writer", "Successfully wrote hint");
+
+        // Read the hint acknowledgement from the host.
+        let mut hint_ack = [0u8; 1];
+        self.channel.read_exact(&mut hint_ack).await?;
+
+        trace!(target: "hint_writer", "Received hint acknowledgement");
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<C> HintWriterClient for HintWriter<C>
 where
@@ -32,18 +67,30 @@ where
 
         // Form the hint into a byte buffer. The format is a 4-byte big-endian length prefix
         // followed by the hint string.
-        self.channel.write(u32::to_be_bytes(hint.len() as u32).as_ref()).await?;
-        self.channel.write(hint.as_bytes()).await?;
+        self.write_frame(hint.len() as u32, hint.as_bytes()).await
+    }
 
-        trace!(target: "hint_writer", "Successfully wrote hint");
+    /// Write a batch of hints to the host in a single frame, and block until the host acknowledges
+    /// the whole batch.
+    #[cfg(feature = "hint-batching")]
+    async fn write_batch(&self, hints: &[String]) -> PreimageOracleResult<()>
+    where
+        Self: Sync,
+    {
+        if hints.is_empty() {
+            return Ok(());
+        }
 
-        // Read the hint acknowledgement from the host.
-        let mut hint_ack = [0u8; 1];
-        self.channel.read_exact(&mut hint_ack).await?;
+        trace!(target: "hint_writer", "Writing batch of {} hints", hints.len());
 
-        trace!(target: "hint_writer", "Received hint acknowledgement");
+        let mut payload = Vec::with_capacity(4 + hints.iter().map(|h| 4 + h.len()).sum::<usize>());
+        payload.extend_from_slice(&(hints.len() as u32).to_be_bytes());
+        for hint in hints {
+            payload.extend_from_slice(&(hint.len() as u32).to_be_bytes());
+            payload.extend_from_slice(hint.as_bytes());
+        }
 
-        Ok(())
+        self.write_frame(HINT_BATCH_FLAG | payload.len() as u32, &payload).await
     }
 }
 
@@ -73,15 +120,22 @@ where
     where
         R: HintRouter + Send + Sync,
     {
-        // Read the length of the raw hint payload.
+        // Read the length of the raw hint payload. The high bit of the length prefix marks a
+        // batch of hints.
         let mut len_buf = [0u8; 4];
         self.channel.read_exact(&mut len_buf).await?;
         let len = u32::from_be_bytes(len_buf);
+        let is_batch = len & HINT_BATCH_FLAG != 0;
 
         // Read the raw hint payload.
-        let mut raw_payload = vec![0u8; len as usize];
+        let mut raw_payload = vec![0u8; (len & !HINT_BATCH_FLAG) as usize];
         self.channel.read_exact(raw_payload.as_mut_slice()).await?;
-        let payload = match String::from_utf8(raw_payload) {
+        let decoded = if is_batch {
+            decode_hint_batch(&raw_payload)
+        } else {
+            String::from_utf8(raw_payload).map(|p| vec![p]).map_err(|e| e.to_string())
+        };
+        let mut payloads = match decoded {
             Ok(p) => p,
             Err(e) => {
                 // Write back on error to prevent blocking the client.
@@ -93,10 +147,16 @@ where
             }
         };
 
-        trace!(target: "hint_reader", "Successfully read hint: \"{payload}\"");
-
-        // Route the hint
-        if let Err(e) = hint_router.route_hint(payload).await {
+        // Route the hint(s)
+        let routed = if is_batch {
+            trace!(target: "hint_reader", "Successfully read batch of {} hints", payloads.len());
+            hint_router.route_hints(payloads).await
+        } else {
+            let payload = payloads.remove(0);
+            trace!(target: "hint_reader", "Successfully read hint: \"{payload}\"");
+            hint_router.route_hint(payload).await
+        };
+        if let Err(e) = routed {
             // Write back on error to prevent blocking the client.
             self.channel.write(&[0x00]).await?;
 
@@ -113,6 +173,31 @@ where
     }
 }
 
+/// Decodes the payload of a batch frame into its hints. See [HINT_BATCH_FLAG] for the format.
+fn decode_hint_batch(mut payload: &[u8]) -> Result<Vec<String>, String> {
+    fn take<'a>(payload: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+        if payload.len() < len {
+            return Err(String::from("Hint batch is truncated"));
+        }
+        let (head, rest) = payload.split_at(len);
+        *payload = rest;
+        Ok(head)
+    }
+
+    let count = u32::from_be_bytes(take(&mut payload, 4)?.try_into().expect("Length checked"));
+    let mut hints = Vec::with_capacity((count as usize).min(payload.len() / 4));
+    for _ in 0..count {
+        let len = u32::from_be_bytes(take(&mut payload, 4)?.try_into().expect("Length checked"));
+        let hint = take(&mut payload, len as usize)?;
+        hints.push(String::from_utf8(hint.to_vec()).map_err(|e| e.to_string())?);
+    }
+
+    if !payload.is_empty() {
+        return Err(format!("Hint batch has {} trailing bytes", payload.len()));
+    }
+    Ok(hints)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -191,6 +276,70 @@ mod test {
         assert!(h.unwrap().is_err_and(|e| matches!(e, PreimageOracleError::KeyNotFound)));
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_unblock_on_bad_batch() {
+        // A batch frame claiming two hints, but only holding one.
+        let mut payload = 2u32.to_be_bytes().to_vec();
+        payload.extend_from_slice(&2u32.to_be_bytes());
+        payload.extend_from_slice(b"hi");
+
+        let hint_channel = BidirectionalChannel::new().unwrap();
+
+        let client = tokio::task::spawn(async move {
+            let len_prefix = HINT_BATCH_FLAG | payload.len() as u32;
+            hint_channel.client.write(&len_prefix.to_be_bytes()).await.unwrap();
+            hint_channel.client.write(&payload).await.unwrap();
+            let mut hint_ack = [0u8; 1];
+            hint_channel.client.read_exact(&mut hint_ack).await
+        });
+        let host = tokio::task::spawn(async move {
+            let router = TestRouter { incoming_hints: Default::default() };
+
+            let hint_reader = HintReader::new(hint_channel.host);
+            hint_reader.next_hint(&router).await
+        });
+
+        let (c, h) = tokio::join!(client, host);
+        c.unwrap().unwrap();
+        assert!(h.unwrap().is_err_and(|e| {
+            let PreimageOracleError::Other(e) = e else {
+                return false;
+            };
+            e.contains("Hint batch is truncated")
+        }));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_hint_batch_client_and_host() {
+        const MOCK_DATA: [&str; 3] = ["test-hint 0xfacade", "test-hint 0xbeef", "test-hint 0x"];
+
+        let incoming_hints = Arc::new(Mutex::new(Vec::new()));
+        let hint_channel = BidirectionalChannel::new().unwrap();
+
+        let client = tokio::task::spawn(async move {
+            let hint_writer = HintWriter::new(hint_channel.client);
+
+            hint_writer.write_batch(&MOCK_DATA.map(String::from)).await
+        });
+        let host = tokio::task::spawn({
+            let incoming_hints_ref = Arc::clone(&incoming_hints);
+            async move {
+                let router = TestRouter { incoming_hints: incoming_hints_ref.clone() };
+
+                // With `hint-batching` disabled, the batch is sent as individual hints.
+                let hint_reader = HintReader::new(hint_channel.host);
+                while incoming_hints_ref.lock().await.len() < MOCK_DATA.len() {
+                    hint_reader.next_hint(&router).await.unwrap();
+                }
+            }
+        });
+
+        let (c, h) = tokio::join!(client, host);
+        c.unwrap().unwrap();
+        h.unwrap();
+        assert_eq!(*incoming_hints.lock().await, MOCK_DATA);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_hint_client_and_host() {
         const MOCK_DATA: &str = "test-hint 0xfacade";
@@ -21,7 +21,7 @@ mod oracle;
 pub use oracle::{OracleReader, OracleServer};
 
 mod hint;
-pub use hint::{HintReader, HintWriter};
+pub use hint::{HINT_BATCH_FLAG, HintReader, HintWriter};
 
 mod traits;
 pub use traits::{
@@ -37,6 +37,23 @@ pub trait HintWriterClient {
     /// - `Ok(())` if the hint was successfully written to the host.
     /// - `Err(_)` if the hint could not be written to the host.
     async fn write(&self, hint: &str) -> PreimageOracleResult<()>;
+
+    /// Write a batch of hints to the host. By default, the hints are written one at a time.
+    /// Implementations may instead send the batch in a single write, with a single
+    /// acknowledgement from the host.
+    ///
+    /// # Returns
+    /// - `Ok(())` if all hints were successfully written to the host.
+    /// - `Err(_)` if any hint could not be written to the host.
+    async fn write_batch(&self, hints: &[String]) -> PreimageOracleResult<()>
+    where
+        Self: Sync,
+    {
+        for hint in hints {
+            self.write(hint).await?;
+        }
+        Ok(())
+    }
 }
 
 /// A [CommsClient] is a trait that combines the [PreimageOracleClient] and [HintWriterClient]
@@ -86,6 +103,25 @@ pub trait HintRouter {
     /// - `Ok(())` if the hint was successfully routed.
     /// - `Err(_)` if the hint could not be routed.
     async fn route_hint(&self, hint: String) -> PreimageOracleResult<()>;
+
+    /// Routes a batch of hints, received in a single write from the client. By default, the hints
+    /// are routed one at a time, in order.
+    ///
+    /// # Arguments
+    /// - `hints`: The hints to route.
+    ///
+    /// # Returns
+    /// - `Ok(())` if all hints were successfully routed.
+    /// - `Err(_)` if any hint could not be routed.
+    async fn route_hints(&self, hints: Vec<String>) -> PreimageOracleResult<()>
+    where
+        Self: Sync,
+    {
+        for hint in hints {
+            self.route_hint(hint).await?;
+        }
+        Ok(())
+    }
 }
 
 /// A [PreimageFetcher] is a high-level interface to fetch preimages during preimage requests.
@@ -4,7 +4,7 @@
 //! [OracleReader]: kona_preimage::OracleReader
 //! [HintWriter]: kona_preimage::HintWriter
 
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::num::NonZeroUsize;
 use kona_preimage::{
@@ -16,6 +16,13 @@ use spin::Mutex;
 /// A wrapper around an [OracleReader] and [HintWriter] that stores a configurable number of
 /// responses in an [LruCache] for quick retrieval.
 ///
+/// With the `hint-batching` feature, hints are not written to the host immediately. They are
+/// collected until a preimage is read that is not in the cache, and then written in a single
+/// [HintWriterClient::write_batch] call before the read, so that the trie and provider hints sent
+/// between two reads from the host cost a single acknowledgement, and hints followed only by
+/// cached reads cost none until the next read from the host. Otherwise, hints are written through
+/// as they are sent.
+///
 /// [OracleReader]: kona_preimage::OracleReader
 /// [HintWriter]: kona_preimage::HintWriter
 #[allow(unreachable_pub)]
@@ -31,6 +38,9 @@ where
     oracle_reader: OR,
     /// Hint writer type.
     hint_writer: HW,
+    /// The hints written since the last preimage was read from the host.
+    #[cfg(feature = "hint-batching")]
+    pending_hints: Arc<Mutex<Vec<String>>>,
 }
 
 impl<OR, HW> CachingOracle<OR, HW>
@@ -49,8 +59,20 @@ where
             ))),
             oracle_reader,
             hint_writer,
+            #[cfg(feature = "hint-batching")]
+            pending_hints: Default::default(),
         }
     }
+
+    /// Writes the pending hints to the host in a single batch.
+    #[cfg(feature = "hint-batching")]
+    async fn flush_hints(&self) -> PreimageOracleResult<()>
+    where
+        HW: Sync,
+    {
+        let hints = core::mem::take(&mut *self.pending_hints.lock());
+        self.hint_writer.write_batch(&hints).await
+    }
 }
 
 /// A trait that provides a method to flush a cache.
@@ -81,6 +103,8 @@ where
         if let Some(value) = cache_lock.get(&key) {
             Ok(value.clone())
         } else {
+            #[cfg(feature = "hint-batching")]
+            self.flush_hints().await?;
             let value = self.oracle_reader.get(key).await?;
             cache_lock.put(key, value.clone());
             Ok(value)
@@ -95,6 +119,8 @@ where
             buf.copy_from_slice(value.as_slice());
             Ok(())
         } else {
+            #[cfg(feature = "hint-batching")]
+            self.flush_hints().await?;
             self.oracle_reader.get_exact(key, buf).await?;
             cache_lock.put(key, buf.to_vec());
             Ok(())
@@ -108,7 +134,123 @@ where
     OR: PreimageOracleClient + Sync,
     HW: HintWriterClient + Sync,
 {
+    #[cfg(feature = "hint-batching")]
+    async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+        self.pending_hints.lock().push(hint.into());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "hint-batching"))]
     async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
         self.hint_writer.write(hint).await
     }
+
+    #[cfg(feature = "hint-batching")]
+    async fn write_batch(&self, hints: &[String]) -> PreimageOracleResult<()> {
+        self.pending_hints.lock().extend_from_slice(hints);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "hint-batching"))]
+    async fn write_batch(&self, hints: &[String]) -> PreimageOracleResult<()> {
+        self.hint_writer.write_batch(hints).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::l1::OracleL1ChainProvider;
+    use alloc::collections::BTreeMap;
+    use alloy_consensus::{EMPTY_ROOT_HASH, Header};
+    use alloy_primitives::keccak256;
+    use alloy_rlp::{EMPTY_STRING_CODE, Encodable};
+    use kona_derive::traits::ChainProvider;
+    use kona_preimage::errors::PreimageOracleError;
+
+    /// An in-memory oracle that records the hint frames written to it, each of which is
+    /// acknowledged by the host.
+    #[derive(Debug, Clone, Default)]
+    struct MemoryOracle {
+        preimages: Arc<Mutex<BTreeMap<PreimageKey, Vec<u8>>>>,
+        frames: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    #[async_trait]
+    impl PreimageOracleClient for MemoryOracle {
+        async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            self.preimages.lock().get(&key).cloned().ok_or(PreimageOracleError::KeyNotFound)
+        }
+
+        async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+            buf.copy_from_slice(&self.get(key).await?);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl HintWriterClient for MemoryOracle {
+        async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+            self.frames.lock().push(alloc::vec![hint.into()]);
+            Ok(())
+        }
+
+        async fn write_batch(&self, hints: &[String]) -> PreimageOracleResult<()> {
+            if !hints.is_empty() {
+                self.frames.lock().push(hints.to_vec());
+            }
+            Ok(())
+        }
+    }
+
+    /// Fetches an empty L1 block through a [CachingOracle] the way the derivation pipeline does,
+    /// and returns the oracle that the hints were written to.
+    async fn fetch_l1_block() -> MemoryOracle {
+        let header = Header {
+            receipts_root: EMPTY_ROOT_HASH,
+            transactions_root: EMPTY_ROOT_HASH,
+            ..Default::default()
+        };
+        let mut header_rlp = Vec::new();
+        header.encode(&mut header_rlp);
+        let hash = keccak256(&header_rlp);
+
+        let oracle = MemoryOracle::default();
+        oracle.preimages.lock().extend([
+            (PreimageKey::new_keccak256(*hash), header_rlp),
+            (PreimageKey::new_keccak256(*EMPTY_ROOT_HASH), alloc::vec![EMPTY_STRING_CODE]),
+        ]);
+        let caching_oracle = Arc::new(CachingOracle::new(16, oracle.clone(), oracle.clone()));
+        let mut provider = OracleL1ChainProvider::new(hash, caching_oracle);
+
+        provider.header_by_hash(hash).await.unwrap();
+        assert!(provider.receipts_by_hash(hash).await.unwrap().is_empty());
+        assert!(provider.block_info_and_transactions_by_hash(hash).await.unwrap().1.is_empty());
+        oracle
+    }
+
+    #[cfg(feature = "hint-batching")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_batches_hints_of_l1_block() {
+        let oracle = fetch_l1_block().await;
+
+        // Five hints are sent. The three that precede reads from the host are written in two
+        // acknowledged frames rather than one each, and the last two stay pending, since the reads
+        // after them were served from the cache.
+        let frames = oracle.frames.lock();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames.iter().map(Vec::len).collect::<Vec<_>>(), [1, 2]);
+        assert!(frames[1][1].starts_with("l1-receipts"));
+    }
+
+    #[cfg(not(feature = "hint-batching"))]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_writes_hints_through() {
+        let oracle = fetch_l1_block().await;
+
+        // Each of the five hints is written to the host as it is sent.
+        let frames = oracle.frames.lock();
+        assert_eq!(frames.len(), 5);
+        assert!(frames.iter().all(|frame| frame.len() == 1));
+    }
 }
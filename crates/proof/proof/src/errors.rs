@@ -1,6 +1,7 @@
 //! Error types for the proof program.
 
 use alloc::string::{String, ToString};
+use alloy_primitives::B256;
 use kona_derive::errors::{PipelineError, PipelineErrorKind};
 use kona_mpt::{OrderedListWalkerError, TrieNodeError};
 use kona_preimage::errors::PreimageOracleError;
@@ -40,6 +41,9 @@ pub enum OracleProviderError {
     /// Unknown Chain ID
     #[error("Unknown chain ID: {0}")]
     UnknownChainId(u64),
+    /// A blob failed verification against its versioned hash.
+    #[error("Blob {0} failed verification: {1}")]
+    InvalidBlob(B256, BlobVerificationError),
 }
 
 impl From<OracleProviderError> for PipelineErrorKind {
@@ -51,6 +55,20 @@ impl From<OracleProviderError> for PipelineErrorKind {
     }
 }
 
+/// Error verifying a blob against its versioned hash.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobVerificationError {
+    /// The KZG commitment of the blob does not match its versioned hash.
+    #[error("KZG commitment does not match the versioned hash")]
+    CommitmentMismatch,
+    /// A field element of the blob is not a canonical BLS12-381 scalar.
+    #[error("Field element {0} is not canonical")]
+    NonCanonicalFieldElement(usize),
+    /// The KZG proof of the blob's evaluation did not verify.
+    #[error("KZG proof did not verify")]
+    InvalidProof,
+}
+
 /// Error parsing a hint.
 #[derive(Error, Debug)]
 #[error("Hint parsing error: {_0}")]
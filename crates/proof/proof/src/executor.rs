@@ -64,9 +64,15 @@ where
 
     /// Updates the safe header.
     ///
-    /// Since the L2 block executor is stateless, on an update to the safe head,
-    /// a new executor is created with the updated header.
+    /// If the new safe head is the last block executed, the executor is kept, along with the
+    /// state it has cached. Otherwise, its caches are flushed and it continues from the new safe
+    /// head. A new executor is only created for the first safe head.
     fn update_safe_head(&mut self, header: Sealed<Header>) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.update_parent_header(header);
+            return;
+        }
+
         let mut builder = StatelessL2BlockExecutor::builder(
             self.rollup_config,
             self.trie_provider.clone(),
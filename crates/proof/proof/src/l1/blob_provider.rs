@@ -1,25 +1,69 @@
 //! Contains the concrete implementation of the [BlobProvider] trait for the client program.
 
-use crate::{HintType, errors::OracleProviderError};
+use super::kzg::{POINT_EVALUATION_ADDRESS, blob_point_evaluation_input};
+use crate::{
+    HintType,
+    errors::{BlobVerificationError, OracleProviderError},
+};
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_consensus::Blob;
 use alloy_eips::eip4844::{FIELD_ELEMENTS_PER_BLOB, IndexedBlobHash};
-use alloy_primitives::keccak256;
+use alloy_primitives::{B256, keccak256};
 use async_trait::async_trait;
 use kona_derive::traits::BlobProvider;
 use kona_preimage::{CommsClient, PreimageKey, PreimageKeyType};
 use kona_protocol::BlockInfo;
 
+/// Returns the [PreimageKey] of the whole-blob preimage of the blob with the given versioned
+/// hash.
+///
+/// Alongside the per-field-element [PreimageKeyType::Blob] preimages, hosts that support bulk blob
+/// retrieval serve the full blob as a single [PreimageKeyType::GlobalGeneric] preimage, keyed by
+/// the blob's versioned hash.
+pub fn blob_preimage_key(versioned_hash: B256) -> PreimageKey {
+    PreimageKey::new(*versioned_hash, PreimageKeyType::GlobalGeneric)
+}
+
 /// An oracle-backed blob provider.
 #[derive(Debug, Clone)]
 pub struct OracleBlobProvider<T: CommsClient> {
     oracle: Arc<T>,
+    /// Whether to fetch each blob as a single whole-blob preimage, rather than one preimage per
+    /// field element.
+    bulk_blobs: bool,
+    /// Whether to verify each blob against its versioned hash before returning it.
+    verify_blobs: bool,
 }
 
 impl<T: CommsClient> OracleBlobProvider<T> {
     /// Constructs a new `OracleBlobProvider`.
     pub const fn new(oracle: Arc<T>) -> Self {
-        Self { oracle }
+        Self { oracle, bulk_blobs: false, verify_blobs: false }
+    }
+
+    /// Sets whether blobs are fetched as a single whole-blob preimage, keyed by
+    /// [blob_preimage_key], instead of with one oracle read per field element.
+    ///
+    /// Bulk retrieval requires a host that serves whole-blob preimages, and an FPVM that supports
+    /// [PreimageKeyType::GlobalGeneric] reads. It is disabled by default.
+    ///
+    /// Unlike field element preimages, a whole-blob preimage is not bound to the blob's versioned
+    /// hash by its key, so blobs fetched in bulk are always verified, regardless of
+    /// [Self::with_blob_verification].
+    pub const fn with_bulk_blobs(mut self, bulk_blobs: bool) -> Self {
+        self.bulk_blobs = bulk_blobs;
+        self
+    }
+
+    /// Sets whether each blob is verified against its versioned hash before it is returned.
+    ///
+    /// The blob polynomial is evaluated at the blob's Fiat-Shamir challenge point in the client,
+    /// and the evaluation is checked against the blob's KZG commitment and proof with the
+    /// accelerated KZG point evaluation precompile, so that the blob data does not rely on the
+    /// integrity of the field element or whole-blob preimages. It is disabled by default.
+    pub const fn with_blob_verification(mut self, verify_blobs: bool) -> Self {
+        self.verify_blobs = verify_blobs;
+        self
     }
 
     /// Retrieves a blob from the oracle.
@@ -44,6 +88,40 @@ impl<T: CommsClient> OracleBlobProvider<T> {
         // Send a hint for the blob commitment and field elements.
         HintType::L1Blob.with_data(&[blob_req_meta.as_ref()]).send(self.oracle.as_ref()).await?;
 
+        let blob = if self.bulk_blobs {
+            self.get_whole_blob(blob_hash).await?
+        } else {
+            self.get_blob_elements(blob_hash).await?
+        };
+
+        if self.verify_blobs || self.bulk_blobs {
+            self.verify_blob(blob_hash, &blob).await?;
+        }
+
+        tracing::info!(target: "client_oracle", "Retrieved blob {blob_hash:?} from the oracle.");
+
+        Ok(blob)
+    }
+
+    /// Retrieves a hinted blob from the oracle with a single read of its whole-blob preimage.
+    async fn get_whole_blob(
+        &self,
+        blob_hash: &IndexedBlobHash,
+    ) -> Result<Blob, OracleProviderError> {
+        let mut blob = Blob::default();
+        self.oracle
+            .get_exact(blob_preimage_key(blob_hash.hash), blob.as_mut())
+            .await
+            .map_err(OracleProviderError::Preimage)?;
+        Ok(blob)
+    }
+
+    /// Retrieves a hinted blob from the oracle by reading its commitment, and each of its field
+    /// elements.
+    async fn get_blob_elements(
+        &self,
+        blob_hash: &IndexedBlobHash,
+    ) -> Result<Blob, OracleProviderError> {
         // Fetch the blob commitment.
         let mut commitment = [0u8; 48];
         self.oracle
@@ -69,10 +147,52 @@ impl<T: CommsClient> OracleBlobProvider<T> {
             blob[(i as usize) << 5..(i as usize + 1) << 5].copy_from_slice(field_element.as_ref());
         }
 
-        tracing::info!(target: "client_oracle", "Retrieved blob {blob_hash:?} from the oracle.");
-
         Ok(blob)
     }
+
+    /// Verifies a hinted blob against its versioned hash, with the KZG point evaluation
+    /// precompile.
+    async fn verify_blob(
+        &self,
+        blob_hash: &IndexedBlobHash,
+        blob: &Blob,
+    ) -> Result<(), OracleProviderError> {
+        let mut commitment = [0u8; 48];
+        self.oracle
+            .get_exact(PreimageKey::new(*blob_hash.hash, PreimageKeyType::Sha256), &mut commitment)
+            .await
+            .map_err(OracleProviderError::Preimage)?;
+
+        // The blob KZG proof is served as the field element following the last one.
+        let mut proof_key = [0u8; 80];
+        proof_key[..48].copy_from_slice(commitment.as_ref());
+        proof_key[72..].copy_from_slice(FIELD_ELEMENTS_PER_BLOB.to_be_bytes().as_ref());
+        let mut proof = [0u8; 48];
+        self.oracle
+            .get_exact(PreimageKey::new(*keccak256(proof_key), PreimageKeyType::Blob), &mut proof)
+            .await
+            .map_err(OracleProviderError::Preimage)?;
+
+        let input = blob_point_evaluation_input(blob_hash.hash, blob, &commitment, &proof)
+            .map_err(|e| OracleProviderError::InvalidBlob(blob_hash.hash, e))?;
+
+        let hint_data = [POINT_EVALUATION_ADDRESS.as_slice(), input.as_slice()];
+        HintType::L1Precompile.with_data(&hint_data).send(self.oracle.as_ref()).await?;
+        let result = self
+            .oracle
+            .get(PreimageKey::new(*keccak256(hint_data.concat()), PreimageKeyType::Precompile))
+            .await
+            .map_err(OracleProviderError::Preimage)?;
+
+        // The host prefixes the precompile output with `0x01` if the call succeeded.
+        if result.first() != Some(&1) {
+            return Err(OracleProviderError::InvalidBlob(
+                blob_hash.hash,
+                BlobVerificationError::InvalidProof,
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -91,3 +211,160 @@ impl<T: CommsClient + Sync + Send> BlobProvider for OracleBlobProvider<T> {
         Ok(blobs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hint, l1::kzg::kzg_to_versioned_hash};
+    use alloc::{collections::BTreeMap, string::ToString};
+    use alloy_eips::eip4844::builder::{SidecarBuilder, SimpleCoder};
+    use alloy_primitives::Bytes;
+    use kona_preimage::{
+        HintWriterClient, PreimageOracleClient,
+        errors::{PreimageOracleError, PreimageOracleResult},
+    };
+    use revm::{precompile::kzg_point_evaluation, primitives::Env};
+    use spin::Mutex;
+
+    /// An in-memory oracle that counts its reads, and serves the results of the KZG point
+    /// evaluation precompile calls hinted to it.
+    #[derive(Debug, Clone, Default)]
+    struct MemoryOracle {
+        preimages: Arc<Mutex<BTreeMap<PreimageKey, Bytes>>>,
+        reads: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl PreimageOracleClient for MemoryOracle {
+        async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            *self.reads.lock() += 1;
+            let preimages = self.preimages.lock();
+            preimages.get(&key).map(|p| p.to_vec()).ok_or(PreimageOracleError::KeyNotFound)
+        }
+
+        async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+            buf.copy_from_slice(&self.get(key).await?);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl HintWriterClient for MemoryOracle {
+        async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+            let hint = hint
+                .parse::<Hint<HintType>>()
+                .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+            if hint.ty == HintType::L1Precompile {
+                let input = Bytes::copy_from_slice(&hint.data[20..]);
+                let result = kzg_point_evaluation::run(&input, u64::MAX, &Env::default())
+                    .map_or(alloc::vec![0], |output| [&[1], output.bytes.as_ref()].concat());
+                self.preimages.lock().insert(
+                    PreimageKey::new(*keccak256(&hint.data), PreimageKeyType::Precompile),
+                    result.into(),
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// Returns an oracle serving a blob the way the host does, along with the blob and its hash.
+    fn blob_oracle() -> (MemoryOracle, Blob, IndexedBlobHash) {
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let sidecar = SidecarBuilder::<SimpleCoder>::from_slice(&data).build().unwrap();
+        let blob = sidecar.blobs[0];
+        let commitment = *sidecar.commitments[0];
+        let hash = IndexedBlobHash { index: 0, hash: kzg_to_versioned_hash(&commitment) };
+
+        let mut preimages = BTreeMap::new();
+        preimages.insert(
+            PreimageKey::new(*hash.hash, PreimageKeyType::Sha256),
+            Bytes::copy_from_slice(&commitment),
+        );
+        let mut field_element_key = [0u8; 80];
+        field_element_key[..48].copy_from_slice(&commitment);
+        for i in 0..=FIELD_ELEMENTS_PER_BLOB {
+            field_element_key[72..].copy_from_slice(i.to_be_bytes().as_ref());
+            let value = if i == FIELD_ELEMENTS_PER_BLOB {
+                Bytes::copy_from_slice(sidecar.proofs[0].as_slice())
+            } else {
+                Bytes::copy_from_slice(&blob[(i as usize) << 5..(i as usize + 1) << 5])
+            };
+            preimages.insert(
+                PreimageKey::new(*keccak256(field_element_key), PreimageKeyType::Blob),
+                value,
+            );
+        }
+        preimages.insert(blob_preimage_key(hash.hash), Bytes::copy_from_slice(blob.as_ref()));
+
+        (
+            MemoryOracle { preimages: Arc::new(Mutex::new(preimages)), ..Default::default() },
+            blob,
+            hash,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_by_field_elements() {
+        let (oracle, blob, hash) = blob_oracle();
+        let mut provider = OracleBlobProvider::new(Arc::new(oracle.clone()));
+
+        let blobs = provider.get_blobs(&BlockInfo::default(), &[hash]).await.unwrap();
+        assert_eq!(*blobs[0], blob);
+        assert_eq!(*oracle.reads.lock(), 1 + FIELD_ELEMENTS_PER_BLOB as usize);
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_in_bulk() {
+        let (oracle, blob, hash) = blob_oracle();
+        let mut provider = OracleBlobProvider::new(Arc::new(oracle.clone())).with_bulk_blobs(true);
+
+        let blobs = provider.get_blobs(&BlockInfo::default(), &[hash]).await.unwrap();
+        assert_eq!(*blobs[0], blob);
+        // The whole blob, and the commitment, proof and precompile result that verify it.
+        assert_eq!(*oracle.reads.lock(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_in_bulk_is_verified() {
+        let (oracle, blob, hash) = blob_oracle();
+        let mut provider = OracleBlobProvider::new(Arc::new(oracle.clone())).with_bulk_blobs(true);
+
+        let mut tampered = blob;
+        tampered[1] ^= 1;
+        oracle
+            .preimages
+            .lock()
+            .insert(blob_preimage_key(hash.hash), Bytes::copy_from_slice(tampered.as_ref()));
+
+        let err = provider.get_blobs(&BlockInfo::default(), &[hash.clone()]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            OracleProviderError::InvalidBlob(h, BlobVerificationError::InvalidProof) if h == hash.hash
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_blob() {
+        let (oracle, blob, hash) = blob_oracle();
+        let mut provider = OracleBlobProvider::new(Arc::new(oracle.clone()))
+            .with_bulk_blobs(true)
+            .with_blob_verification(true);
+
+        let blobs = provider.get_blobs(&BlockInfo::default(), &[hash.clone()]).await.unwrap();
+        assert_eq!(*blobs[0], blob);
+
+        // Tamper with the whole-blob preimage.
+        let mut tampered = blob;
+        tampered[1] ^= 1;
+        oracle
+            .preimages
+            .lock()
+            .insert(blob_preimage_key(hash.hash), Bytes::copy_from_slice(tampered.as_ref()));
+
+        let err = provider.get_blobs(&BlockInfo::default(), &[hash.clone()]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            OracleProviderError::InvalidBlob(h, BlobVerificationError::InvalidProof) if h == hash.hash
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+//! Contains the client-side half of the verification of a blob against its KZG commitment.
+//!
+//! The blob polynomial is evaluated at the [EIP-4844] Fiat-Shamir challenge point, so that the
+//! evaluation can be checked against the blob's KZG proof with a single call to the KZG point
+//! evaluation precompile, as in `verify_blob_kzg_proof`.
+//!
+//! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+
+use crate::errors::BlobVerificationError;
+use alloc::vec::Vec;
+use alloy_eips::eip4844::{
+    BYTES_PER_BLOB, Blob, FIELD_ELEMENTS_PER_BLOB, VERSIONED_HASH_VERSION_KZG,
+};
+use alloy_primitives::{Address, B256, U256, uint};
+use sha2::{Digest, Sha256};
+
+/// The address of the KZG point evaluation precompile.
+pub(crate) const POINT_EVALUATION_ADDRESS: Address = Address::with_last_byte(0x0A);
+
+/// The modulus of the BLS12-381 scalar field.
+const BLS_MODULUS: U256 =
+    uint!(0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001_U256);
+
+/// The generator of the multiplicative group of the BLS12-381 scalar field.
+const PRIMITIVE_ROOT_OF_UNITY: U256 = uint!(7_U256);
+
+/// The domain separator of the Fiat-Shamir challenge of a blob.
+const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8; 16] = b"FSBLOBVERIFY_V1_";
+
+/// Returns the versioned hash of a KZG commitment.
+pub(crate) fn kzg_to_versioned_hash(commitment: &[u8; 48]) -> B256 {
+    let mut hash = B256::from_slice(&Sha256::digest(commitment));
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// Returns the 192-byte input of the KZG point evaluation precompile that verifies the blob
+/// against its commitment and blob KZG proof.
+///
+/// Errors if the commitment does not match the versioned hash, or if the blob holds a
+/// non-canonical field element.
+pub(crate) fn blob_point_evaluation_input(
+    versioned_hash: B256,
+    blob: &Blob,
+    commitment: &[u8; 48],
+    proof: &[u8; 48],
+) -> Result<[u8; 192], BlobVerificationError> {
+    if kzg_to_versioned_hash(commitment) != versioned_hash {
+        return Err(BlobVerificationError::CommitmentMismatch);
+    }
+
+    let z = compute_challenge(blob, commitment);
+    let y = evaluate_blob_polynomial(blob, z)?;
+
+    let mut input = [0u8; 192];
+    input[..32].copy_from_slice(versioned_hash.as_slice());
+    input[32..64].copy_from_slice(&z.to_be_bytes::<32>());
+    input[64..96].copy_from_slice(&y.to_be_bytes::<32>());
+    input[96..144].copy_from_slice(commitment);
+    input[144..].copy_from_slice(proof);
+    Ok(input)
+}
+
+/// Computes the Fiat-Shamir challenge point of a blob and its commitment.
+fn compute_challenge(blob: &Blob, commitment: &[u8; 48]) -> U256 {
+    let mut hasher = Sha256::new();
+    hasher.update(FIAT_SHAMIR_PROTOCOL_DOMAIN);
+    hasher.update((FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes());
+    hasher.update(blob.as_slice());
+    hasher.update(commitment);
+    U256::from_be_slice(&hasher.finalize()).reduce_mod(BLS_MODULUS)
+}
+
+/// Evaluates the polynomial of a blob, given in evaluation form over the bit-reversed roots of
+/// unity, at the point `z`, with the barycentric formula.
+fn evaluate_blob_polynomial(blob: &Blob, z: U256) -> Result<U256, BlobVerificationError> {
+    let width = FIELD_ELEMENTS_PER_BLOB as usize;
+    let roots = bit_reversed_roots_of_unity();
+
+    let mut evaluations = Vec::with_capacity(width);
+    for (i, element) in blob.chunks_exact(BYTES_PER_BLOB / width).enumerate() {
+        let evaluation = U256::from_be_slice(element);
+        if evaluation >= BLS_MODULUS {
+            return Err(BlobVerificationError::NonCanonicalFieldElement(i));
+        }
+        if z == roots[i] {
+            return Ok(evaluation);
+        }
+        evaluations.push(evaluation);
+    }
+
+    // sum(f_i * w_i / (z - w_i)) * (z^width - 1) / width
+    let inverses = batch_inverse(roots.iter().map(|root| sub_mod(z, *root)).collect());
+    let sum =
+        evaluations.iter().zip(&roots).zip(inverses).fold(U256::ZERO, |sum, ((f, w), inv)| {
+            sum.add_mod(f.mul_mod(*w, BLS_MODULUS).mul_mod(inv, BLS_MODULUS), BLS_MODULUS)
+        });
+    let width = U256::from(width);
+    let vanishing = sub_mod(z.pow_mod(width, BLS_MODULUS), U256::from(1));
+    let width_inverse = width.inv_mod(BLS_MODULUS).expect("Width is invertible");
+
+    Ok(sum.mul_mod(vanishing, BLS_MODULUS).mul_mod(width_inverse, BLS_MODULUS))
+}
+
+/// Returns the roots of unity of the blob evaluation domain, in bit-reversed order.
+fn bit_reversed_roots_of_unity() -> Vec<U256> {
+    let width = FIELD_ELEMENTS_PER_BLOB as usize;
+    let root = PRIMITIVE_ROOT_OF_UNITY
+        .pow_mod((BLS_MODULUS - U256::from(1)) / U256::from(width), BLS_MODULUS);
+
+    let mut roots = Vec::with_capacity(width);
+    let mut current = U256::from(1);
+    for _ in 0..width {
+        roots.push(current);
+        current = current.mul_mod(root, BLS_MODULUS);
+    }
+
+    let bits = width.trailing_zeros();
+    (0..width).map(|i| roots[i.reverse_bits() >> (usize::BITS - bits)]).collect()
+}
+
+/// Inverts every element of `values`, none of which may be zero, with a single modular inversion.
+fn batch_inverse(values: Vec<U256>) -> Vec<U256> {
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = U256::from(1);
+    for value in &values {
+        prefix.push(acc);
+        acc = acc.mul_mod(*value, BLS_MODULUS);
+    }
+
+    let mut inverse = acc.inv_mod(BLS_MODULUS).expect("Values are non-zero");
+    let mut inverses = alloc::vec![U256::ZERO; values.len()];
+    for i in (0..values.len()).rev() {
+        inverses[i] = inverse.mul_mod(prefix[i], BLS_MODULUS);
+        inverse = inverse.mul_mod(values[i], BLS_MODULUS);
+    }
+    inverses
+}
+
+/// Subtracts `b` from `a`, modulo [BLS_MODULUS]. Both must be reduced.
+fn sub_mod(a: U256, b: U256) -> U256 {
+    a.add_mod(BLS_MODULUS - b, BLS_MODULUS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::builder::{SidecarBuilder, SimpleCoder};
+    use alloy_primitives::Bytes;
+    use revm::{precompile::kzg_point_evaluation, primitives::Env};
+
+    /// Returns a blob of varied data, along with its KZG commitment and blob KZG proof.
+    fn test_blob() -> (Blob, [u8; 48], [u8; 48]) {
+        let data = (0..100_000u32).map(|i| (i * 31 % 251) as u8).collect::<Vec<_>>();
+        let sidecar = SidecarBuilder::<SimpleCoder>::from_slice(&data).build().unwrap();
+        (sidecar.blobs[0], *sidecar.commitments[0], *sidecar.proofs[0])
+    }
+
+    #[test]
+    fn test_point_evaluation_input_verifies() {
+        let (blob, commitment, proof) = test_blob();
+
+        let input = blob_point_evaluation_input(
+            kzg_to_versioned_hash(&commitment),
+            &blob,
+            &commitment,
+            &proof,
+        )
+        .unwrap();
+        kzg_point_evaluation::run(&Bytes::copy_from_slice(&input), u64::MAX, &Env::default())
+            .unwrap();
+
+        // A single modified field element changes the evaluation, and fails the proof.
+        let mut tampered = blob;
+        tampered[63] ^= 1;
+        let input = blob_point_evaluation_input(
+            kzg_to_versioned_hash(&commitment),
+            &tampered,
+            &commitment,
+            &proof,
+        )
+        .unwrap();
+        assert!(
+            kzg_point_evaluation::run(&Bytes::copy_from_slice(&input), u64::MAX, &Env::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_point_evaluation_input_rejects_invalid_blobs() {
+        let commitment = [0xc0; 48];
+        let versioned_hash = kzg_to_versioned_hash(&commitment);
+
+        assert_eq!(
+            blob_point_evaluation_input(B256::ZERO, &test_blob().0, &commitment, &[0; 48]),
+            Err(BlobVerificationError::CommitmentMismatch)
+        );
+
+        let (mut blob, ..) = test_blob();
+        blob[64..96].copy_from_slice(&BLS_MODULUS.to_be_bytes::<32>());
+        assert_eq!(
+            blob_point_evaluation_input(versioned_hash, &blob, &commitment, &[0; 48]),
+            Err(BlobVerificationError::NonCanonicalFieldElement(2))
+        );
+    }
+}
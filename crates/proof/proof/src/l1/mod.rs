@@ -5,8 +5,10 @@ pub use pipeline::{
     OracleAttributesBuilder, OracleDataProvider, OracleDerivationPipeline, OraclePipeline,
 };
 
+mod kzg;
+
 mod blob_provider;
-pub use blob_provider::OracleBlobProvider;
+pub use blob_provider::{OracleBlobProvider, blob_preimage_key};
 
 mod chain_provider;
 pub use chain_provider::OracleL1ChainProvider;
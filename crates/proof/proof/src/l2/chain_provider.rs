@@ -11,7 +11,7 @@ use kona_derive::traits::L2ChainProvider;
 use kona_driver::PipelineCursor;
 use kona_executor::TrieDBProvider;
 use kona_genesis::{RollupConfig, SystemConfig};
-use kona_mpt::{OrderedListWalker, TrieHinter, TrieNode, TrieProvider};
+use kona_mpt::{OrderedListWalker, TrieHinter, TrieNode, TrieNodeCache, TrieProvider};
 use kona_preimage::{CommsClient, PreimageKey, PreimageKeyType};
 use kona_protocol::{BatchValidationProvider, L2BlockInfo, to_system_config};
 use op_alloy_consensus::{OpBlock, OpTxEnvelope};
@@ -30,12 +30,14 @@ pub struct OracleL2ChainProvider<T: CommsClient> {
     cursor: Option<Arc<RwLock<PipelineCursor>>>,
     /// The L2 chain ID to use for the provider's hints.
     chain_id: Option<u64>,
+    /// The cache of decoded trie nodes, shared by all clones of the provider.
+    trie_node_cache: Option<TrieNodeCache>,
 }
 
 impl<T: CommsClient> OracleL2ChainProvider<T> {
     /// Creates a new [OracleL2ChainProvider] with the given boot information and oracle client.
     pub const fn new(l2_head: B256, rollup_config: Arc<RollupConfig>, oracle: Arc<T>) -> Self {
-        Self { l2_head, rollup_config, oracle, cursor: None, chain_id: None }
+        Self { l2_head, rollup_config, oracle, cursor: None, chain_id: None, trie_node_cache: None }
     }
 
     /// Sets the L2 chain ID to use for the provider's hints.
@@ -43,6 +45,11 @@ impl<T: CommsClient> OracleL2ChainProvider<T> {
         self.chain_id = chain_id;
     }
 
+    /// Sets the [TrieNodeCache] that decoded trie nodes are served from and stored in.
+    pub fn set_trie_node_cache(&mut self, trie_node_cache: TrieNodeCache) {
+        self.trie_node_cache = Some(trie_node_cache);
+    }
+
     /// Updates the derivation pipeline cursor
     pub fn set_cursor(&mut self, cursor: Arc<RwLock<PipelineCursor>>) {
         self.cursor = Some(cursor);
@@ -172,19 +179,23 @@ impl<T: CommsClient> TrieProvider for OracleL2ChainProvider<T> {
     type Error = OracleProviderError;
 
     fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, OracleProviderError> {
-        // On L2, trie node preimages are stored as keccak preimage types in the oracle. We assume
-        // that a hint for these preimages has already been sent, prior to this call.
-        crate::block_on(async move {
-            TrieNode::decode(
-                &mut self
-                    .oracle
-                    .get(PreimageKey::new(*key, PreimageKeyType::Keccak256))
-                    .await
-                    .map_err(OracleProviderError::Preimage)?
-                    .as_ref(),
-            )
-            .map_err(OracleProviderError::Rlp)
-        })
+        let fetch = || {
+            // On L2, trie node preimages are stored as keccak preimage types in the oracle. We
+            // assume that a hint for these preimages has already been sent, prior to this call.
+            crate::block_on(async move {
+                TrieNode::decode(
+                    &mut self
+                        .oracle
+                        .get(PreimageKey::new(*key, PreimageKeyType::Keccak256))
+                        .await
+                        .map_err(OracleProviderError::Preimage)?
+                        .as_ref(),
+                )
+                .map_err(OracleProviderError::Rlp)
+            })
+        };
+
+        self.trie_node_cache.as_ref().map_or_else(fetch, |cache| cache.get_or_fetch(key, fetch))
     }
 }
 
@@ -139,6 +139,11 @@ pub enum ResetError {
     /// A Holocene activation temporary error.
     #[error("Holocene activation reset")]
     HoloceneActivation,
+    /// The Alt-DA challenge of a commitment whose input was already derived expired, so the
+    /// input is unavailable. The argument is the number of the L1 block the commitment was
+    /// included in.
+    #[error("Alt-DA challenge expired for a derived commitment included in L1 block {0}")]
+    AltDAChallengeExpired(u64),
 }
 
 impl ResetError {
@@ -2,6 +2,7 @@
 
 use super::{PipelineError, PipelineErrorKind};
 use alloc::string::{String, ToString};
+use alloy_primitives::Bytes;
 use thiserror::Error;
 
 /// Blob Decoding Error
@@ -51,6 +52,36 @@ impl From<BlobProviderError> for PipelineErrorKind {
     }
 }
 
+/// An error returned by the [AltDADataSource].
+///
+/// [AltDADataSource]: crate::sources::AltDADataSource
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AltDAError {
+    /// The commitment is empty.
+    #[error("Empty commitment")]
+    EmptyCommitment,
+    /// The commitment type is unknown.
+    #[error("Unknown commitment type: {0}")]
+    UnknownCommitmentType(u8),
+    /// The keccak256 commitment does not hold a 32 byte hash.
+    #[error("Invalid keccak256 commitment length: {0}")]
+    InvalidKeccakCommitmentLength(usize),
+    /// The generic commitment does not hold a DA layer byte.
+    #[error("Generic commitment is missing the DA layer byte")]
+    MissingDALayer,
+    /// The input of a commitment is unavailable, and can no longer be challenged.
+    #[error(
+        "Input of commitment {0} included in L1 block {1} is unavailable past the challenge window"
+    )]
+    MissingInputPastWindow(Bytes, u64),
+}
+
+impl From<AltDAError> for PipelineErrorKind {
+    fn from(val: AltDAError) -> Self {
+        PipelineError::Provider(val.to_string()).crit()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,4 +105,11 @@ mod tests {
             BlobProviderError::BlobDecoding(BlobDecodingError::InvalidFieldElement).into();
         assert!(matches!(err, PipelineErrorKind::Critical(_)));
     }
+
+    #[test]
+    fn test_from_altda_error() {
+        let err: PipelineErrorKind =
+            AltDAError::MissingInputPastWindow(Default::default(), Default::default()).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+    }
 }
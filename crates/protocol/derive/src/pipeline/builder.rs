@@ -6,7 +6,11 @@ use crate::{
         AttributesQueue, BatchProvider, BatchStream, ChannelProvider, ChannelReader, FrameQueue,
         L1Retrieval, L1Traversal,
     },
-    traits::{AttributesBuilder, ChainProvider, DataAvailabilityProvider, L2ChainProvider},
+    traits::{
+        AttributesBuilder, ChainProvider, DataAvailabilityProvider, DerivationEventSink,
+        L2ChainProvider,
+    },
+    types::EventSink,
 };
 use alloc::sync::Arc;
 use core::fmt::Debug;
@@ -52,6 +56,7 @@ where
     builder: Option<B>,
     origin: Option<BlockInfo>,
     rollup_config: Option<Arc<RollupConfig>>,
+    event_sink: Option<Arc<dyn DerivationEventSink>>,
 }
 
 impl<B, P, T, D> Default for PipelineBuilder<B, P, T, D>
@@ -69,6 +74,7 @@ where
             builder: None,
             origin: None,
             rollup_config: None,
+            event_sink: None,
         }
     }
 }
@@ -121,6 +127,12 @@ where
         self
     }
 
+    /// Sets the optional sink of the frame, channel, and batch events of the pipeline.
+    pub fn event_sink(mut self, event_sink: Arc<dyn DerivationEventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
     /// Builds the pipeline.
     pub fn build(self) -> DerivationPipeline<AttributesQueueStage<D, P, T, B>, T> {
         self.into()
@@ -142,18 +154,23 @@ where
         let l2_chain_provider = builder.l2_chain_provider.expect("chain_provider must be set");
         let dap_source = builder.dap_source.expect("dap_source must be set");
         let attributes_builder = builder.builder.expect("builder must be set");
+        let event_sink = builder.event_sink.map(EventSink::new).unwrap_or_default();
 
         // Compose the stage stack.
         let mut l1_traversal = L1Traversal::new(chain_provider, Arc::clone(&rollup_config));
         l1_traversal.block = Some(builder.origin.expect("origin must be set"));
         let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
-        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
-        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
+        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config))
+            .with_event_sink(event_sink.clone());
+        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue)
+            .with_event_sink(event_sink.clone());
         let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config));
         let batch_stream =
-            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone());
+            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone())
+                .with_event_sink(event_sink.clone());
         let batch_provider =
-            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone())
+                .with_event_sink(event_sink);
         let attributes =
             AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder);
 
@@ -4,8 +4,9 @@ use crate::{
     errors::{PipelineError, PipelineErrorKind},
     traits::{
         L2ChainProvider, NextAttributes, OriginAdvancer, OriginProvider, Pipeline, SignalReceiver,
+        SnapshotProvider,
     },
-    types::{ActivationSignal, PipelineResult, ResetSignal, Signal, StepResult},
+    types::{ActivationSignal, PipelineResult, PipelineSnapshot, ResetSignal, Signal, StepResult},
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -48,6 +49,29 @@ where
     }
 }
 
+impl<S, P> DerivationPipeline<S, P>
+where
+    S: NextAttributes
+        + SignalReceiver
+        + OriginProvider
+        + OriginAdvancer
+        + SnapshotProvider
+        + Debug
+        + Send,
+    P: L2ChainProvider + Send + Sync + Debug,
+{
+    /// Returns a [PipelineSnapshot] of the internal state of the pipeline and each of its stages.
+    pub fn snapshot(&self) -> PipelineSnapshot {
+        let mut snapshot = PipelineSnapshot {
+            origin: self.origin(),
+            prepared_attributes: self.prepared.len(),
+            ..Default::default()
+        };
+        self.attributes.snapshot(&mut snapshot);
+        snapshot
+    }
+}
+
 impl<S, P> OriginProvider for DerivationPipeline<S, P>
 where
     S: NextAttributes + SignalReceiver + OriginProvider + OriginAdvancer + Debug + Send,
@@ -192,7 +216,14 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{pipeline::DerivationPipeline, test_utils::*};
+    use crate::{
+        pipeline::DerivationPipeline,
+        test_utils::*,
+        types::{
+            AttributesQueueSnapshot, ChannelProviderSnapshot, FrameQueueSnapshot,
+            L1RetrievalSnapshot, L1TraversalSnapshot,
+        },
+    };
     use alloc::{string::ToString, sync::Arc};
     use alloy_rpc_types_engine::PayloadAttributes;
     use kona_genesis::{RollupConfig, SystemConfig};
@@ -240,6 +271,42 @@ mod tests {
         assert_eq!(result, Some(expected));
     }
 
+    #[test]
+    fn test_pipeline_snapshot() {
+        let mut pipeline = new_test_pipeline();
+        pipeline.prepared.push_back(default_test_payload_attributes());
+
+        let snapshot = pipeline.snapshot();
+        assert_eq!(snapshot.origin, Some(BlockInfo::default()));
+        assert_eq!(snapshot.prepared_attributes, 1);
+        assert_eq!(
+            snapshot.l1_traversal,
+            Some(L1TraversalSnapshot { origin: Some(BlockInfo::default()), done: false })
+        );
+        assert_eq!(snapshot.l1_retrieval, Some(L1RetrievalSnapshot { next: None }));
+        assert_eq!(
+            snapshot.frame_queue,
+            Some(FrameQueueSnapshot { origin: Some(BlockInfo::default()), ..Default::default() })
+        );
+        assert_eq!(
+            snapshot.channel_provider,
+            Some(ChannelProviderSnapshot {
+                origin: Some(BlockInfo::default()),
+                ..Default::default()
+            })
+        );
+        assert!(snapshot.channel_reader.is_some());
+        assert!(snapshot.batch_stream.is_some());
+        assert!(snapshot.batch_provider.is_some());
+        assert_eq!(
+            snapshot.attributes_queue,
+            Some(AttributesQueueSnapshot {
+                origin: Some(BlockInfo::default()),
+                ..Default::default()
+            })
+        );
+    }
+
     #[tokio::test]
     async fn test_derivation_pipeline_missing_block() {
         let mut pipeline = new_test_pipeline();
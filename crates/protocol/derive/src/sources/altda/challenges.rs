@@ -0,0 +1,414 @@
+//! Contains the [ChallengeTracker], which follows the challenges of [AltDACommitment]s in the DA
+//! challenge contract on L1.
+
+use crate::{
+    errors::{PipelineError, ResetError},
+    sources::AltDACommitment,
+    traits::ChainProvider,
+    types::PipelineResult,
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use alloy_consensus::{Transaction, TxReceipt};
+use alloy_primitives::{Address, B256, Bytes, TxKind};
+use alloy_sol_types::{SolCall, SolEvent, sol};
+use kona_protocol::BlockInfo;
+
+sol! {
+    /// @notice Emitted when the status of a challenge changes.
+    event ChallengeStatusChanged(
+        uint256 indexed challengedBlockNumber,
+        bytes challengedCommitment,
+        uint8 status
+    );
+
+    /// @notice Resolves an active challenge by providing the input of the challenged commitment.
+    function resolve(
+        uint256 challengedBlockNumber,
+        bytes calldata challengedCommitment,
+        bytes calldata resolveData
+    ) external;
+}
+
+/// The status of a challenge in the DA challenge contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeStatus {
+    /// The challenge is active, and the input must be resolved on L1 before the resolve window
+    /// ends.
+    Active,
+    /// The challenge was resolved by submitting the input on L1.
+    Resolved,
+    /// The challenge was not resolved within the resolve window. The input is unavailable.
+    Expired,
+}
+
+impl ChallengeStatus {
+    /// Returns the [ChallengeStatus] of the `status` of a [ChallengeStatusChanged] event.
+    const fn from_event_status(status: u8) -> Option<Self> {
+        match status {
+            1 => Some(Self::Active),
+            2 => Some(Self::Resolved),
+            3 => Some(Self::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// A challenge of an [AltDACommitment].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    /// The status of the challenge.
+    pub status: ChallengeStatus,
+    /// The number of the last L1 block in which the challenge can be resolved.
+    pub resolve_deadline: u64,
+    /// The input submitted on L1 to resolve the challenge, if it was resolved.
+    pub resolved_input: Option<Bytes>,
+}
+
+/// The availability of the input of an [AltDACommitment], according to the challenges that the
+/// [ChallengeTracker] has seen so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputAvailability {
+    /// The commitment was not challenged, and can still be challenged.
+    Challengeable,
+    /// The commitment was not challenged within the challenge window.
+    Unchallenged,
+    /// The commitment is challenged, and the challenge can still be resolved.
+    Challenged,
+    /// The challenge was resolved with the given input.
+    Resolved(Option<Bytes>),
+    /// The challenge expired. The input must be skipped.
+    Expired,
+}
+
+/// Follows the challenges of [AltDACommitment]s in the DA challenge contract, one L1 block at a
+/// time.
+///
+/// The tracker may be ahead of the L1 origin of the derivation pipeline, since it looks ahead for
+/// challenges of inputs that are missing from the DA server. Its state is kept across pipeline
+/// resets, so that the inputs of expired challenges are skipped when they are derived again. If
+/// the L1 blocks it processed are reorged out, the tracker is cleared, the pipeline is reset, and
+/// the challenges of the window before the new L1 origin are processed again.
+#[derive(Debug, Clone, Default)]
+pub struct ChallengeTracker {
+    /// The address of the DA challenge contract.
+    pub challenge_address: Address,
+    /// The number of L1 blocks after the inclusion of a commitment in which it can be challenged.
+    pub challenge_window: u64,
+    /// The number of L1 blocks after a challenge in which it can be resolved.
+    pub resolve_window: u64,
+    /// The last L1 block whose challenge events were processed.
+    pub origin: Option<BlockInfo>,
+    /// The challenges seen so far, by the encoded commitment and the number of the L1 block it was
+    /// included in.
+    pub challenges: BTreeMap<(Bytes, u64), Challenge>,
+    /// The commitments whose inputs were derived from the DA server, and that can still be
+    /// challenged or whose challenge can still expire.
+    pub derived: BTreeSet<(Bytes, u64)>,
+    /// The hashes of the processed L1 blocks that are still within the challenge and resolve
+    /// windows, by number, used to detect L1 reorgs.
+    pub processed: BTreeMap<u64, B256>,
+}
+
+impl ChallengeTracker {
+    /// Creates a new [ChallengeTracker] for the given DA challenge contract.
+    pub const fn new(
+        challenge_address: Address,
+        challenge_window: u64,
+        resolve_window: u64,
+    ) -> Self {
+        Self {
+            challenge_address,
+            challenge_window,
+            resolve_window,
+            origin: None,
+            challenges: BTreeMap::new(),
+            derived: BTreeSet::new(),
+            processed: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of L1 blocks in which a commitment can be challenged and its challenge
+    /// resolved. Challenges older than this can no longer affect the availability of an input.
+    pub const fn window(&self) -> u64 {
+        self.challenge_window + self.resolve_window
+    }
+
+    /// Clears all challenges and processed L1 blocks. The next call to [Self::advance_to]
+    /// processes the challenge events of the window before its target block again.
+    pub fn reset(&mut self) {
+        self.origin = None;
+        self.challenges.clear();
+        self.derived.clear();
+        self.processed.clear();
+    }
+
+    /// Processes the challenge events of the L1 blocks up to and including `block`, if the tracker
+    /// is not already past it. If the tracker has no origin, the challenge events of the
+    /// [Self::window] before `block` are processed first.
+    ///
+    /// Returns a [ResetError::ReorgDetected] if `block` is not the L1 block that was processed
+    /// at its height, in which case the tracker is cleared.
+    pub async fn advance_to<C: ChainProvider + Send>(
+        &mut self,
+        chain_provider: &mut C,
+        block: &BlockInfo,
+    ) -> PipelineResult<()> {
+        if let Some(&hash) = self.processed.get(&block.number).filter(|&&h| h != block.hash) {
+            self.reset();
+            return Err(ResetError::ReorgDetected(hash, block.hash).reset());
+        }
+
+        let start = block.number.saturating_sub(self.window());
+        while self.origin.is_none_or(|origin| origin.number < block.number) {
+            let number = self.origin.map_or(start, |origin| origin.number + 1);
+            let next = if number == block.number {
+                *block
+            } else {
+                chain_provider.block_info_by_number(number).await.map_err(Into::into)?
+            };
+            self.process_block(chain_provider, next).await?;
+        }
+        Ok(())
+    }
+
+    /// Processes the challenge events of the L1 block after the last processed one, to look ahead
+    /// for the challenges of a missing input.
+    pub async fn look_ahead<C: ChainProvider + Send>(
+        &mut self,
+        chain_provider: &mut C,
+    ) -> PipelineResult<()> {
+        let origin = self.origin.ok_or(PipelineError::MissingOrigin.crit())?;
+        let next =
+            chain_provider.block_info_by_number(origin.number + 1).await.map_err(Into::into)?;
+        self.process_block(chain_provider, next).await
+    }
+
+    /// Returns the [InputAvailability] of the given commitment, included in the L1 block with the
+    /// given number.
+    pub fn availability(&self, commitment: &AltDACommitment, inclusion: u64) -> InputAvailability {
+        let origin = self.origin.map(|origin| origin.number).unwrap_or_default();
+        match self.challenges.get(&(commitment.encode(), inclusion)) {
+            Some(challenge) => match challenge.status {
+                ChallengeStatus::Active => InputAvailability::Challenged,
+                ChallengeStatus::Resolved => {
+                    InputAvailability::Resolved(challenge.resolved_input.clone())
+                }
+                ChallengeStatus::Expired => InputAvailability::Expired,
+            },
+            None if origin >= inclusion + self.challenge_window => InputAvailability::Unchallenged,
+            None => InputAvailability::Challengeable,
+        }
+    }
+
+    /// Records that the input of the given commitment was derived from the DA server, so that the
+    /// pipeline is reset if the commitment is challenged and the challenge expires.
+    pub fn record_derived(&mut self, commitment: &AltDACommitment, inclusion: u64) {
+        if matches!(
+            self.availability(commitment, inclusion),
+            InputAvailability::Challengeable | InputAvailability::Challenged
+        ) {
+            self.derived.insert((commitment.encode(), inclusion));
+        }
+    }
+
+    /// Processes the challenge events of the given L1 block, and expires the challenges whose
+    /// resolve window ended before it.
+    async fn process_block<C: ChainProvider + Send>(
+        &mut self,
+        chain_provider: &mut C,
+        block: BlockInfo,
+    ) -> PipelineResult<()> {
+        if let Some(origin) = self.origin.filter(|o| o.hash != block.parent_hash) {
+            self.reset();
+            return Err(ResetError::ReorgDetected(origin.hash, block.parent_hash).reset());
+        }
+
+        let mut expired_derived = None;
+        for (key, challenge) in self.challenges.iter_mut() {
+            if challenge.status == ChallengeStatus::Active &&
+                challenge.resolve_deadline < block.number
+            {
+                challenge.status = ChallengeStatus::Expired;
+                if self.derived.remove(key) {
+                    expired_derived = Some(key.1);
+                }
+            }
+        }
+        if let Some(inclusion) = expired_derived {
+            return Err(ResetError::AltDAChallengeExpired(inclusion).reset());
+        }
+
+        // Derived commitments can no longer expire once their challenge window ended without a
+        // challenge.
+        let (challenges, challenge_window) = (&self.challenges, self.challenge_window);
+        self.derived.retain(|key| {
+            key.1 + challenge_window >= block.number ||
+                challenges.get(key).is_some_and(|c| c.status == ChallengeStatus::Active)
+        });
+
+        let receipts = chain_provider.receipts_by_hash(block.hash).await.map_err(Into::into)?;
+        let mut events = Vec::new();
+        for (index, receipt) in receipts.iter().enumerate() {
+            if !receipt.status() {
+                continue;
+            }
+
+            for log in receipt.logs() {
+                if log.address != self.challenge_address ||
+                    log.topics().first() != Some(&ChallengeStatusChanged::SIGNATURE_HASH)
+                {
+                    continue;
+                }
+                let Ok(event) = ChallengeStatusChanged::decode_log_data(&log.data, true) else {
+                    warn!(target: "altda", "Failed to decode challenge event in L1 block {}", block.number);
+                    continue;
+                };
+                let Some(status) = ChallengeStatus::from_event_status(event.status) else {
+                    continue;
+                };
+                let Ok(inclusion) = u64::try_from(event.challengedBlockNumber) else {
+                    continue;
+                };
+                events.push((index, (event.challengedCommitment, inclusion), status));
+            }
+        }
+
+        // The resolved inputs are only recovered from direct calls to the contract, so the
+        // transactions of the block are only fetched if a challenge was resolved in it.
+        let transactions = if events.iter().any(|(_, _, s)| *s == ChallengeStatus::Resolved) {
+            chain_provider
+                .block_info_and_transactions_by_hash(block.hash)
+                .await
+                .map_err(Into::into)?
+                .1
+        } else {
+            Vec::new()
+        };
+
+        for (index, key, status) in events {
+            match status {
+                ChallengeStatus::Active => {
+                    self.challenges.insert(
+                        key,
+                        Challenge {
+                            status,
+                            resolve_deadline: block.number + self.resolve_window,
+                            resolved_input: None,
+                        },
+                    );
+                }
+                ChallengeStatus::Resolved => {
+                    let resolved_input = transactions
+                        .get(index)
+                        .filter(|&tx| tx.kind() == TxKind::Call(self.challenge_address))
+                        .and_then(|tx| resolveCall::abi_decode(tx.input(), true).ok())
+                        .map(|call| call.resolveData);
+                    if resolved_input.is_none() {
+                        warn!(
+                            target: "altda",
+                            "Failed to recover the resolved input from L1 block {}",
+                            block.number
+                        );
+                    }
+
+                    let challenge = self.challenges.entry(key).or_insert(Challenge {
+                        status,
+                        resolve_deadline: block.number,
+                        resolved_input: None,
+                    });
+                    challenge.status = status;
+                    challenge.resolved_input = resolved_input;
+                }
+                ChallengeStatus::Expired => {
+                    if let Some(challenge) = self.challenges.get_mut(&key) {
+                        challenge.status = status;
+                    }
+                    if self.derived.remove(&key) {
+                        expired_derived = Some(key.1);
+                    }
+                }
+            }
+        }
+
+        self.origin = Some(block);
+        self.processed.insert(block.number, block.hash);
+        self.processed = self.processed.split_off(&block.number.saturating_sub(self.window()));
+        if let Some(inclusion) = expired_derived {
+            return Err(ResetError::AltDAChallengeExpired(inclusion).reset());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        TEST_CHALLENGE_CONTRACT, TestChainProvider, challenge_status_receipt, test_l1_block,
+    };
+    use alloc::{vec, vec::Vec};
+    use alloy_primitives::keccak256;
+
+    /// Returns a [TestChainProvider] with L1 blocks 0 to 10, without challenge events.
+    fn chain() -> TestChainProvider {
+        let mut chain = TestChainProvider::default();
+        for number in 0..=10 {
+            chain.insert_block(number, test_l1_block(number));
+            chain.insert_receipts(test_l1_block(number).hash, Vec::new());
+        }
+        chain
+    }
+
+    #[tokio::test]
+    async fn test_processes_window_before_first_block() {
+        let commitment = AltDACommitment::Keccak256(keccak256(b"input"));
+        let mut chain = chain();
+        chain.receipts.retain(|(hash, _)| *hash != test_l1_block(4).hash);
+        chain.insert_receipts(
+            test_l1_block(4).hash,
+            vec![challenge_status_receipt(&commitment, 3, 1)],
+        );
+
+        let mut tracker = ChallengeTracker::new(TEST_CHALLENGE_CONTRACT, 2, 2);
+        tracker.advance_to(&mut chain, &test_l1_block(6)).await.unwrap();
+
+        assert_eq!(tracker.origin, Some(test_l1_block(6)));
+        assert_eq!(tracker.processed.keys().copied().collect::<Vec<_>>(), [2, 3, 4, 5, 6]);
+        assert_eq!(tracker.availability(&commitment, 3), InputAvailability::Challenged);
+        assert_eq!(tracker.availability(&commitment, 4), InputAvailability::Unchallenged);
+    }
+
+    #[tokio::test]
+    async fn test_detects_reorg_of_processed_block() {
+        let mut chain = chain();
+        let mut tracker = ChallengeTracker::new(TEST_CHALLENGE_CONTRACT, 2, 2);
+        tracker.advance_to(&mut chain, &test_l1_block(3)).await.unwrap();
+
+        let reorged = BlockInfo { hash: B256::repeat_byte(0xFF), ..test_l1_block(2) };
+        assert_eq!(
+            tracker.advance_to(&mut chain, &reorged).await,
+            Err(ResetError::ReorgDetected(test_l1_block(2).hash, reorged.hash).reset())
+        );
+        assert!(tracker.origin.is_none());
+        assert!(tracker.processed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detects_reorg_on_look_ahead() {
+        let mut chain = chain();
+        let mut tracker = ChallengeTracker::new(TEST_CHALLENGE_CONTRACT, 2, 2);
+        tracker.advance_to(&mut chain, &test_l1_block(3)).await.unwrap();
+
+        let reorged = BlockInfo { parent_hash: B256::repeat_byte(0xFF), ..test_l1_block(4) };
+        chain.blocks.retain(|(number, _)| *number != 4);
+        chain.insert_block(4, reorged);
+        assert_eq!(
+            tracker.look_ahead(&mut chain).await,
+            Err(ResetError::ReorgDetected(test_l1_block(3).hash, reorged.parent_hash).reset())
+        );
+        assert!(tracker.origin.is_none());
+    }
+}
@@ -0,0 +1,125 @@
+//! Contains the [AltDACommitment] posted by the batcher in place of the batch data of Alt-DA
+//! chains.
+
+use crate::errors::AltDAError;
+use alloc::vec::Vec;
+use alloy_primitives::{B256, Bytes, keccak256};
+
+/// The version byte of batcher transaction data that holds an [AltDACommitment].
+pub const ALTDA_DERIVATION_VERSION: u8 = 0x01;
+
+/// The type byte of an [AltDACommitment::Keccak256] commitment.
+pub const KECCAK256_COMMITMENT_TYPE: u8 = 0x00;
+
+/// The type byte of an [AltDACommitment::Generic] commitment.
+pub const GENERIC_COMMITMENT_TYPE: u8 = 0x01;
+
+/// The maximum size of the input of an [AltDACommitment::Keccak256] commitment, so that it can be
+/// resolved on L1 if it is challenged.
+pub const MAX_INPUT_SIZE: usize = 130_672;
+
+/// A commitment to an input held by an Alt-DA server.
+///
+/// See: <https://specs.optimism.io/experimental/alt-da.html#input-commitment-submission>
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AltDACommitment {
+    /// The keccak256 hash of the input. Inputs of keccak256 commitments can be challenged on L1.
+    Keccak256(B256),
+    /// An opaque commitment to the input, interpreted by the DA layer.
+    Generic {
+        /// The DA layer byte.
+        da_layer: u8,
+        /// The commitment payload.
+        payload: Bytes,
+    },
+}
+
+impl AltDACommitment {
+    /// Decodes an [AltDACommitment] from its encoding, without the [ALTDA_DERIVATION_VERSION]
+    /// byte.
+    pub fn decode(data: &[u8]) -> Result<Self, AltDAError> {
+        let (&commitment_type, payload) = data.split_first().ok_or(AltDAError::EmptyCommitment)?;
+        match commitment_type {
+            KECCAK256_COMMITMENT_TYPE => {
+                if payload.len() != B256::len_bytes() {
+                    return Err(AltDAError::InvalidKeccakCommitmentLength(payload.len()));
+                }
+                Ok(Self::Keccak256(B256::from_slice(payload)))
+            }
+            GENERIC_COMMITMENT_TYPE => {
+                let (&da_layer, payload) =
+                    payload.split_first().ok_or(AltDAError::MissingDALayer)?;
+                Ok(Self::Generic { da_layer, payload: Bytes::copy_from_slice(payload) })
+            }
+            ty => Err(AltDAError::UnknownCommitmentType(ty)),
+        }
+    }
+
+    /// Returns the encoding of the [AltDACommitment], without the [ALTDA_DERIVATION_VERSION] byte.
+    /// This is the commitment that is challenged in the DA challenge contract.
+    pub fn encode(&self) -> Bytes {
+        let mut encoded = Vec::new();
+        match self {
+            Self::Keccak256(hash) => {
+                encoded.push(KECCAK256_COMMITMENT_TYPE);
+                encoded.extend_from_slice(hash.as_slice());
+            }
+            Self::Generic { da_layer, payload } => {
+                encoded.push(GENERIC_COMMITMENT_TYPE);
+                encoded.push(*da_layer);
+                encoded.extend_from_slice(payload);
+            }
+        }
+        encoded.into()
+    }
+
+    /// Returns whether the given input matches the [AltDACommitment]. Generic commitments are
+    /// opaque, and match any input.
+    pub fn verify(&self, input: &[u8]) -> bool {
+        match self {
+            Self::Keccak256(hash) => keccak256(input) == *hash,
+            Self::Generic { .. } => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_keccak256_commitment_roundtrip() {
+        let commitment = AltDACommitment::Keccak256(keccak256(b"input"));
+        let encoded = commitment.encode();
+        assert_eq!(encoded[0], KECCAK256_COMMITMENT_TYPE);
+        assert_eq!(AltDACommitment::decode(&encoded).unwrap(), commitment);
+        assert!(commitment.verify(b"input"));
+        assert!(!commitment.verify(b"other input"));
+    }
+
+    #[test]
+    fn test_generic_commitment_roundtrip() {
+        let encoded = hex!("010c0102");
+        let commitment = AltDACommitment::decode(&encoded).unwrap();
+        assert_eq!(
+            commitment,
+            AltDACommitment::Generic { da_layer: 0x0c, payload: Bytes::from_static(&[1, 2]) }
+        );
+        assert_eq!(commitment.encode().as_ref(), encoded);
+    }
+
+    #[test]
+    fn test_decode_invalid_commitments() {
+        assert_eq!(AltDACommitment::decode(&[]), Err(AltDAError::EmptyCommitment));
+        assert_eq!(AltDACommitment::decode(&[0x02]), Err(AltDAError::UnknownCommitmentType(2)));
+        assert_eq!(
+            AltDACommitment::decode(&[KECCAK256_COMMITMENT_TYPE, 0x01]),
+            Err(AltDAError::InvalidKeccakCommitmentLength(1))
+        );
+        assert_eq!(
+            AltDACommitment::decode(&[GENERIC_COMMITMENT_TYPE]),
+            Err(AltDAError::MissingDALayer)
+        );
+    }
+}
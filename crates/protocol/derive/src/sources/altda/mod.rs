@@ -0,0 +1,18 @@
+//! Contains the [AltDADataSource] and the types it uses to resolve the [AltDACommitment]s posted
+//! by the batcher of Alt-DA chains.
+//!
+//! See: <https://specs.optimism.io/experimental/alt-da.html>
+
+mod commitment;
+pub use commitment::{
+    ALTDA_DERIVATION_VERSION, AltDACommitment, GENERIC_COMMITMENT_TYPE, KECCAK256_COMMITMENT_TYPE,
+    MAX_INPUT_SIZE,
+};
+
+mod challenges;
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) use challenges::ChallengeStatusChanged;
+pub use challenges::{Challenge, ChallengeStatus, ChallengeTracker, InputAvailability};
+
+mod source;
+pub use source::AltDADataSource;
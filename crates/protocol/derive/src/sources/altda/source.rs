@@ -0,0 +1,436 @@
+//! Contains the [AltDADataSource], which is a concrete implementation of the
+//! [DataAvailabilityProvider] trait for Alt-DA chains.
+
+use crate::{
+    errors::{AltDAError, PipelineError, PipelineErrorKind},
+    sources::{
+        ALTDA_DERIVATION_VERSION, AltDACommitment, CalldataSource, ChallengeTracker,
+        InputAvailability, MAX_INPUT_SIZE,
+    },
+    traits::{AltDAProvider, ChainProvider, DataAvailabilityProvider},
+    types::PipelineResult,
+};
+use alloc::{boxed::Box, fmt::Debug};
+use alloy_primitives::{Address, Bytes};
+use async_trait::async_trait;
+use kona_genesis::RollupConfig;
+use kona_protocol::BlockInfo;
+
+/// A data source for Alt-DA chains, where the batcher posts [AltDACommitment]s to calldata in
+/// place of the batch data, and the inputs of the commitments are held by a DA server.
+///
+/// Commitments are resolved through the [AltDAProvider]. If an input is missing from the DA
+/// server, the source looks ahead on L1 for a challenge of the commitment in the DA challenge
+/// contract: inputs of resolved challenges are read from L1, and inputs of expired challenges are
+/// skipped. Generic commitments cannot be challenged, and their inputs are retried until the DA
+/// server serves them. Batcher transactions that do not hold a commitment are forwarded as is.
+///
+/// See: <https://specs.optimism.io/experimental/alt-da.html#derivation>
+#[derive(Debug, Clone)]
+pub struct AltDADataSource<C, A>
+where
+    C: ChainProvider + Send + Clone,
+    A: AltDAProvider + Send,
+{
+    /// The chain provider, used to follow the DA challenge contract.
+    pub chain_provider: C,
+    /// The client of the DA server.
+    pub altda_provider: A,
+    /// The calldata source of the commitments.
+    pub calldata_source: CalldataSource<C>,
+    /// The tracker of the challenges in the DA challenge contract.
+    pub challenges: ChallengeTracker,
+    /// The commitment whose input is being resolved, if any.
+    pub commitment: Option<AltDACommitment>,
+}
+
+impl<C, A> AltDADataSource<C, A>
+where
+    C: ChainProvider + Send + Clone + Debug,
+    A: AltDAProvider + Send + Debug,
+{
+    /// Instantiates a new [AltDADataSource].
+    pub const fn new(
+        chain_provider: C,
+        altda_provider: A,
+        calldata_source: CalldataSource<C>,
+        challenges: ChallengeTracker,
+    ) -> Self {
+        Self { chain_provider, altda_provider, calldata_source, challenges, commitment: None }
+    }
+
+    /// Instantiates a new [AltDADataSource] from parts, with the DA challenge contract and windows
+    /// of the [RollupConfig].
+    pub fn new_from_parts(provider: C, altda_provider: A, cfg: &RollupConfig) -> Self {
+        let altda = cfg.alt_da_config.clone().unwrap_or_default();
+        let challenges = ChallengeTracker::new(
+            altda.da_challenge_address.or(cfg.da_challenge_address).unwrap_or_default(),
+            altda.da_challenge_window.unwrap_or_default(),
+            altda.da_resolve_window.unwrap_or_default(),
+        );
+        Self::new(
+            provider.clone(),
+            altda_provider,
+            CalldataSource::new(provider, cfg.batch_inbox_address),
+            challenges,
+        )
+    }
+
+    /// Resolves the input of the given commitment, included in the given L1 block. Returns [None]
+    /// if the input must be skipped.
+    async fn resolve_input(
+        &mut self,
+        commitment: &AltDACommitment,
+        block_ref: &BlockInfo,
+    ) -> PipelineResult<Option<Bytes>> {
+        // Generic commitments cannot be challenged, so their input is only read from the DA
+        // server, and retried until it is served.
+        if let AltDACommitment::Generic { .. } = commitment {
+            let input = self.altda_provider.get_input(commitment).await.map_err(Into::into)?;
+            if input.is_none() {
+                warn!(target: "altda", "Input of {commitment:?} is missing from the DA server");
+                return Err(PipelineError::NotEnoughData.temp());
+            }
+            return Ok(input);
+        }
+
+        let availability = self.challenges.availability(commitment, block_ref.number);
+        match availability {
+            InputAvailability::Expired => {
+                warn!(target: "altda", "Challenge expired, skipping input of {commitment:?}");
+                return Ok(None);
+            }
+            InputAvailability::Resolved(Some(input)) => return Ok(Some(input)),
+            _ => {}
+        }
+
+        let input = self
+            .altda_provider
+            .get_input(commitment)
+            .await
+            .map_err(Into::into)?
+            .filter(|input| commitment.verify(input));
+        if let Some(input) = input {
+            self.challenges.record_derived(commitment, block_ref.number);
+            return Ok(Some(input));
+        }
+
+        match availability {
+            InputAvailability::Unchallenged | InputAvailability::Resolved(None) => {
+                Err(AltDAError::MissingInputPastWindow(commitment.encode(), block_ref.number)
+                    .into())
+            }
+            _ => {
+                // Look ahead for a challenge of the missing input, or for the end of its window.
+                self.challenges.look_ahead(&mut self.chain_provider).await?;
+                Err(PipelineError::NotEnoughData.temp())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<C, A> DataAvailabilityProvider for AltDADataSource<C, A>
+where
+    C: ChainProvider + Send + Sync + Clone + Debug,
+    A: AltDAProvider + Send + Sync + Debug,
+{
+    type Item = Bytes;
+
+    async fn next(
+        &mut self,
+        block_ref: &BlockInfo,
+        batcher_address: Address,
+    ) -> PipelineResult<Self::Item> {
+        if let Err(e) = self.challenges.advance_to(&mut self.chain_provider, block_ref).await {
+            if matches!(e, PipelineErrorKind::Reset(_)) {
+                self.clear();
+            }
+            return Err(e);
+        }
+
+        loop {
+            let commitment = match self.commitment.take() {
+                Some(commitment) => commitment,
+                None => {
+                    let data = self.calldata_source.next(block_ref, batcher_address).await?;
+                    if data.first() != Some(&ALTDA_DERIVATION_VERSION) {
+                        return Ok(data);
+                    }
+                    match AltDACommitment::decode(&data[1..]) {
+                        Ok(commitment) => commitment,
+                        Err(e) => {
+                            warn!(target: "altda", "Invalid commitment, skipping: {e}");
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            match self.resolve_input(&commitment, block_ref).await {
+                Ok(Some(input)) => {
+                    if matches!(commitment, AltDACommitment::Keccak256(_)) &&
+                        input.len() > MAX_INPUT_SIZE
+                    {
+                        warn!(target: "altda", "Input exceeds the maximum size, skipping: {}", input.len());
+                        continue;
+                    }
+                    return Ok(input);
+                }
+                Ok(None) => continue,
+                Err(e @ PipelineErrorKind::Reset(_)) => {
+                    // The pipeline derives the L1 blocks again after the reset.
+                    self.clear();
+                    return Err(e);
+                }
+                Err(e) => {
+                    // Retry the same commitment on the next call.
+                    self.commitment = Some(commitment);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.calldata_source.clear();
+        self.commitment = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::ResetError,
+        sources::altda::challenges::resolveCall,
+        test_utils::{
+            TEST_CHALLENGE_CONTRACT, TestAltDAProvider, TestChainProvider,
+            challenge_status_receipt, test_l1_block,
+        },
+    };
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::{Receipt, Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::{
+        B256, PrimitiveSignature as Signature, TxKind, U256, address, keccak256,
+    };
+    use alloy_sol_types::SolCall;
+
+    const BATCH_INBOX: Address = address!("0123456789012345678901234567890123456789");
+
+    fn tx(to: Address, input: Bytes) -> TxEnvelope {
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy { to: TxKind::Call(to), input, ..Default::default() },
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    }
+
+    /// Returns an [AltDADataSource] with a challenge and resolve window of 2 L1 blocks, over L1
+    /// blocks 0 to 10, with the given batcher data included in block 1. Returns the batcher
+    /// address along with the source.
+    fn source(
+        batcher_data: Bytes,
+        altda_provider: TestAltDAProvider,
+    ) -> (AltDADataSource<TestChainProvider, TestAltDAProvider>, Address) {
+        let batcher_tx = tx(BATCH_INBOX, batcher_data);
+        let mut chain = TestChainProvider::default();
+        chain.insert_block_with_transactions(1, test_l1_block(1), vec![batcher_tx.clone()]);
+        for number in 0..=10 {
+            if number != 1 {
+                chain.insert_block(number, test_l1_block(number));
+            }
+            chain.insert_receipts(test_l1_block(number).hash, Vec::new());
+        }
+
+        let source = AltDADataSource::new(
+            chain.clone(),
+            altda_provider,
+            CalldataSource::new(chain, BATCH_INBOX),
+            ChallengeTracker::new(TEST_CHALLENGE_CONTRACT, 2, 2),
+        );
+        (source, batcher_tx.recover_signer().unwrap())
+    }
+
+    /// Replaces the receipts of the given L1 block in the chain provider of the source.
+    fn set_receipts(
+        source: &mut AltDADataSource<TestChainProvider, TestAltDAProvider>,
+        number: u64,
+        receipts: Vec<Receipt>,
+    ) {
+        let hash = test_l1_block(number).hash;
+        source.chain_provider.receipts.retain(|(h, _)| *h != hash);
+        source.chain_provider.insert_receipts(hash, receipts);
+    }
+
+    fn commitment_data(commitment: &AltDACommitment) -> Bytes {
+        [&[ALTDA_DERIVATION_VERSION], commitment.encode().as_ref()].concat().into()
+    }
+
+    #[tokio::test]
+    async fn test_forwards_non_altda_data() {
+        let (mut source, batcher) = source(Bytes::from_static(&[0x00, 0x01]), Default::default());
+        assert_eq!(
+            source.next(&test_l1_block(1), batcher).await.unwrap(),
+            Bytes::from_static(&[0, 1])
+        );
+        assert_eq!(source.next(&test_l1_block(1), batcher).await, Err(PipelineError::Eof.temp()));
+    }
+
+    #[tokio::test]
+    async fn test_resolves_input_from_da_server() {
+        let mut altda = TestAltDAProvider::default();
+        let commitment = altda.insert_input(Bytes::from_static(b"batch"));
+        let (mut source, batcher) = source(commitment_data(&commitment), altda);
+
+        assert_eq!(
+            source.next(&test_l1_block(1), batcher).await.unwrap(),
+            Bytes::from_static(b"batch")
+        );
+        assert_eq!(source.next(&test_l1_block(1), batcher).await, Err(PipelineError::Eof.temp()));
+    }
+
+    #[tokio::test]
+    async fn test_skips_invalid_commitment() {
+        let (mut source, batcher) =
+            source(Bytes::from_static(&[ALTDA_DERIVATION_VERSION, 0x02]), Default::default());
+        assert_eq!(source.next(&test_l1_block(1), batcher).await, Err(PipelineError::Eof.temp()));
+    }
+
+    #[tokio::test]
+    async fn test_missing_input_past_challenge_window() {
+        let commitment = AltDACommitment::Keccak256(keccak256(b"batch"));
+        let (mut source, batcher) = source(commitment_data(&commitment), Default::default());
+
+        // The source looks ahead for challenges until the challenge window ends.
+        for _ in 0..2 {
+            assert_eq!(
+                source.next(&test_l1_block(1), batcher).await,
+                Err(PipelineError::NotEnoughData.temp())
+            );
+        }
+        assert!(matches!(
+            source.next(&test_l1_block(1), batcher).await,
+            Err(PipelineErrorKind::Critical(_))
+        ));
+        assert_eq!(source.challenges.origin, Some(test_l1_block(3)));
+    }
+
+    #[tokio::test]
+    async fn test_retries_missing_generic_input() {
+        let commitment =
+            AltDACommitment::Generic { da_layer: 0x0c, payload: Bytes::from_static(b"blob") };
+        let (mut source, batcher) = source(commitment_data(&commitment), Default::default());
+
+        // The missing input is retried, without looking ahead for challenges, past the challenge
+        // window.
+        for _ in 0..4 {
+            assert_eq!(
+                source.next(&test_l1_block(1), batcher).await,
+                Err(PipelineError::NotEnoughData.temp())
+            );
+        }
+        assert_eq!(source.challenges.origin, Some(test_l1_block(1)));
+
+        source.altda_provider.inputs.insert(commitment, Bytes::from_static(b"batch"));
+        assert_eq!(
+            source.next(&test_l1_block(1), batcher).await.unwrap(),
+            Bytes::from_static(b"batch")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolves_input_from_challenge() {
+        let commitment = AltDACommitment::Keccak256(keccak256(b"batch"));
+        let (mut source, batcher) = source(commitment_data(&commitment), Default::default());
+
+        // The commitment is challenged in block 2, and resolved in block 3.
+        set_receipts(&mut source, 2, vec![challenge_status_receipt(&commitment, 1, 1)]);
+        set_receipts(&mut source, 3, vec![challenge_status_receipt(&commitment, 1, 2)]);
+        let resolve = resolveCall {
+            challengedBlockNumber: U256::from(1),
+            challengedCommitment: commitment.encode(),
+            resolveData: Bytes::from_static(b"batch"),
+        };
+        let resolve_tx = tx(TEST_CHALLENGE_CONTRACT, resolve.abi_encode().into());
+        source.chain_provider.transactions.push((test_l1_block(3).hash, vec![resolve_tx]));
+
+        for _ in 0..2 {
+            assert_eq!(
+                source.next(&test_l1_block(1), batcher).await,
+                Err(PipelineError::NotEnoughData.temp())
+            );
+        }
+        assert_eq!(
+            source.next(&test_l1_block(1), batcher).await.unwrap(),
+            Bytes::from_static(b"batch")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_input_of_expired_challenge() {
+        let commitment = AltDACommitment::Keccak256(keccak256(b"batch"));
+        let (mut source, batcher) = source(commitment_data(&commitment), Default::default());
+
+        // The commitment is challenged in block 2, and the challenge expires after block 4.
+        set_receipts(&mut source, 2, vec![challenge_status_receipt(&commitment, 1, 1)]);
+
+        for _ in 0..4 {
+            assert_eq!(
+                source.next(&test_l1_block(1), batcher).await,
+                Err(PipelineError::NotEnoughData.temp())
+            );
+        }
+        assert_eq!(source.next(&test_l1_block(1), batcher).await, Err(PipelineError::Eof.temp()));
+    }
+
+    #[tokio::test]
+    async fn test_resets_on_expired_challenge_of_derived_input() {
+        let mut altda = TestAltDAProvider::default();
+        let commitment = altda.insert_input(Bytes::from_static(b"batch"));
+        let (mut source, batcher) = source(commitment_data(&commitment), altda);
+        set_receipts(&mut source, 2, vec![challenge_status_receipt(&commitment, 1, 1)]);
+
+        assert_eq!(
+            source.next(&test_l1_block(1), batcher).await.unwrap(),
+            Bytes::from_static(b"batch")
+        );
+        assert_eq!(
+            source.next(&test_l1_block(5), batcher).await,
+            Err(ResetError::AltDAChallengeExpired(1).reset())
+        );
+
+        // The input is skipped once the pipeline derives the L1 block again.
+        assert_eq!(source.next(&test_l1_block(1), batcher).await, Err(PipelineError::Eof.temp()));
+    }
+
+    #[tokio::test]
+    async fn test_resets_on_l1_reorg() {
+        let mut altda = TestAltDAProvider::default();
+        let commitment = altda.insert_input(Bytes::from_static(b"batch"));
+        let (mut source, batcher) = source(commitment_data(&commitment), altda);
+        assert_eq!(
+            source.next(&test_l1_block(1), batcher).await.unwrap(),
+            Bytes::from_static(b"batch")
+        );
+
+        let reorged = BlockInfo { hash: B256::repeat_byte(0xFF), ..test_l1_block(1) };
+        assert_eq!(
+            source.next(&reorged, batcher).await,
+            Err(ResetError::ReorgDetected(test_l1_block(1).hash, reorged.hash).reset())
+        );
+        assert!(source.challenges.origin.is_none());
+        assert!(source.commitment.is_none());
+        assert!(!source.calldata_source.open);
+    }
+
+    #[tokio::test]
+    async fn test_clear() {
+        let (mut source, _) = source(Bytes::default(), Default::default());
+        source.commitment = Some(AltDACommitment::Keccak256(B256::ZERO));
+        source.calldata_source.open = true;
+        source.clear();
+        assert!(source.commitment.is_none());
+        assert!(!source.calldata_source.open);
+    }
+}
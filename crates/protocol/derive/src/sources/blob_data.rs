@@ -24,9 +24,14 @@ pub struct BlobData {
 }
 
 impl BlobData {
+    /// Creates a new [BlobData] from the given [Blob].
+    pub fn from_blob(blob: &Blob) -> Self {
+        Self { data: Some(Bytes::copy_from_slice(blob.as_slice())), calldata: None }
+    }
+
     /// Decodes the blob into raw byte data.
     /// Returns a [BlobDecodingError] if the blob is invalid.
-    pub(crate) fn decode(&self) -> Result<Bytes, BlobDecodingError> {
+    pub fn decode(&self) -> Result<Bytes, BlobDecodingError> {
         let data = self.data.as_ref().ok_or(BlobDecodingError::MissingData)?;
 
         // Validate the blob encoding version
@@ -243,6 +248,15 @@ mod tests {
         assert_eq!(blob_data.decode(), Ok(Bytes::from(vec![0u8; 1])));
     }
 
+    #[test]
+    fn test_blob_data_from_blob_decode() {
+        let mut blob = Blob::ZERO;
+        blob[4] = 0x01;
+        blob[5] = 0xAA;
+        let blob_data = BlobData::from_blob(&blob);
+        assert_eq!(blob_data.decode(), Ok(Bytes::from(vec![0xAAu8; 1])));
+    }
+
     #[test]
     fn test_blob_data_decode_invalid_field_element() {
         let mut data = vec![0u8; alloy_eips::eip4844::BYTES_PER_BLOB + 10];
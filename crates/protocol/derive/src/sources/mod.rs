@@ -18,3 +18,12 @@ pub use blobs::BlobSource;
 
 mod calldata;
 pub use calldata::CalldataSource;
+
+mod altda;
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) use altda::ChallengeStatusChanged;
+pub use altda::{
+    ALTDA_DERIVATION_VERSION, AltDACommitment, AltDADataSource, Challenge, ChallengeStatus,
+    ChallengeTracker, GENERIC_COMMITMENT_TYPE, InputAvailability, KECCAK256_COMMITMENT_TYPE,
+    MAX_INPUT_SIZE,
+};
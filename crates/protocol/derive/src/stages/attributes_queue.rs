@@ -4,9 +4,9 @@ use crate::{
     errors::{PipelineError, ResetError},
     traits::{
         AttributesBuilder, AttributesProvider, NextAttributes, OriginAdvancer, OriginProvider,
-        SignalReceiver,
+        SignalReceiver, SnapshotProvider,
     },
-    types::{PipelineResult, Signal},
+    types::{AttributesQueueSnapshot, PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
@@ -171,6 +171,26 @@ where
     }
 }
 
+impl<P, AB> SnapshotProvider for AttributesQueue<P, AB>
+where
+    P: AttributesProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + SnapshotProvider
+        + Debug,
+    AB: AttributesBuilder + Debug,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        self.prev.snapshot(snapshot);
+        snapshot.attributes_queue = Some(AttributesQueueSnapshot {
+            origin: self.origin(),
+            batch_timestamp: self.batch.as_ref().map(|batch| batch.timestamp),
+            is_last_in_span: self.is_last_in_span,
+        });
+    }
+}
+
 #[async_trait]
 impl<P, AB> SignalReceiver for AttributesQueue<P, AB>
 where
@@ -4,8 +4,11 @@ use super::NextBatchProvider;
 use crate::{
     errors::PipelineError,
     stages::{BatchQueue, BatchValidator},
-    traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{
+        AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver,
+        SnapshotProvider,
+    },
+    types::{BatchProviderSnapshot, EventSink, PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
@@ -46,6 +49,8 @@ where
     ///
     /// Must be [None] if `prev` or `batch_queue` is [Some].
     batch_validator: Option<BatchValidator<P>>,
+    /// The sink of the batch events, passed on to the active stage.
+    event_sink: EventSink,
 }
 
 impl<P, F> BatchProvider<P, F>
@@ -55,7 +60,20 @@ where
 {
     /// Creates a new [BatchProvider] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, provider: F) -> Self {
-        Self { cfg, provider, prev: Some(prev), batch_queue: None, batch_validator: None }
+        Self {
+            cfg,
+            provider,
+            prev: Some(prev),
+            batch_queue: None,
+            batch_validator: None,
+            event_sink: EventSink::none(),
+        }
+    }
+
+    /// Sets the [EventSink] that receives the batch events of the active stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
     }
 
     /// Attempts to update the active stage of the mux.
@@ -65,16 +83,22 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.batch_validator = Some(BatchValidator::new(self.cfg.clone(), prev));
+                self.batch_validator = Some(
+                    BatchValidator::new(self.cfg.clone(), prev)
+                        .with_event_sink(self.event_sink.clone()),
+                );
             } else {
-                self.batch_queue =
-                    Some(BatchQueue::new(self.cfg.clone(), prev, self.provider.clone()));
+                self.batch_queue = Some(
+                    BatchQueue::new(self.cfg.clone(), prev, self.provider.clone())
+                        .with_event_sink(self.event_sink.clone()),
+                );
             }
         } else if self.batch_queue.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the batch queue is active and Holocene is also active, transition to the batch
             // validator.
             let batch_queue = self.batch_queue.take().expect("Must have batch queue");
-            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev);
+            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev)
+                .with_event_sink(self.event_sink.clone());
             bv.l1_blocks = batch_queue.l1_blocks;
             self.batch_validator = Some(bv);
         } else if self.batch_validator.is_some() && !self.cfg.is_holocene_active(origin.timestamp) {
@@ -83,7 +107,8 @@ where
             // until Holocene re-activates.
             let batch_validator = self.batch_validator.take().expect("Must have batch validator");
             let mut bq =
-                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone());
+                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone())
+                    .with_event_sink(self.event_sink.clone());
             bq.l1_blocks = batch_validator.l1_blocks;
             self.batch_queue = Some(bq);
         }
@@ -128,6 +153,38 @@ where
     }
 }
 
+impl<P, F> SnapshotProvider for BatchProvider<P, F>
+where
+    P: NextBatchProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + SnapshotProvider
+        + Debug,
+    F: L2ChainProvider + Clone + Debug,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        let mut batch_snapshot = BatchProviderSnapshot {
+            validator: self.batch_validator.is_some(),
+            ..Default::default()
+        };
+        if let Some(batch_validator) = self.batch_validator.as_ref() {
+            batch_validator.prev.snapshot(snapshot);
+            batch_snapshot.l1_blocks = batch_validator.l1_blocks.clone();
+        } else if let Some(batch_queue) = self.batch_queue.as_ref() {
+            batch_queue.prev.snapshot(snapshot);
+            batch_snapshot.l1_blocks = batch_queue.l1_blocks.clone();
+            batch_snapshot.buffered_batches = batch_queue.batches.len();
+            batch_snapshot.buffered_span_batches = batch_queue.next_spans.len();
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.snapshot(snapshot);
+        }
+
+        batch_snapshot.origin = self.origin();
+        snapshot.batch_provider = Some(batch_snapshot);
+    }
+}
+
 #[async_trait]
 impl<P, F> SignalReceiver for BatchProvider<P, F>
 where
@@ -4,7 +4,7 @@ use super::NextBatchProvider;
 use crate::{
     errors::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError},
     traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    types::{DerivationEvent, EventSink, PipelineResult, ResetSignal, Signal},
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
@@ -55,6 +55,8 @@ where
     pub(crate) next_spans: Vec<SingleBatch>,
     /// Used to validate the batches.
     pub(crate) fetcher: BF,
+    /// The sink of the batch events of the stage.
+    pub(crate) event_sink: EventSink,
 }
 
 impl<P, BF> BatchQueue<P, BF>
@@ -73,9 +75,16 @@ where
             batches: Default::default(),
             next_spans: Default::default(),
             fetcher,
+            event_sink: EventSink::none(),
         }
     }
 
+    /// Sets the [EventSink] that receives the batch events of the stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
+    }
+
     /// Pops the next batch from the current queued up span-batch cache.
     /// The parent is used to set the parent hash of the batch.
     /// The parent is verified when the batch is later validated.
@@ -132,6 +141,19 @@ where
             let batch = &self.batches[i];
             let validity =
                 batch.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+
+            // Buffered batches are checked again until a final decision is made on them.
+            let is_final = match validity {
+                BatchValidity::Future => self.cfg.is_holocene_active(origin.timestamp),
+                BatchValidity::Undecided => false,
+                _ => true,
+            };
+            if is_final {
+                self.event_sink.emit(|| {
+                    DerivationEvent::batch_checked(&batch.batch, batch.inclusion_block, validity)
+                });
+            }
+
             match validity {
                 BatchValidity::Future => {
                     // Drop Future batches post-holocene.
@@ -248,12 +270,25 @@ where
         let drop = validity.is_drop() ||
             (self.cfg.is_holocene_active(origin.timestamp) && validity.is_future());
         if drop {
+            self.event_sink.emit(|| {
+                DerivationEvent::batch_checked(&data.batch, data.inclusion_block, validity)
+            });
             self.prev.flush();
             return Ok(());
         } else if validity.is_outdated() {
             // If the batch is outdated, we drop it without flushing the previous stage.
+            self.event_sink.emit(|| {
+                DerivationEvent::batch_checked(&data.batch, data.inclusion_block, validity)
+            });
             return Ok(());
+        } else if !validity.is_accept() {
+            // Future and undecided batches are checked again later. They are reported on their
+            // first check, and again once a final decision is made in `derive_next_batch`.
+            self.event_sink.emit(|| {
+                DerivationEvent::batch_checked(&data.batch, data.inclusion_block, validity)
+            });
         }
+        // Accepted batches are reported once they are checked again in `derive_next_batch`.
         self.batches.push(data);
         Ok(())
     }
@@ -397,6 +432,10 @@ where
                         return Err(e);
                     }
                 };
+                self.event_sink.emit(|| DerivationEvent::SpanBatchExpanded {
+                    timestamp: sb.starting_timestamp(),
+                    batches: batches.len(),
+                });
                 self.next_spans = batches;
                 let nb = match self
                     .pop_next_batch(parent)
@@ -461,7 +500,7 @@ where
 mod tests {
     use super::*;
     use crate::test_utils::{
-        CollectingLayer, TestL2ChainProvider, TestNextBatchProvider, TraceStorage,
+        CollectingLayer, TestEventSink, TestL2ChainProvider, TestNextBatchProvider, TraceStorage,
     };
     use alloc::vec;
     use alloy_consensus::Header;
@@ -824,6 +863,59 @@ mod tests {
         assert_eq!(bq.batches.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_batch_queue_reports_buffered_batches() {
+        let cfg = Arc::new(RollupConfig { max_sequencer_drift: 700, ..Default::default() });
+        assert!(!cfg.is_holocene_active(0));
+        let batch = SingleBatch { timestamp: 100, ..Default::default() };
+        let future = SingleBatch { timestamp: 200, ..Default::default() };
+        let parent = L2BlockInfo {
+            block_info: BlockInfo { timestamp: 100, ..Default::default() },
+            ..Default::default()
+        };
+
+        let mut mock = TestNextBatchProvider::new(vec![]);
+        mock.origin = Some(BlockInfo::default());
+        let fetcher = TestL2ChainProvider::default();
+        let sink = TestEventSink::default();
+        let mut bq = BatchQueue::new(cfg, mock, fetcher)
+            .with_event_sink(EventSink::new(Arc::new(sink.clone())));
+        bq.origin = Some(BlockInfo::default());
+        bq.l1_blocks.push(BlockInfo::default());
+        bq.l1_blocks.push(BlockInfo::default());
+
+        // The pre-holocene future batch is reported when it is first checked, and the batch that
+        // is accepted once it is checked again.
+        bq.add_batch(Batch::Single(batch.clone()), parent).await.unwrap();
+        bq.add_batch(Batch::Single(future), parent).await.unwrap();
+        assert_eq!(bq.batches.len(), 2);
+        let future_checked = DerivationEvent::BatchChecked {
+            timestamp: 200,
+            span: false,
+            origin: BlockInfo::default(),
+            validity: BatchValidity::Future,
+        };
+        assert_eq!(sink.events(), [future_checked.clone()]);
+
+        // The accepted batch is reported, and the future batch stays buffered without being
+        // reported again.
+        let next = bq.derive_next_batch(false, parent).await.unwrap();
+        assert_eq!(next, Batch::Single(batch));
+        assert_eq!(bq.batches.len(), 1);
+        assert_eq!(
+            sink.events(),
+            [
+                future_checked,
+                DerivationEvent::BatchChecked {
+                    timestamp: 100,
+                    span: false,
+                    origin: BlockInfo::default(),
+                    validity: BatchValidity::Accept,
+                }
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_holocene_derive_next_batch_future() {
         let trace_store: TraceStorage = Default::default();
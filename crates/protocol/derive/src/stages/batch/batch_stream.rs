@@ -3,8 +3,10 @@
 use crate::{
     errors::{PipelineEncodingError, PipelineError},
     stages::NextBatchProvider,
-    traits::{L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider},
+    types::{
+        BatchStreamSnapshot, DerivationEvent, EventSink, PipelineResult, PipelineSnapshot, Signal,
+    },
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -50,6 +52,8 @@ where
     config: Arc<RollupConfig>,
     /// Used to validate the batches.
     fetcher: BF,
+    /// The sink of the span batch events of the stage.
+    event_sink: EventSink,
 }
 
 impl<P, BF> BatchStream<P, BF>
@@ -59,7 +63,20 @@ where
 {
     /// Create a new [BatchStream] stage.
     pub const fn new(prev: P, config: Arc<RollupConfig>, fetcher: BF) -> Self {
-        Self { prev, span: None, buffer: VecDeque::new(), config, fetcher }
+        Self {
+            prev,
+            span: None,
+            buffer: VecDeque::new(),
+            config,
+            fetcher,
+            event_sink: EventSink::none(),
+        }
+    }
+
+    /// Sets the [EventSink] that receives the span batch events of the stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
     }
 
     /// Returns if the [BatchStream] stage is active based on the
@@ -89,11 +106,14 @@ where
         l1_origins: &[BlockInfo],
     ) -> PipelineResult<()> {
         if let Some(span) = self.span.take() {
-            self.buffer.extend(
-                span.get_singular_batches(l1_origins, parent).map_err(|e| {
-                    PipelineError::BadEncoding(PipelineEncodingError::from(e)).crit()
-                })?,
-            );
+            let batches = span
+                .get_singular_batches(l1_origins, parent)
+                .map_err(|e| PipelineError::BadEncoding(PipelineEncodingError::from(e)).crit())?;
+            self.event_sink.emit(|| DerivationEvent::SpanBatchExpanded {
+                timestamp: span.starting_timestamp(),
+                batches: batches.len(),
+            });
+            self.buffer.extend(batches);
         }
         Ok(())
     }
@@ -152,6 +172,12 @@ where
                             &mut self.fetcher,
                         )
                         .await;
+                    self.event_sink.emit(|| DerivationEvent::BatchChecked {
+                        timestamp: b.starting_timestamp(),
+                        span: true,
+                        origin: batch_with_inclusion.inclusion_block,
+                        validity,
+                    });
 
                     match validity {
                         BatchValidity::Accept => self.span = Some(b),
@@ -203,6 +229,26 @@ where
     }
 }
 
+impl<P, BF> SnapshotProvider for BatchStream<P, BF>
+where
+    P: BatchStreamProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + SnapshotProvider
+        + Debug,
+    BF: L2ChainProvider + Debug,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        self.prev.snapshot(snapshot);
+        snapshot.batch_stream = Some(BatchStreamSnapshot {
+            origin: self.origin(),
+            span_batch: self.span.is_some(),
+            buffered_batches: self.buffer.len(),
+        });
+    }
+}
+
 #[async_trait]
 impl<P, BF> SignalReceiver for BatchStream<P, BF>
 where
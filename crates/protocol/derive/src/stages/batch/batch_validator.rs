@@ -5,7 +5,7 @@ use crate::{
     errors::ResetError,
     prelude::{OriginProvider, PipelineError, PipelineErrorKind},
     traits::{AttributesProvider, OriginAdvancer, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    types::{DerivationEvent, EventSink, PipelineResult, ResetSignal, Signal},
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
@@ -36,6 +36,8 @@ where
     /// If new L2 Block's L1 origin is not included in this list, fetch and
     /// push it to the list.
     pub(crate) l1_blocks: Vec<BlockInfo>,
+    /// The sink of the batch events of the stage.
+    pub(crate) event_sink: EventSink,
 }
 
 impl<P> BatchValidator<P>
@@ -44,7 +46,13 @@ where
 {
     /// Create a new [BatchValidator] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, origin: None, l1_blocks: Vec::new() }
+        Self { cfg, prev, origin: None, l1_blocks: Vec::new(), event_sink: EventSink::none() }
+    }
+
+    /// Sets the [EventSink] that receives the batch events of the stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
     }
 
     /// Returns `true` if the pipeline origin is behind the parent origin.
@@ -232,12 +240,19 @@ where
         next_batch.parent_hash = parent.block_info.hash;
 
         // Check the validity of the single batch before forwarding it.
-        match next_batch.check_batch(
+        let validity = next_batch.check_batch(
             self.cfg.as_ref(),
             self.l1_blocks.as_ref(),
             parent,
             &stage_origin,
-        ) {
+        );
+        self.event_sink.emit(|| DerivationEvent::BatchChecked {
+            timestamp: next_batch.timestamp,
+            span: false,
+            origin: stage_origin,
+            validity,
+        });
+        match validity {
             BatchValidity::Accept => {
                 info!(target: "batch-validator", "Found next batch (epoch #{})", next_batch.epoch_num);
                 Ok(next_batch)
@@ -312,15 +327,15 @@ mod test {
     use crate::{
         errors::{PipelineError, PipelineErrorKind, ResetError},
         stages::{BatchValidator, NextBatchProvider},
-        test_utils::{CollectingLayer, TestNextBatchProvider, TraceStorage},
+        test_utils::{CollectingLayer, TestEventSink, TestNextBatchProvider, TraceStorage},
         traits::{AttributesProvider, OriginAdvancer, SignalReceiver},
-        types::{PipelineResult, ResetSignal, Signal},
+        types::{DerivationEvent, EventSink, PipelineResult, ResetSignal, Signal},
     };
     use alloc::{sync::Arc, vec, vec::Vec};
     use alloy_eips::{BlockNumHash, NumHash};
     use alloy_primitives::B256;
     use kona_genesis::{HardForkConfig, RollupConfig};
-    use kona_protocol::{Batch, BlockInfo, L2BlockInfo, SingleBatch, SpanBatch};
+    use kona_protocol::{Batch, BatchValidity, BlockInfo, L2BlockInfo, SingleBatch, SpanBatch};
     use tracing::Level;
     use tracing_subscriber::layer::SubscriberExt;
 
@@ -538,6 +553,44 @@ mod test {
         assert_eq!(batch, produced_batch);
     }
 
+    #[tokio::test]
+    async fn test_batch_validator_emits_batch_checked() {
+        let cfg = Arc::new(RollupConfig {
+            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            block_time: 2,
+            max_sequencer_drift: 700,
+            ..Default::default()
+        });
+        let batch = SingleBatch { epoch_num: 2, timestamp: 4, ..Default::default() };
+        let parent = L2BlockInfo {
+            l1_origin: BlockNumHash { number: 0, ..Default::default() },
+            block_info: BlockInfo { timestamp: 2, ..Default::default() },
+            ..Default::default()
+        };
+        let origin = BlockInfo { number: 1, ..Default::default() };
+
+        let mut mock = TestNextBatchProvider::new(vec![Ok(Batch::Single(batch))]);
+        mock.origin = Some(origin);
+        let sink = TestEventSink::default();
+        let mut bv =
+            BatchValidator::new(cfg, mock).with_event_sink(EventSink::new(Arc::new(sink.clone())));
+        bv.signal(Signal::Reset(ResetSignal { l1_origin: origin, ..Default::default() }))
+            .await
+            .unwrap();
+        bv.l1_blocks.push(origin);
+
+        bv.next_batch(parent).await.unwrap();
+        assert_eq!(
+            sink.events(),
+            [DerivationEvent::BatchChecked {
+                timestamp: 4,
+                span: false,
+                origin,
+                validity: BatchValidity::Accept,
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn test_batch_validator_next_batch_sequence_window_expired() {
         let trace_store: TraceStorage = Default::default();
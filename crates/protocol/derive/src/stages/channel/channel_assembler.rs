@@ -4,7 +4,7 @@ use super::{ChannelReaderProvider, NextFrameProvider};
 use crate::{
     prelude::{OriginProvider, PipelineError},
     traits::{OriginAdvancer, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{DerivationEvent, EventSink, FrameDropReason, PipelineResult, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::{Bytes, hex};
@@ -32,6 +32,8 @@ where
     pub(crate) prev: P,
     /// The current [Channel] being assembled.
     pub(crate) channel: Option<Channel>,
+    /// The sink of the frame and channel events of the stage.
+    pub(crate) event_sink: EventSink,
 }
 
 impl<P> ChannelAssembler<P>
@@ -40,7 +42,13 @@ where
 {
     /// Creates a new [ChannelAssembler] stage with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, channel: None }
+        Self { cfg, prev, channel: None, event_sink: EventSink::none() }
+    }
+
+    /// Sets the [EventSink] that receives the frame and channel events of the stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
     }
 
     /// Returns whether or not the channel currently being assembled has timed out.
@@ -76,6 +84,11 @@ where
                     origin.number,
                     channel.open_block_number()
                 );
+                self.event_sink.emit(|| DerivationEvent::ChannelTimedOut {
+                    channel_id: channel.id(),
+                    open_block_number: channel.open_block_number(),
+                    origin,
+                });
                 self.channel = None;
             }
         }
@@ -91,6 +104,8 @@ where
                 hex::encode(next_frame.id),
                 origin.number
             );
+            self.event_sink
+                .emit(|| DerivationEvent::ChannelOpened { channel_id: next_frame.id, origin });
             self.channel = Some(Channel::new(next_frame.id, origin));
         }
 
@@ -104,6 +119,7 @@ where
                 hex::encode(channel.id()),
                 origin.number
             );
+            let (frame_id, frame_number) = (next_frame.id, next_frame.number);
             if channel.add_frame(next_frame, origin).is_err() {
                 error!(
                     target: "channel-assembler",
@@ -111,8 +127,18 @@ where
                     hex::encode(channel.id()),
                     origin.number
                 );
+                self.event_sink.emit(|| DerivationEvent::FrameDropped {
+                    channel_id: frame_id,
+                    frame_number,
+                    reason: FrameDropReason::Rejected,
+                });
                 return Err(PipelineError::NotEnoughData.temp());
             }
+            self.event_sink.emit(|| DerivationEvent::FrameAccepted {
+                channel_id: frame_id,
+                frame_number,
+                origin,
+            });
 
             let max_rlp_bytes_per_channel = if self.cfg.is_fjord_active(origin.timestamp) {
                 MAX_RLP_BYTES_PER_CHANNEL_FJORD
@@ -126,6 +152,10 @@ where
                     hex::encode(channel.id()),
                     channel.size()
                 );
+                self.event_sink.emit(|| DerivationEvent::ChannelPruned {
+                    channel_id: channel.id(),
+                    size: channel.size(),
+                });
                 self.channel = None;
                 return Err(PipelineError::NotEnoughData.temp());
             }
@@ -145,6 +175,12 @@ where
                 self.channel = None;
                 return Ok(Some(channel_bytes));
             }
+        } else {
+            self.event_sink.emit(|| DerivationEvent::FrameDropped {
+                channel_id: next_frame.id,
+                frame_number: next_frame.number,
+                reason: FrameDropReason::NoOpenChannel,
+            });
         }
 
         Err(PipelineError::NotEnoughData.temp())
@@ -5,7 +5,7 @@ use crate::{
     errors::{PipelineError, PipelineErrorKind},
     stages::ChannelReaderProvider,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{DerivationEvent, EventSink, FrameDropReason, PipelineResult, Signal},
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::{Bytes, hex, map::HashMap};
@@ -44,6 +44,8 @@ where
     pub(crate) channel_queue: VecDeque<ChannelId>,
     /// The previous stage of the derivation pipeline.
     pub(crate) prev: P,
+    /// The sink of the frame and channel events of the stage.
+    pub(crate) event_sink: EventSink,
 }
 
 impl<P> ChannelBank<P>
@@ -52,7 +54,19 @@ where
 {
     /// Create a new [ChannelBank] stage.
     pub fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, channels: HashMap::default(), channel_queue: VecDeque::new(), prev }
+        Self {
+            cfg,
+            channels: HashMap::default(),
+            channel_queue: VecDeque::new(),
+            prev,
+            event_sink: EventSink::none(),
+        }
+    }
+
+    /// Sets the [EventSink] that receives the frame and channel events of the stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
     }
 
     /// Returns the size of the channel bank by accumulating over all channels.
@@ -75,6 +89,8 @@ where
                 self.channel_queue.pop_front().ok_or(PipelineError::ChannelProviderEmpty.crit())?;
             let channel = self.channels.remove(&id).ok_or(PipelineError::ChannelNotFound.crit())?;
             total_size -= channel.size();
+            self.event_sink
+                .emit(|| DerivationEvent::ChannelPruned { channel_id: id, size: channel.size() });
         }
         Ok(())
     }
@@ -88,6 +104,8 @@ where
             Some(c) => c,
             None => {
                 let channel = Channel::new(frame.id, origin);
+                self.event_sink
+                    .emit(|| DerivationEvent::ChannelOpened { channel_id: frame.id, origin });
                 self.channel_queue.push_back(frame.id);
                 self.channels.insert(frame.id, channel);
                 self.channels.get_mut(&frame.id).expect("Channel must be in queue")
@@ -102,15 +120,30 @@ where
                 target: "channel-bank",
                 "Channel (ID: {}) timed out", hex::encode(frame.id)
             );
+            self.event_sink.emit(|| DerivationEvent::FrameDropped {
+                channel_id: frame.id,
+                frame_number: frame.number,
+                reason: FrameDropReason::ChannelTimedOut,
+            });
             return Ok(());
         }
 
         // Ingest the frame. If it fails, ignore the frame.
-        let frame_id = frame.id;
+        let (frame_id, frame_number) = (frame.id, frame.number);
         if current_channel.add_frame(frame, origin).is_err() {
             warn!(target: "channel-bank", "Failed to add frame to channel: {:?}", frame_id);
+            self.event_sink.emit(|| DerivationEvent::FrameDropped {
+                channel_id: frame_id,
+                frame_number,
+                reason: FrameDropReason::Rejected,
+            });
             return Ok(());
         }
+        self.event_sink.emit(|| DerivationEvent::FrameAccepted {
+            channel_id: frame_id,
+            frame_number,
+            origin,
+        });
 
         self.prune()
     }
@@ -137,6 +170,11 @@ where
                 target: "channel-bank",
                 "Channel (ID: {}) timed out", hex::encode(first)
             );
+            self.event_sink.emit(|| DerivationEvent::ChannelTimedOut {
+                channel_id: first,
+                open_block_number: channel.open_block_number(),
+                origin,
+            });
             self.channels.remove(&first);
             self.channel_queue.pop_front();
             return Ok(None);
@@ -244,7 +282,7 @@ where
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{CollectingLayer, TestNextFrameProvider, TraceStorage},
+        test_utils::{CollectingLayer, TestEventSink, TestNextFrameProvider, TraceStorage},
         types::ResetSignal,
     };
     use alloc::{vec, vec::Vec};
@@ -286,6 +324,44 @@ mod tests {
         assert_eq!(err, PipelineError::Eof.temp());
     }
 
+    #[test]
+    fn test_channel_events() {
+        let origin = BlockInfo { number: 10, ..Default::default() };
+        let mut mock = TestNextFrameProvider::new(vec![]);
+        mock.block_info = Some(origin);
+        let cfg = Arc::new(RollupConfig::default());
+        let sink = TestEventSink::default();
+        let mut channel_bank =
+            ChannelBank::new(cfg, mock).with_event_sink(EventSink::new(Arc::new(sink.clone())));
+
+        let id: ChannelId = [0xFF; 16];
+        let frame = Frame { id, number: 0, data: b"seven__".to_vec(), is_last: false };
+        channel_bank.ingest_frame(frame.clone()).unwrap();
+        channel_bank.ingest_frame(frame).unwrap();
+
+        // Time out the channel.
+        channel_bank.prev.block_info = Some(BlockInfo { number: 1_000, ..Default::default() });
+        assert_eq!(channel_bank.read(), Ok(None));
+
+        assert_eq!(
+            sink.events(),
+            [
+                DerivationEvent::ChannelOpened { channel_id: id, origin },
+                DerivationEvent::FrameAccepted { channel_id: id, frame_number: 0, origin },
+                DerivationEvent::FrameDropped {
+                    channel_id: id,
+                    frame_number: 0,
+                    reason: FrameDropReason::Rejected,
+                },
+                DerivationEvent::ChannelTimedOut {
+                    channel_id: id,
+                    open_block_number: 10,
+                    origin: BlockInfo { number: 1_000, ..Default::default() },
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_try_read_channel_at_index() {
         let mock = TestNextFrameProvider::new(vec![]);
@@ -3,15 +3,18 @@
 use super::{ChannelAssembler, ChannelBank, ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider},
+    types::{
+        ChannelProviderSnapshot, ChannelSnapshot, EventSink, PipelineResult, PipelineSnapshot,
+        Signal,
+    },
 };
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
-use kona_protocol::BlockInfo;
+use kona_protocol::{BlockInfo, Channel};
 
 /// The [ChannelProvider] stage is a mux between the [ChannelBank] and [ChannelAssembler] stages.
 ///
@@ -40,6 +43,8 @@ where
     ///
     /// Must be [None] if `prev` or `channel_bank` is [Some].
     channel_assembler: Option<ChannelAssembler<P>>,
+    /// The sink of the frame and channel events, passed on to the active stage.
+    event_sink: EventSink,
 }
 
 impl<P> ChannelProvider<P>
@@ -48,7 +53,19 @@ where
 {
     /// Creates a new [ChannelProvider] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev: Some(prev), channel_bank: None, channel_assembler: None }
+        Self {
+            cfg,
+            prev: Some(prev),
+            channel_bank: None,
+            channel_assembler: None,
+            event_sink: EventSink::none(),
+        }
+    }
+
+    /// Sets the [EventSink] that receives the frame and channel events of the active stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
     }
 
     /// Attempts to update the active stage of the mux.
@@ -58,16 +75,24 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.channel_assembler = Some(ChannelAssembler::new(self.cfg.clone(), prev));
+                self.channel_assembler = Some(
+                    ChannelAssembler::new(self.cfg.clone(), prev)
+                        .with_event_sink(self.event_sink.clone()),
+                );
             } else {
-                self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), prev));
+                self.channel_bank = Some(
+                    ChannelBank::new(self.cfg.clone(), prev)
+                        .with_event_sink(self.event_sink.clone()),
+                );
             }
         } else if self.channel_bank.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the channel bank is active and Holocene is also active, transition to the channel
             // assembler.
             let channel_bank = self.channel_bank.take().expect("Must have channel bank");
-            self.channel_assembler =
-                Some(ChannelAssembler::new(self.cfg.clone(), channel_bank.prev));
+            self.channel_assembler = Some(
+                ChannelAssembler::new(self.cfg.clone(), channel_bank.prev)
+                    .with_event_sink(self.event_sink.clone()),
+            );
         } else if self.channel_assembler.is_some() && !self.cfg.is_holocene_active(origin.timestamp)
         {
             // If the channel assembler is active, and Holocene is not active, it indicates an L1
@@ -75,7 +100,10 @@ where
             // until Holocene re-activates.
             let channel_assembler =
                 self.channel_assembler.take().expect("Must have channel assembler");
-            self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), channel_assembler.prev));
+            self.channel_bank = Some(
+                ChannelBank::new(self.cfg.clone(), channel_assembler.prev)
+                    .with_event_sink(self.event_sink.clone()),
+            );
         }
         Ok(())
     }
@@ -116,6 +144,50 @@ where
     }
 }
 
+impl<P> SnapshotProvider for ChannelProvider<P>
+where
+    P: NextFrameProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + SnapshotProvider
+        + Debug,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        let channel_snapshot = |channel: &Channel| ChannelSnapshot {
+            id: channel.id(),
+            open_block_number: channel.open_block_number(),
+            frames: channel.len(),
+            size: channel.size(),
+            ready: channel.is_ready(),
+        };
+
+        let channels = if let Some(channel_assembler) = self.channel_assembler.as_ref() {
+            channel_assembler.prev.snapshot(snapshot);
+            channel_assembler.channel.iter().map(channel_snapshot).collect()
+        } else if let Some(channel_bank) = self.channel_bank.as_ref() {
+            channel_bank.prev.snapshot(snapshot);
+            channel_bank
+                .channel_queue
+                .iter()
+                .filter_map(|id| channel_bank.channels.get(id))
+                .map(channel_snapshot)
+                .collect()
+        } else {
+            if let Some(prev) = self.prev.as_ref() {
+                prev.snapshot(snapshot);
+            }
+            Vec::new()
+        };
+
+        snapshot.channel_provider = Some(ChannelProviderSnapshot {
+            origin: self.origin(),
+            assembler: self.channel_assembler.is_some(),
+            channels,
+        });
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for ChannelProvider<P>
 where
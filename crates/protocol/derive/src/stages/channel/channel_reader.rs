@@ -3,8 +3,8 @@
 use crate::{
     errors::PipelineError,
     stages::BatchStreamProvider,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider},
+    types::{ChannelReaderSnapshot, PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Bytes;
@@ -140,6 +140,24 @@ where
     }
 }
 
+impl<P> SnapshotProvider for ChannelReader<P>
+where
+    P: ChannelReaderProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + SnapshotProvider
+        + Debug,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        self.prev.snapshot(snapshot);
+        snapshot.channel_reader = Some(ChannelReaderSnapshot {
+            origin: self.origin(),
+            reading: self.next_batch.is_some(),
+        });
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for ChannelReader<P>
 where
@@ -3,8 +3,11 @@
 use crate::{
     errors::PipelineError,
     stages::NextFrameProvider,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider},
+    types::{
+        DerivationEvent, EventSink, FrameDropReason, FrameQueueSnapshot, PipelineResult,
+        PipelineSnapshot, Signal,
+    },
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::Bytes;
@@ -40,6 +43,8 @@ where
     queue: VecDeque<Frame>,
     /// The rollup config.
     rollup_config: Arc<RollupConfig>,
+    /// The sink of the frames dropped by the stage.
+    event_sink: EventSink,
 }
 
 impl<P> FrameQueue<P>
@@ -50,7 +55,25 @@ where
     ///
     /// [L1Retrieval]: crate::stages::L1Retrieval
     pub const fn new(prev: P, cfg: Arc<RollupConfig>) -> Self {
-        Self { prev, queue: VecDeque::new(), rollup_config: cfg }
+        Self { prev, queue: VecDeque::new(), rollup_config: cfg, event_sink: EventSink::none() }
+    }
+
+    /// Sets the [EventSink] that receives the frames dropped by the stage.
+    pub fn with_event_sink(mut self, event_sink: EventSink) -> Self {
+        self.event_sink = event_sink;
+        self
+    }
+
+    /// Removes the frame at the given index from the queue, emitting a
+    /// [DerivationEvent::FrameDropped] event with the given reason.
+    fn drop_frame(&mut self, index: usize, reason: FrameDropReason) {
+        if let Some(frame) = self.queue.remove(index) {
+            self.event_sink.emit(|| DerivationEvent::FrameDropped {
+                channel_id: frame.id,
+                frame_number: frame.number,
+                reason,
+            });
+        }
     }
 
     /// Returns if holocene is active.
@@ -73,19 +96,19 @@ where
             // If the frames are in the same channel, and the frame numbers are not sequential,
             // drop the next frame.
             if extends_channel && prev_frame.number + 1 != next_frame.number {
-                self.queue.remove(i + 1);
+                self.drop_frame(i + 1, FrameDropReason::NonSequential);
                 continue;
             }
 
             // If the frames are in the same channel, and the previous is last, drop the next frame.
             if extends_channel && prev_frame.is_last {
-                self.queue.remove(i + 1);
+                self.drop_frame(i + 1, FrameDropReason::AfterLastFrame);
                 continue;
             }
 
             // If the frames are in different channels, the next frame must be first.
             if !extends_channel && next_frame.number != 0 {
-                self.queue.remove(i + 1);
+                self.drop_frame(i + 1, FrameDropReason::NotFirstFrame);
                 continue;
             }
 
@@ -100,6 +123,13 @@ where
                 // Drain all frames from the previous channel.
                 let drained = self.queue.drain(first_frame..=i);
                 i = i.saturating_sub(drained.len());
+                for frame in drained {
+                    self.event_sink.emit(|| DerivationEvent::FrameDropped {
+                        channel_id: frame.id,
+                        frame_number: frame.number,
+                        reason: FrameDropReason::ChannelSuperseded,
+                    });
+                }
                 continue;
             }
 
@@ -178,6 +208,25 @@ where
     }
 }
 
+impl<P> SnapshotProvider for FrameQueue<P>
+where
+    P: FrameQueueProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + SnapshotProvider
+        + Debug,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        self.prev.snapshot(snapshot);
+        snapshot.frame_queue = Some(FrameQueueSnapshot {
+            origin: self.origin(),
+            queued_frames: self.queue.iter().map(|frame| (frame.id, frame.number)).collect(),
+            queued_bytes: self.queue.iter().map(|frame| frame.data.len()).sum(),
+        });
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for FrameQueue<P>
 where
@@ -193,7 +242,10 @@ where
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
-    use crate::{test_utils::TestFrameQueueProvider, types::ResetSignal};
+    use crate::{
+        test_utils::{TestEventSink, TestFrameQueueProvider},
+        types::ResetSignal,
+    };
     use alloc::vec;
     use kona_genesis::HardForkConfig;
 
@@ -207,6 +259,26 @@ pub(crate) mod tests {
         assert!(frame_queue.prev.reset);
     }
 
+    #[test]
+    fn test_frame_queue_snapshot() {
+        let mut mock = TestFrameQueueProvider::new(vec![]);
+        mock.set_origin(BlockInfo::default());
+        let mut frame_queue = FrameQueue::new(mock, Default::default());
+        frame_queue.queue.push_back(crate::frame!(0xFF, 0, vec![0xDD; 50], false));
+        frame_queue.queue.push_back(crate::frame!(0xFF, 1, vec![0xDD; 20], true));
+
+        let mut snapshot = PipelineSnapshot::default();
+        frame_queue.snapshot(&mut snapshot);
+        assert_eq!(
+            snapshot.frame_queue,
+            Some(FrameQueueSnapshot {
+                origin: Some(BlockInfo::default()),
+                queued_frames: vec![([0xFF; 16], 0), ([0xFF; 16], 1)],
+                queued_bytes: 70,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn test_frame_queue_empty_bytes() {
         let data = vec![Ok(Bytes::from(vec![0x00]))];
@@ -377,6 +449,39 @@ pub(crate) mod tests {
         assert.next_frames().await;
     }
 
+    #[test]
+    fn test_holocene_prune_emits_dropped_frames() {
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let sink = TestEventSink::default();
+        let mut frame_queue = FrameQueue::new(TestFrameQueueProvider::new(vec![]), Arc::new(cfg))
+            .with_event_sink(EventSink::new(Arc::new(sink.clone())));
+        frame_queue.queue.extend([
+            crate::frame!(0xEE, 0, vec![0xDD; 50], false),
+            crate::frame!(0xEE, 2, vec![0xDD; 50], true), // Non-sequential
+            crate::frame!(0xFF, 1, vec![0xDD; 50], true), // Not the first frame
+            crate::frame!(0xAA, 0, vec![0xDD; 50], true), // Supersedes the first channel
+        ]);
+
+        frame_queue.prune(BlockInfo::default());
+        assert_eq!(frame_queue.queue.len(), 1);
+        let dropped = |id, frame_number, reason| DerivationEvent::FrameDropped {
+            channel_id: [id; 16],
+            frame_number,
+            reason,
+        };
+        assert_eq!(
+            sink.events(),
+            [
+                dropped(0xEE, 2, FrameDropReason::NonSequential),
+                dropped(0xFF, 1, FrameDropReason::NotFirstFrame),
+                dropped(0xEE, 0, FrameDropReason::ChannelSuperseded),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_holocene_unclosed_channel() {
         let frames = [
@@ -3,8 +3,13 @@
 use crate::{
     errors::{PipelineError, PipelineErrorKind},
     stages::FrameQueueProvider,
-    traits::{DataAvailabilityProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{ActivationSignal, PipelineResult, ResetSignal, Signal},
+    traits::{
+        DataAvailabilityProvider, OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider,
+    },
+    types::{
+        ActivationSignal, L1RetrievalSnapshot, PipelineResult, PipelineSnapshot, ResetSignal,
+        Signal,
+    },
 };
 use alloc::boxed::Box;
 use alloy_primitives::Address;
@@ -115,6 +120,17 @@ where
     }
 }
 
+impl<DAP, P> SnapshotProvider for L1Retrieval<DAP, P>
+where
+    DAP: DataAvailabilityProvider,
+    P: L1RetrievalProvider + OriginAdvancer + OriginProvider + SignalReceiver + SnapshotProvider,
+{
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        self.prev.snapshot(snapshot);
+        snapshot.l1_retrieval = Some(L1RetrievalSnapshot { next: self.next });
+    }
+}
+
 #[async_trait]
 impl<DAP, P> SignalReceiver for L1Retrieval<DAP, P>
 where
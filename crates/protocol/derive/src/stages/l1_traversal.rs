@@ -3,8 +3,11 @@
 use crate::{
     errors::{PipelineError, ResetError},
     stages::L1RetrievalProvider,
-    traits::{ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{ActivationSignal, PipelineResult, ResetSignal, Signal},
+    traits::{ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider},
+    types::{
+        ActivationSignal, L1TraversalSnapshot, PipelineResult, PipelineSnapshot, ResetSignal,
+        Signal,
+    },
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Address;
@@ -120,6 +123,12 @@ impl<F: ChainProvider> OriginProvider for L1Traversal<F> {
     }
 }
 
+impl<F: ChainProvider> SnapshotProvider for L1Traversal<F> {
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.l1_traversal = Some(L1TraversalSnapshot { origin: self.block, done: self.done });
+    }
+}
+
 #[async_trait]
 impl<F: ChainProvider + Send> SignalReceiver for L1Traversal<F> {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
@@ -0,0 +1,40 @@
+//! L1 blocks and challenge events for testing the Alt-DA challenge tracking.
+
+use crate::sources::{AltDACommitment, ChallengeStatusChanged};
+use alloc::vec;
+use alloy_consensus::{Eip658Value, Receipt};
+use alloy_primitives::{Address, B256, Log, U256, address};
+use alloy_sol_types::SolEvent;
+use kona_protocol::BlockInfo;
+
+/// The address of the DA challenge contract used in tests.
+pub const TEST_CHALLENGE_CONTRACT: Address = address!("1234567890123456789012345678901234567890");
+
+/// Returns the L1 block with the given number. The hashes of the blocks link them into a chain.
+pub fn test_l1_block(number: u64) -> BlockInfo {
+    BlockInfo {
+        number,
+        hash: B256::with_last_byte(number as u8),
+        parent_hash: B256::with_last_byte(number.saturating_sub(1) as u8),
+        ..Default::default()
+    }
+}
+
+/// Returns a successful receipt with a `ChallengeStatusChanged` event of the
+/// [TEST_CHALLENGE_CONTRACT], for the commitment included in the given L1 block.
+pub fn challenge_status_receipt(
+    commitment: &AltDACommitment,
+    inclusion: u64,
+    status: u8,
+) -> Receipt {
+    let event = ChallengeStatusChanged {
+        challengedBlockNumber: U256::from(inclusion),
+        challengedCommitment: commitment.encode(),
+        status,
+    };
+    Receipt {
+        status: Eip658Value::Eip658(true),
+        logs: vec![Log { address: TEST_CHALLENGE_CONTRACT, data: event.encode_log_data() }],
+        ..Default::default()
+    }
+}
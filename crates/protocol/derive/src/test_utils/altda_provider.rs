@@ -0,0 +1,60 @@
+//! An implementation of the [AltDAProvider] trait for tests.
+
+use crate::{
+    errors::{PipelineError, PipelineErrorKind},
+    sources::AltDACommitment,
+    traits::AltDAProvider,
+};
+use alloc::{boxed::Box, string::ToString};
+use alloy_primitives::{Bytes, keccak256, map::HashMap};
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// A mock DA server for testing.
+#[derive(Debug, Clone, Default)]
+pub struct TestAltDAProvider {
+    /// Maps commitments to their inputs.
+    pub inputs: HashMap<AltDACommitment, Bytes>,
+    /// Whether the DA server should return an error.
+    pub should_error: bool,
+}
+
+impl TestAltDAProvider {
+    /// Inserts an input into the mock DA server, returning its keccak256 [AltDACommitment].
+    pub fn insert_input(&mut self, input: Bytes) -> AltDACommitment {
+        let commitment = AltDACommitment::Keccak256(keccak256(&input));
+        self.inputs.insert(commitment.clone(), input);
+        commitment
+    }
+
+    /// Clears the inputs from the mock DA server.
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+    }
+}
+
+/// An error for the [TestAltDAProvider].
+#[derive(Error, Debug)]
+#[error("DA server unavailable")]
+pub struct TestAltDAProviderError;
+
+impl From<TestAltDAProviderError> for PipelineErrorKind {
+    fn from(val: TestAltDAProviderError) -> Self {
+        PipelineError::Provider(val.to_string()).temp()
+    }
+}
+
+#[async_trait]
+impl AltDAProvider for TestAltDAProvider {
+    type Error = TestAltDAProviderError;
+
+    async fn get_input(
+        &mut self,
+        commitment: &AltDACommitment,
+    ) -> Result<Option<Bytes>, Self::Error> {
+        if self.should_error {
+            return Err(TestAltDAProviderError);
+        }
+        Ok(self.inputs.get(commitment).cloned())
+    }
+}
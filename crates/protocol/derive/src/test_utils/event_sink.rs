@@ -0,0 +1,22 @@
+//! A [DerivationEventSink] that collects the events of the pipeline for testing.
+
+use crate::{traits::DerivationEventSink, types::DerivationEvent};
+use alloc::{sync::Arc, vec::Vec};
+use spin::Mutex;
+
+/// A [DerivationEventSink] that collects the emitted [DerivationEvent]s, shared by its clones.
+#[derive(Debug, Default, Clone)]
+pub struct TestEventSink(pub Arc<Mutex<Vec<DerivationEvent>>>);
+
+impl TestEventSink {
+    /// Returns the collected events, in emission order.
+    pub fn events(&self) -> Vec<DerivationEvent> {
+        self.0.lock().clone()
+    }
+}
+
+impl DerivationEventSink for TestEventSink {
+    fn emit(&self, event: DerivationEvent) {
+        self.0.lock().push(event);
+    }
+}
@@ -3,8 +3,8 @@
 use crate::{
     errors::PipelineError,
     stages::FrameQueueProvider,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider},
+    types::{PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, vec::Vec};
 use alloy_primitives::Bytes;
@@ -42,6 +42,10 @@ impl OriginProvider for TestFrameQueueProvider {
     }
 }
 
+impl SnapshotProvider for TestFrameQueueProvider {
+    fn snapshot(&self, _: &mut PipelineSnapshot) {}
+}
+
 #[async_trait]
 impl OriginAdvancer for TestFrameQueueProvider {
     async fn advance_origin(&mut self) -> PipelineResult<()> {
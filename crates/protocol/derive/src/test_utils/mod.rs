@@ -16,6 +16,12 @@ pub use chain_providers::{TestChainProvider, TestL2ChainProvider, TestProviderEr
 mod data_availability_provider;
 pub use data_availability_provider::TestDAP;
 
+mod altda_provider;
+pub use altda_provider::{TestAltDAProvider, TestAltDAProviderError};
+
+mod altda_challenges;
+pub use altda_challenges::{TEST_CHALLENGE_CONTRACT, challenge_status_receipt, test_l1_block};
+
 mod batch_provider;
 pub use batch_provider::TestNextBatchProvider;
 
@@ -37,6 +43,9 @@ pub use channel_reader::TestChannelReaderProvider;
 mod frame_queue;
 pub use frame_queue::TestFrameQueueProvider;
 
+mod event_sink;
+pub use event_sink::TestEventSink;
+
 mod tracing;
 pub use tracing::{CollectingLayer, TraceStorage};
 
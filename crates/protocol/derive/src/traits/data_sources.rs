@@ -1,7 +1,7 @@
 //! Contains traits that describe the functionality of various data sources used in the derivation
 //! pipeline's stages.
 
-use crate::{errors::PipelineErrorKind, types::PipelineResult};
+use crate::{errors::PipelineErrorKind, sources::AltDACommitment, types::PipelineResult};
 use alloc::{boxed::Box, fmt::Debug, string::ToString, vec::Vec};
 use alloy_eips::eip4844::{Blob, IndexedBlobHash};
 use alloy_primitives::{Address, Bytes};
@@ -23,6 +23,21 @@ pub trait BlobProvider {
     ) -> Result<Vec<Box<Blob>>, Self::Error>;
 }
 
+/// The AltDAProvider trait specifies the functionality of a client of an Alt-DA server, which
+/// serves the inputs of the commitments posted by the batcher.
+#[async_trait]
+pub trait AltDAProvider {
+    /// The error type for the [AltDAProvider].
+    type Error: Display + ToString + Into<PipelineErrorKind>;
+
+    /// Fetches the input of the given [AltDACommitment] from the DA server. Returns [None] if the
+    /// DA server does not hold the input.
+    async fn get_input(
+        &mut self,
+        commitment: &AltDACommitment,
+    ) -> Result<Option<Bytes>, Self::Error>;
+}
+
 /// Describes the functionality of a data source that can provide data availability information.
 #[async_trait]
 pub trait DataAvailabilityProvider {
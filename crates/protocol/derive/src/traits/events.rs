@@ -0,0 +1,15 @@
+//! Contains the [DerivationEventSink] trait, which receives the events of the derivation
+//! pipeline.
+
+use crate::types::DerivationEvent;
+use core::fmt::Debug;
+
+/// A sink of the [DerivationEvent]s emitted by the stages of the derivation pipeline, such as
+/// the frames, channels, and batches that are accepted or dropped, and why.
+///
+/// The sink is called synchronously from within the stages, so implementations should be cheap,
+/// e.g. by pushing the events into a channel or a buffer.
+pub trait DerivationEventSink: Debug + Send + Sync {
+    /// Receives a [DerivationEvent].
+    fn emit(&self, event: DerivationEvent);
+}
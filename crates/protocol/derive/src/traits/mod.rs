@@ -11,10 +11,13 @@ mod attributes;
 pub use attributes::{AttributesBuilder, AttributesProvider, NextAttributes};
 
 mod data_sources;
-pub use data_sources::{BlobProvider, DataAvailabilityProvider};
+pub use data_sources::{AltDAProvider, BlobProvider, DataAvailabilityProvider};
+
+mod events;
+pub use events::DerivationEventSink;
 
 mod reset;
 pub use reset::ResetProvider;
 
 mod stages;
-pub use stages::{OriginAdvancer, OriginProvider, SignalReceiver};
+pub use stages::{OriginAdvancer, OriginProvider, SignalReceiver, SnapshotProvider};
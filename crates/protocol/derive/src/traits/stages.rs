@@ -4,7 +4,7 @@ use alloc::boxed::Box;
 use async_trait::async_trait;
 use kona_protocol::BlockInfo;
 
-use crate::types::{PipelineResult, Signal};
+use crate::types::{PipelineResult, PipelineSnapshot, Signal};
 
 /// Providers a way for the pipeline to accept a signal from the driver.
 #[async_trait]
@@ -26,3 +26,10 @@ pub trait OriginAdvancer {
     /// This method is the equivalent of the reference implementation `advance_l1_block`.
     async fn advance_origin(&mut self) -> PipelineResult<()>;
 }
+
+/// Provides a snapshot of the internal state of a stage, to find out where a stalled pipeline is
+/// stuck.
+pub trait SnapshotProvider {
+    /// Records the state of the stage, and of the stages before it, into the [PipelineSnapshot].
+    fn snapshot(&self, snapshot: &mut PipelineSnapshot);
+}
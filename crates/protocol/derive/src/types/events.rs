@@ -0,0 +1,144 @@
+//! Events emitted by the stages of the derivation pipeline.
+
+use crate::traits::DerivationEventSink;
+use alloc::sync::Arc;
+use kona_protocol::{Batch, BatchValidity, BlockInfo, ChannelId};
+
+/// An event in the lifecycle of a frame, channel, or batch, emitted by the stages of the
+/// derivation pipeline to a [DerivationEventSink].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "camelCase"))]
+pub enum DerivationEvent {
+    /// A frame was added to its channel.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    FrameAccepted {
+        /// The id of the channel of the frame.
+        channel_id: ChannelId,
+        /// The number of the frame.
+        frame_number: u16,
+        /// The L1 origin at which the frame was added.
+        origin: BlockInfo,
+    },
+    /// A frame was dropped.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    FrameDropped {
+        /// The id of the channel of the frame.
+        channel_id: ChannelId,
+        /// The number of the frame.
+        frame_number: u16,
+        /// The reason the frame was dropped.
+        reason: FrameDropReason,
+    },
+    /// A new channel was opened.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    ChannelOpened {
+        /// The id of the channel.
+        channel_id: ChannelId,
+        /// The L1 origin at which the channel was opened.
+        origin: BlockInfo,
+    },
+    /// A channel was not completed within the channel timeout, and was discarded.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    ChannelTimedOut {
+        /// The id of the channel.
+        channel_id: ChannelId,
+        /// The number of the L1 block in which the channel was opened.
+        open_block_number: u64,
+        /// The L1 origin at which the channel timed out.
+        origin: BlockInfo,
+    },
+    /// A channel was discarded before it was read, because it exceeded the size limits.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    ChannelPruned {
+        /// The id of the channel.
+        channel_id: ChannelId,
+        /// The size of the channel.
+        size: usize,
+    },
+    /// A batch was checked against the L2 safe head, with the given [BatchValidity]. Batches
+    /// that are buffered to be checked again, because they are future or undecided, are
+    /// reported when they are first checked, and again once the final decision is made.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    BatchChecked {
+        /// The timestamp of the batch. For span batches, this is the timestamp of the first
+        /// block of the span.
+        timestamp: u64,
+        /// Whether the batch is a span batch.
+        span: bool,
+        /// The L1 origin at which the batch was read, which the batch is checked against as its
+        /// inclusion block.
+        origin: BlockInfo,
+        /// The validity of the batch.
+        validity: BatchValidity,
+    },
+    /// A span batch was expanded into single batches.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    SpanBatchExpanded {
+        /// The timestamp of the first block of the span.
+        timestamp: u64,
+        /// The number of single batches in the span.
+        batches: usize,
+    },
+}
+
+impl DerivationEvent {
+    /// Returns a [DerivationEvent::BatchChecked] event for the given batch.
+    pub fn batch_checked(batch: &Batch, origin: BlockInfo, validity: BatchValidity) -> Self {
+        Self::BatchChecked {
+            timestamp: batch.timestamp(),
+            span: matches!(batch, Batch::Span(_)),
+            origin,
+            validity,
+        }
+    }
+}
+
+/// The reason a frame was dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum FrameDropReason {
+    /// The frame does not directly follow the previous frame of its channel. Holocene only.
+    NonSequential,
+    /// The frame follows the last frame of its channel. Holocene only.
+    AfterLastFrame,
+    /// The frame is the first frame of its channel in the queue, but is not numbered 0. Holocene
+    /// only.
+    NotFirstFrame,
+    /// The channel of the frame was superseded by a new channel before it was closed. Holocene
+    /// only.
+    ChannelSuperseded,
+    /// The channel of the frame timed out.
+    ChannelTimedOut,
+    /// There is no open channel for the frame. Holocene only.
+    NoOpenChannel,
+    /// The frame could not be added to its channel, e.g. because it is a duplicate or the
+    /// channel is closed.
+    Rejected,
+}
+
+/// An optional, shared handle to the [DerivationEventSink] of the pipeline, held by each stage
+/// that emits [DerivationEvent]s.
+#[derive(Debug, Clone, Default)]
+pub struct EventSink(Option<Arc<dyn DerivationEventSink>>);
+
+impl EventSink {
+    /// Returns an [EventSink] without a [DerivationEventSink], which drops all events.
+    pub const fn none() -> Self {
+        Self(None)
+    }
+
+    /// Creates a new [EventSink] that emits events to the given [DerivationEventSink].
+    pub fn new(sink: Arc<dyn DerivationEventSink>) -> Self {
+        Self(Some(sink))
+    }
+
+    /// Emits the event built by `event`, if a [DerivationEventSink] is set. The event is only
+    /// built if it is emitted.
+    pub fn emit(&self, event: impl FnOnce() -> DerivationEvent) {
+        if let Some(sink) = self.0.as_ref() {
+            sink.emit(event());
+        }
+    }
+}
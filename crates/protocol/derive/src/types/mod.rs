@@ -5,3 +5,13 @@ pub use results::{PipelineResult, StepResult};
 
 mod signals;
 pub use signals::{ActivationSignal, ResetSignal, Signal};
+
+mod events;
+pub use events::{DerivationEvent, EventSink, FrameDropReason};
+
+mod snapshot;
+pub use snapshot::{
+    AttributesQueueSnapshot, BatchProviderSnapshot, BatchStreamSnapshot, ChannelProviderSnapshot,
+    ChannelReaderSnapshot, ChannelSnapshot, FrameQueueSnapshot, L1RetrievalSnapshot,
+    L1TraversalSnapshot, PipelineSnapshot,
+};
@@ -0,0 +1,189 @@
+//! Snapshot types describing the internal state of the derivation pipeline stages.
+
+use alloc::vec::Vec;
+use kona_protocol::{BlockInfo, ChannelId};
+
+/// A snapshot of the internal state of the [DerivationPipeline] and each of its stages, used to
+/// find out where a stalled pipeline is stuck.
+///
+/// Each stage records its own state through the [SnapshotProvider] trait. Stages that are not
+/// part of the pipeline, or that do not record their state, are left as [None].
+///
+/// [DerivationPipeline]: crate::pipeline::DerivationPipeline
+/// [SnapshotProvider]: crate::traits::SnapshotProvider
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PipelineSnapshot {
+    /// The L1 origin of the pipeline.
+    pub origin: Option<BlockInfo>,
+    /// The number of prepared attributes that were not yet consumed.
+    pub prepared_attributes: usize,
+    /// The state of the [L1Traversal] stage.
+    ///
+    /// [L1Traversal]: crate::stages::L1Traversal
+    pub l1_traversal: Option<L1TraversalSnapshot>,
+    /// The state of the [L1Retrieval] stage.
+    ///
+    /// [L1Retrieval]: crate::stages::L1Retrieval
+    pub l1_retrieval: Option<L1RetrievalSnapshot>,
+    /// The state of the [FrameQueue] stage.
+    ///
+    /// [FrameQueue]: crate::stages::FrameQueue
+    pub frame_queue: Option<FrameQueueSnapshot>,
+    /// The state of the [ChannelProvider] stage.
+    ///
+    /// [ChannelProvider]: crate::stages::ChannelProvider
+    pub channel_provider: Option<ChannelProviderSnapshot>,
+    /// The state of the [ChannelReader] stage.
+    ///
+    /// [ChannelReader]: crate::stages::ChannelReader
+    pub channel_reader: Option<ChannelReaderSnapshot>,
+    /// The state of the [BatchStream] stage.
+    ///
+    /// [BatchStream]: crate::stages::BatchStream
+    pub batch_stream: Option<BatchStreamSnapshot>,
+    /// The state of the [BatchProvider] stage.
+    ///
+    /// [BatchProvider]: crate::stages::BatchProvider
+    pub batch_provider: Option<BatchProviderSnapshot>,
+    /// The state of the [AttributesQueue] stage.
+    ///
+    /// [AttributesQueue]: crate::stages::AttributesQueue
+    pub attributes_queue: Option<AttributesQueueSnapshot>,
+}
+
+/// A snapshot of the [L1Traversal] stage.
+///
+/// [L1Traversal]: crate::stages::L1Traversal
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct L1TraversalSnapshot {
+    /// The current L1 block of the traversal.
+    pub origin: Option<BlockInfo>,
+    /// Whether the current L1 block was already handed to the next stage.
+    pub done: bool,
+}
+
+/// A snapshot of the [L1Retrieval] stage.
+///
+/// [L1Retrieval]: crate::stages::L1Retrieval
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct L1RetrievalSnapshot {
+    /// The L1 block whose data is being retrieved, if any.
+    pub next: Option<BlockInfo>,
+}
+
+/// A snapshot of the [FrameQueue] stage.
+///
+/// [FrameQueue]: crate::stages::FrameQueue
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FrameQueueSnapshot {
+    /// The L1 origin of the stage.
+    pub origin: Option<BlockInfo>,
+    /// The channel ids and frame numbers of the queued frames, in queue order.
+    pub queued_frames: Vec<(ChannelId, u16)>,
+    /// The total size of the data of the queued frames.
+    pub queued_bytes: usize,
+}
+
+/// A snapshot of a channel that is being assembled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ChannelSnapshot {
+    /// The id of the channel.
+    pub id: ChannelId,
+    /// The number of the L1 block in which the channel was opened.
+    pub open_block_number: u64,
+    /// The number of frames received for the channel.
+    pub frames: usize,
+    /// The size of the channel, as counted towards the channel bank size limit.
+    pub size: usize,
+    /// Whether all frames of the channel were received.
+    pub ready: bool,
+}
+
+/// A snapshot of the [ChannelProvider] stage.
+///
+/// [ChannelProvider]: crate::stages::ChannelProvider
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ChannelProviderSnapshot {
+    /// The L1 origin of the stage.
+    pub origin: Option<BlockInfo>,
+    /// Whether the channel assembler is active, rather than the channel bank. The channel
+    /// assembler replaces the channel bank after the Holocene hardfork.
+    pub assembler: bool,
+    /// The open channels, oldest first.
+    pub channels: Vec<ChannelSnapshot>,
+}
+
+/// A snapshot of the [ChannelReader] stage.
+///
+/// [ChannelReader]: crate::stages::ChannelReader
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ChannelReaderSnapshot {
+    /// The L1 origin of the stage.
+    pub origin: Option<BlockInfo>,
+    /// Whether the stage is reading batches from a channel.
+    pub reading: bool,
+}
+
+/// A snapshot of the [BatchStream] stage.
+///
+/// [BatchStream]: crate::stages::BatchStream
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BatchStreamSnapshot {
+    /// The L1 origin of the stage.
+    pub origin: Option<BlockInfo>,
+    /// Whether a span batch is staged.
+    pub span_batch: bool,
+    /// The number of buffered single batches derived from the staged span batch.
+    pub buffered_batches: usize,
+}
+
+/// A snapshot of the [BatchProvider] stage.
+///
+/// [BatchProvider]: crate::stages::BatchProvider
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BatchProviderSnapshot {
+    /// The L1 origin of the stage.
+    pub origin: Option<BlockInfo>,
+    /// Whether the batch validator is active, rather than the batch queue. The batch validator
+    /// replaces the batch queue after the Holocene hardfork.
+    pub validator: bool,
+    /// The window of L1 blocks that the L1 origins of the unsafe L2 blocks are in.
+    pub l1_blocks: Vec<BlockInfo>,
+    /// The number of buffered batches that were not yet validated.
+    pub buffered_batches: usize,
+    /// The number of single batches derived from a span batch that were not yet consumed.
+    pub buffered_span_batches: usize,
+}
+
+/// A snapshot of the [AttributesQueue] stage.
+///
+/// [AttributesQueue]: crate::stages::AttributesQueue
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AttributesQueueSnapshot {
+    /// The L1 origin of the stage.
+    pub origin: Option<BlockInfo>,
+    /// The timestamp of the batch that is being turned into attributes, if any.
+    pub batch_timestamp: Option<u64>,
+    /// Whether the current batch is the last in its span.
+    pub is_last_in_span: bool,
+}
@@ -1,5 +1,7 @@
 //! Span Batch Errors
 
+use crate::BrotliDecompressionError;
+
 /// Span Batch Errors
 #[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
 pub enum SpanBatchError {
@@ -45,6 +47,34 @@ pub enum BatchDecodingError {
     SpanBatchError(#[from] SpanBatchError),
 }
 
+/// An error reading a batch from the data of a channel with the [BatchReader].
+///
+/// [BatchReader]: crate::BatchReader
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BatchReaderError {
+    /// The channel data uses an unknown compression type
+    #[error("Unknown compression type: {0}")]
+    UnknownCompressionType(u8),
+    /// Error decompressing the zlib channel data
+    #[error("Failed to decompress zlib channel data")]
+    ZlibDecompression,
+    /// Error decompressing the brotli channel data
+    #[error("Failed to decompress brotli channel data: {0}")]
+    BrotliDecompression(#[from] BrotliDecompressionError),
+    /// The decompressed channel data exceeds the maximum RLP bytes per channel
+    #[error("Decompressed channel data too large: {0} bytes")]
+    ChannelTooLarge(usize),
+    /// Error decoding the RLP bytes of a batch
+    #[error("Error decoding the batch RLP bytes: {0}")]
+    AlloyRlpError(alloy_rlp::Error),
+    /// Error decoding a batch
+    #[error("Error decoding a batch: {0}")]
+    BatchDecodingError(#[from] BatchDecodingError),
+    /// The channel data is brotli compressed before the Fjord hardfork
+    #[error("Brotli compression used before the Fjord hardfork")]
+    BrotliBeforeFjord,
+}
+
 /// Decoding Error
 #[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
 pub enum SpanDecodingError {
@@ -25,7 +25,9 @@ mod inclusion;
 pub use inclusion::BatchWithInclusionBlock;
 
 mod errors;
-pub use errors::{BatchDecodingError, BatchEncodingError, SpanBatchError, SpanDecodingError};
+pub use errors::{
+    BatchDecodingError, BatchEncodingError, BatchReaderError, SpanBatchError, SpanDecodingError,
+};
 
 mod bits;
 pub use bits::SpanBatchBits;
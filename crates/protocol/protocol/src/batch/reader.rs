@@ -1,6 +1,6 @@
 //! Contains the [BatchReader] which is used to iteratively consume batches from raw data.
 
-use crate::{Batch, decompress_brotli};
+use crate::{Batch, BatchReaderError, decompress_brotli};
 use alloc::vec::Vec;
 use alloy_primitives::Bytes;
 use alloy_rlp::Decodable;
@@ -47,49 +47,61 @@ impl BatchReader {
 
     /// Pulls out the next batch from the reader.
     pub fn next_batch(&mut self, cfg: &RollupConfig) -> Option<Batch> {
+        self.try_next_batch(cfg).ok().flatten()
+    }
+
+    /// Pulls out the next batch from the reader, returning a [BatchReaderError] if the channel
+    /// data or the batch cannot be decoded. Returns `Ok(None)` once all batches were read.
+    pub fn try_next_batch(
+        &mut self,
+        cfg: &RollupConfig,
+    ) -> Result<Option<Batch>, BatchReaderError> {
         // If the data is not already decompressed, decompress it.
         let mut brotli_used = false;
 
         if let Some(data) = self.data.take() {
             // Peek at the data to determine the compression type.
             if data.is_empty() {
-                return None;
+                return Ok(None);
             }
 
             let compression_type = data[0];
             if (compression_type & 0x0F) == ZLIB_DEFLATE_COMPRESSION_METHOD ||
                 (compression_type & 0x0F) == ZLIB_RESERVED_COMPRESSION_METHOD
             {
-                self.decompressed = decompress_to_vec_zlib(&data).ok()?;
+                self.decompressed = decompress_to_vec_zlib(&data)
+                    .map_err(|_| BatchReaderError::ZlibDecompression)?;
 
                 // Check the size of the decompressed channel RLP.
                 if self.decompressed.len() > self.max_rlp_bytes_per_channel {
-                    return None;
+                    return Err(BatchReaderError::ChannelTooLarge(self.decompressed.len()));
                 }
             } else if compression_type == CHANNEL_VERSION_BROTLI {
                 brotli_used = true;
-                self.decompressed =
-                    decompress_brotli(&data[1..], self.max_rlp_bytes_per_channel).ok()?;
+                self.decompressed = decompress_brotli(&data[1..], self.max_rlp_bytes_per_channel)?;
             } else {
-                return None;
+                return Err(BatchReaderError::UnknownCompressionType(compression_type));
             }
         }
 
+        // Stop once all of the decompressed data was read.
+        if self.cursor >= self.decompressed.len() {
+            return Ok(None);
+        }
+
         // Decompress and RLP decode the batch data, before finally decoding the batch itself.
         let decompressed_reader = &mut self.decompressed.as_slice()[self.cursor..].as_ref();
-        let bytes = Bytes::decode(decompressed_reader).ok()?;
-        let Ok(batch) = Batch::decode(&mut bytes.as_ref(), cfg) else {
-            return None;
-        };
+        let bytes = Bytes::decode(decompressed_reader).map_err(BatchReaderError::AlloyRlpError)?;
+        let batch = Batch::decode(&mut bytes.as_ref(), cfg)?;
 
         // Confirm that brotli decompression was performed *after* the Fjord hardfork.
         if brotli_used && !cfg.is_fjord_active(batch.timestamp()) {
-            return None;
+            return Err(BatchReaderError::BrotliBeforeFjord);
         }
 
         // Advance the cursor on the reader.
         self.cursor = self.decompressed.len() - decompressed_reader.len();
-        Some(batch)
+        Ok(Some(batch))
     }
 }
 
@@ -130,4 +142,34 @@ mod test {
             .unwrap();
         assert_eq!(reader.cursor, decompressed_len);
     }
+
+    #[test]
+    fn test_try_next_batch_until_exhausted() {
+        let raw = new_compressed_batch_data();
+        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        let cfg = RollupConfig::default();
+        assert!(reader.try_next_batch(&cfg).unwrap().is_some());
+        assert_eq!(reader.try_next_batch(&cfg), Ok(None));
+    }
+
+    #[test]
+    fn test_try_next_batch_unknown_compression_type() {
+        let mut reader =
+            BatchReader::new(alloc::vec![0x02, 0x00], MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        assert_eq!(
+            reader.try_next_batch(&RollupConfig::default()),
+            Err(BatchReaderError::UnknownCompressionType(0x02))
+        );
+    }
+
+    #[test]
+    fn test_try_next_batch_channel_too_large() {
+        let raw = new_compressed_batch_data();
+        let decompressed_len = decompress_to_vec_zlib(&raw).unwrap().len();
+        let mut reader = BatchReader::new(raw, decompressed_len - 1);
+        assert_eq!(
+            reader.try_next_batch(&RollupConfig::default()),
+            Err(BatchReaderError::ChannelTooLarge(decompressed_len))
+        );
+    }
 }